@@ -64,6 +64,8 @@ pub const DEVSERVER_PORT_ENV: &str = "DIOXUS_DEVSERVER_PORT";
 pub const ALWAYS_ON_TOP_ENV: &str = "DIOXUS_ALWAYS_ON_TOP";
 pub const ASSET_ROOT_ENV: &str = "DIOXUS_ASSET_ROOT";
 pub const APP_TITLE_ENV: &str = "DIOXUS_APP_TITLE";
+pub const WEB_HISTORY_ENV: &str = "DIOXUS_WEB_HISTORY";
+pub const CSRF_PROTECTION_ENV: &str = "DIOXUS_CSRF_PROTECTION";
 
 #[deprecated(since = "0.6.0", note = "The CLI currently does not set this.")]
 #[doc(hidden)]
@@ -193,6 +195,13 @@ pub fn app_title() -> Option<String> {
     read_env_config!("DIOXUS_APP_TITLE")
 }
 
+/// Check if the server should enable CSRF protection for server functions, usually set by the Dioxus.toml.
+///
+/// This is used as the default of the fullstack server config if the app itself doesn't set it.
+pub fn csrf_protection() -> Option<bool> {
+    read_env_config!("DIOXUS_CSRF_PROTECTION").and_then(|s| s.parse().ok())
+}
+
 /// Check if the application should forced to "float" on top of other windows.
 ///
 /// The CLI sets this based on the `--always-on-top` flag and the settings system.
@@ -275,6 +284,34 @@ pub fn format_base_path_meta_element(base_path: &str) -> String {
     format!(r#"<meta name="{ASSET_ROOT_ENV}" content="{base_path}">"#,)
 }
 
+/// Get the history the web app should use from the `history` option in the `[web.app]` section of
+/// Dioxus.toml. This is either `"web"` or `"hash"`.
+///
+/// This uses wasm_bindgen on the browser to extract the history from a meta element.
+#[cfg(feature = "web")]
+pub fn web_history() -> Option<String> {
+    // In debug mode, we get the history from the meta element which can be changed without recompiling
+    #[cfg(debug_assertions)]
+    {
+        thread_local! {
+            static HISTORY: std::cell::OnceCell<Option<String>> = const { std::cell::OnceCell::new() };
+        }
+        HISTORY.with(|f| f.get_or_init(|| get_meta_contents(WEB_HISTORY_ENV)).clone())
+    }
+
+    // In release mode, we get the history from the environment variable
+    #[cfg(not(debug_assertions))]
+    {
+        option_env!("DIOXUS_WEB_HISTORY").map(ToString::to_string)
+    }
+}
+
+/// Format a meta element for the web history to be used in the output HTML
+#[doc(hidden)]
+pub fn format_web_history_meta_element(history: &str) -> String {
+    format!(r#"<meta name="{WEB_HISTORY_ENV}" content="{history}">"#,)
+}
+
 /// Get the path to the output directory where the application is being built.
 ///
 /// This might not return a valid path - we don't recommend relying on this.
@@ -42,14 +42,18 @@ use object::{File, Object, ObjectSection, ObjectSymbol, ReadCache, ReadRef, Sect
 use pdb::FallibleIterator;
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
-/// Extract all manganis symbols and their sections from the given object file.
-fn manganis_symbols<'a, 'b, R: ReadRef<'a>>(
+/// The prefix of the symbols the `asset!()` macro embeds the assets in
+const MANGANIS_SYMBOL: &str = "__MANGANIS__";
+
+/// Extract all symbols with the given prefix and their sections from the given object file.
+fn prefixed_symbols<'a, 'b, R: ReadRef<'a>>(
     file: &'b File<'a, R>,
+    prefix: &'b str,
 ) -> impl Iterator<Item = (Symbol<'a, 'b, R>, Section<'a, 'b, R>)> + 'b {
     file.symbols()
-        .filter(|symbol| {
+        .filter(move |symbol| {
             if let Ok(name) = symbol.name() {
-                looks_like_manganis_symbol(name)
+                name.contains(prefix)
             } else {
                 false
             }
@@ -61,28 +65,26 @@ fn manganis_symbols<'a, 'b, R: ReadRef<'a>>(
         })
 }
 
-fn looks_like_manganis_symbol(name: &str) -> bool {
-    name.contains("__MANGANIS__")
-}
-
-/// Find the offsets of any manganis symbols in the given file.
-fn find_symbol_offsets<'a, R: ReadRef<'a>>(
+/// Find the offsets of any symbols with the given prefix in the given file. This is used for the
+/// `__MANGANIS__` asset symbols and the `__DIOXUS_SITEMAP__` symbols of the router.
+pub(crate) fn find_symbol_offsets<'a, R: ReadRef<'a>>(
     path: &Path,
     file_contents: &[u8],
     file: &File<'a, R>,
+    prefix: &str,
 ) -> Result<Vec<u64>> {
     let pdb_file = find_pdb_file(path);
 
     match file.format() {
         // We need to handle dynamic offsets in wasm files differently
-        object::BinaryFormat::Wasm => find_wasm_symbol_offsets(file_contents, file),
+        object::BinaryFormat::Wasm => find_wasm_symbol_offsets(file_contents, file, prefix),
         // Windows puts the symbol information in a PDB file alongside the executable.
         // If this is a windows PE file and we found a PDB file, we will use that to find the symbol offsets.
         object::BinaryFormat::Pe if pdb_file.is_some() => {
-            find_pdb_symbol_offsets(&pdb_file.unwrap())
+            find_pdb_symbol_offsets(&pdb_file.unwrap(), prefix)
         }
-        // Otherwise, look for manganis symbols in the object file.
-        _ => find_native_symbol_offsets(file),
+        // Otherwise, look for the symbols in the object file.
+        _ => find_native_symbol_offsets(file, prefix),
     }
 }
 
@@ -117,8 +119,8 @@ fn find_pdb_file(path: &Path) -> Option<PathBuf> {
     }
 }
 
-/// Find the offsets of any manganis symbols in a pdb file.
-fn find_pdb_symbol_offsets(pdb_file: &Path) -> Result<Vec<u64>> {
+/// Find the offsets of any symbols with the given prefix in a pdb file.
+fn find_pdb_symbol_offsets(pdb_file: &Path, prefix: &str) -> Result<Vec<u64>> {
     let pdb_file_handle = std::fs::File::open(pdb_file)?;
     let mut pdb_file = pdb::PDB::open(pdb_file_handle).context("Failed to open PDB file")?;
     let Ok(Some(sections)) = pdb_file.sections() else {
@@ -142,7 +144,7 @@ fn find_pdb_symbol_offsets(pdb_file: &Path) -> Result<Vec<u64>> {
         };
 
         let name = data.name.to_string();
-        if name.contains("__MANGANIS__") {
+        if name.contains(prefix) {
             let section = sections
                 .get(rva.section as usize - 1)
                 .expect("Section index out of bounds");
@@ -153,15 +155,18 @@ fn find_pdb_symbol_offsets(pdb_file: &Path) -> Result<Vec<u64>> {
     Ok(addresses)
 }
 
-/// Find the offsets of any manganis symbols in a native object file.
-fn find_native_symbol_offsets<'a, R: ReadRef<'a>>(file: &File<'a, R>) -> Result<Vec<u64>> {
+/// Find the offsets of any symbols with the given prefix in a native object file.
+fn find_native_symbol_offsets<'a, R: ReadRef<'a>>(
+    file: &File<'a, R>,
+    prefix: &str,
+) -> Result<Vec<u64>> {
     let mut offsets = Vec::new();
-    for (symbol, section) in manganis_symbols(file) {
+    for (symbol, section) in prefixed_symbols(file, prefix) {
         let virtual_address = symbol.address();
 
         let Some((section_range_start, _)) = section.file_range() else {
             tracing::error!(
-                "Found __MANGANIS__ symbol {:?} in section {}, but the section has no file range",
+                "Found {prefix} symbol {:?} in section {}, but the section has no file range",
                 symbol.name(),
                 section.index()
             );
@@ -194,10 +199,11 @@ fn eval_walrus_global_expr(module: &walrus::Module, expr: &walrus::ConstExpr) ->
     }
 }
 
-/// Find the offsets of any manganis symbols in the wasm file.
+/// Find the offsets of any symbols with the given prefix in the wasm file.
 fn find_wasm_symbol_offsets<'a, R: ReadRef<'a>>(
     file_contents: &[u8],
     file: &File<'a, R>,
+    prefix: &str,
 ) -> Result<Vec<u64>> {
     let Some(section) = file
         .sections()
@@ -259,7 +265,7 @@ fn find_wasm_symbol_offsets<'a, R: ReadRef<'a>>(
         eval_walrus_global_expr(&module, &main_memory_offset).unwrap_or_default();
 
     for export in module.exports.iter() {
-        if !looks_like_manganis_symbol(&export.name) {
+        if !export.name.contains(prefix) {
             continue;
         }
 
@@ -273,7 +279,7 @@ fn find_wasm_symbol_offsets<'a, R: ReadRef<'a>>(
 
         let Some(virtual_address) = eval_walrus_global_expr(&module, &pointer) else {
             tracing::error!(
-                "Found __MANGANIS__ symbol {:?} in WASM file, but the global expression could not be evaluated",
+                "Found {prefix} symbol {:?} in WASM file, but the global expression could not be evaluated",
                 export.name
             );
             continue;
@@ -301,7 +307,7 @@ pub(crate) fn extract_assets_from_file(path: impl AsRef<Path>) -> Result<AssetMa
     let mut reader = Cursor::new(&file_contents);
     let read_cache = ReadCache::new(&mut reader);
     let object_file = object::File::parse(&read_cache)?;
-    let offsets = find_symbol_offsets(path, &file_contents, &object_file)?;
+    let offsets = find_symbol_offsets(path, &file_contents, &object_file, MANGANIS_SYMBOL)?;
 
     let mut assets = Vec::new();
 
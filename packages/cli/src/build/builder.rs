@@ -492,6 +492,13 @@ impl AppBuilder {
             ));
         }
 
+        if krate.config.web.app.csrf_protection {
+            envs.push((
+                dioxus_cli_config::CSRF_PROTECTION_ENV.into(),
+                "true".to_string(),
+            ));
+        }
+
         if let Some(env_filter) = env::var_os("RUST_LOG").and_then(|e| e.into_string().ok()) {
             envs.push(("RUST_LOG".into(), env_filter));
         }
@@ -14,6 +14,7 @@ mod context;
 mod patch;
 mod pre_render;
 mod request;
+mod sitemap;
 mod tools;
 
 pub(crate) use assets::*;
@@ -22,4 +23,5 @@ pub(crate) use context::*;
 pub(crate) use patch::*;
 pub(crate) use pre_render::*;
 pub(crate) use request::*;
+pub(crate) use sitemap::*;
 pub(crate) use tools::*;
@@ -6,7 +6,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     time::Duration,
 };
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 
 use crate::BuildId;
 
@@ -26,12 +26,7 @@ pub(crate) async fn pre_render_static_routes(
             .unwrap();
     }
     let server_exe = builder.build.main_exe();
-
-    // Use the address passed in through environment variables or default to localhost:9999. We need
-    // to default to a value that is different than the CLI default address to avoid conflicts
-    let ip = server_ip().unwrap_or_else(|| IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
-    let port = server_port().unwrap_or(9999);
-    let fullstack_address = SocketAddr::new(ip, port);
+    let (_child, fullstack_address) = spawn_build_server(devserver_ip, builder)?;
     let address = fullstack_address.ip().to_string();
     let port = fullstack_address.port().to_string();
 
@@ -41,63 +36,18 @@ pub(crate) async fn pre_render_static_routes(
 
     tracing::info!("Running SSG at http://{address}:{port} for {server_exe:?}");
 
-    let vars = builder.child_environment_variables(
-        devserver_ip,
-        Some(fullstack_address),
-        false,
-        BuildId::SERVER,
-    );
-    // Run the server executable
-    let _child = Command::new(&server_exe)
-        .envs(vars)
-        .current_dir(server_exe.parent().unwrap())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .kill_on_drop(true)
-        .spawn()?;
-
     // Borrow reqwest_client so we only move the reference into the futures
     let reqwest_client = reqwest::Client::new();
     let reqwest_client = &reqwest_client;
 
     // Get the routes from the `/static_routes` endpoint
-    let mut routes = None;
-
-    // The server may take a few seconds to start up. Try fetching the route up to 5 times with a one second delay
-    const RETRY_ATTEMPTS: usize = 5;
-    for i in 0..=RETRY_ATTEMPTS {
-        tracing::debug!(
-            "Attempting to get static routes from server. Attempt {i} of {RETRY_ATTEMPTS}"
-        );
-
-        let request = reqwest_client
-            .post(format!("http://{address}:{port}/api/static_routes"))
-            .body("{}".to_string())
-            .send()
-            .await;
-        match request {
-            Ok(request) => {
-                routes = Some(request
-                    .json::<Vec<String>>()
-                    .await
-                    .inspect(|text| tracing::debug!("Got static routes: {text:?}"))
-                    .context("Failed to parse static routes from the server. Make sure your server function returns Vec<String> with the (default) json encoding")?);
-                break;
-            }
-            Err(err) => {
-                // If the request fails, try  up to 5 times with a one second delay
-                // If it fails 5 times, return the error
-                if i == RETRY_ATTEMPTS {
-                    return Err(err).context("Failed to get static routes from server. Make sure you have a server function at the `/api/static_routes` endpoint that returns Vec<String> of static routes.");
-                }
-                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-            }
-        }
-    }
-
-    let routes = routes.expect(
-        "static routes should exist or an error should have been returned on the last attempt",
-    );
+    let routes = call_server_fn(reqwest_client, fullstack_address, "static_routes")
+        .await
+        .context("Failed to get static routes from server. Make sure you have a server function at the `/api/static_routes` endpoint that returns Vec<String> of static routes.")?
+        .json::<Vec<String>>()
+        .await
+        .inspect(|text| tracing::debug!("Got static routes: {text:?}"))
+        .context("Failed to parse static routes from the server. Make sure your server function returns Vec<String> with the (default) json encoding")?;
 
     // Create a pool of futures that cache each route
     let mut resolved_routes = routes
@@ -146,3 +96,66 @@ pub(crate) async fn pre_render_static_routes(
 
     Ok(())
 }
+
+/// Start the server executable of a fullstack build so the CLI can call its server functions. The
+/// server is killed when the returned child is dropped.
+pub(crate) fn spawn_build_server(
+    devserver_ip: Option<SocketAddr>,
+    builder: &mut AppBuilder,
+) -> anyhow::Result<(Child, SocketAddr)> {
+    let server_exe = builder.build.main_exe();
+
+    // Use the address passed in through environment variables or default to localhost:9999. We need
+    // to default to a value that is different than the CLI default address to avoid conflicts
+    let ip = server_ip().unwrap_or_else(|| IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+    let port = server_port().unwrap_or(9999);
+    let fullstack_address = SocketAddr::new(ip, port);
+
+    let vars = builder.child_environment_variables(
+        devserver_ip,
+        Some(fullstack_address),
+        false,
+        BuildId::SERVER,
+    );
+    // Run the server executable
+    let child = Command::new(&server_exe)
+        .envs(vars)
+        .current_dir(server_exe.parent().unwrap())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    Ok((child, fullstack_address))
+}
+
+/// Call a server function without arguments at `/api/{endpoint}` on a server started with
+/// [`spawn_build_server`].
+pub(crate) async fn call_server_fn(
+    reqwest_client: &reqwest::Client,
+    address: SocketAddr,
+    endpoint: &str,
+) -> Result<reqwest::Response, reqwest::Error> {
+    // The server may take a few seconds to start up. Try fetching the route up to 5 times with a one second delay
+    const RETRY_ATTEMPTS: usize = 5;
+    let mut i = 0;
+    loop {
+        tracing::debug!(
+            "Attempting to call /api/{endpoint} on the server. Attempt {i} of {RETRY_ATTEMPTS}"
+        );
+
+        let request = reqwest_client
+            .post(format!("http://{address}/api/{endpoint}"))
+            .body("{}".to_string())
+            .send()
+            .await;
+        match request {
+            Ok(response) => return Ok(response),
+            // If the request fails, try  up to 5 times with a one second delay
+            // If it fails 5 times, return the error
+            Err(err) if i == RETRY_ATTEMPTS => return Err(err),
+            Err(_) => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+        i += 1;
+    }
+}
@@ -323,8 +323,8 @@ use crate::{
 use anyhow::{bail, Context};
 use cargo_metadata::diagnostic::Diagnostic;
 use depinfo::RustcDepInfo;
-use dioxus_cli_config::format_base_path_meta_element;
-use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV};
+use dioxus_cli_config::{format_base_path_meta_element, format_web_history_meta_element};
+use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV, CSRF_PROTECTION_ENV, WEB_HISTORY_ENV};
 use dioxus_cli_opt::{process_file_to, AssetManifest};
 use itertools::Itertools;
 use krates::{cm::TargetKind, NodeId};
@@ -2384,6 +2384,13 @@ impl BuildRequest {
                 env_vars.push((ASSET_ROOT_ENV.into(), base_path.to_string()));
             }
             env_vars.push((APP_TITLE_ENV.into(), self.config.web.app.title.clone()));
+            env_vars.push((
+                WEB_HISTORY_ENV.into(),
+                self.config.web.app.history.as_str().to_string(),
+            ));
+            if self.config.web.app.csrf_protection {
+                env_vars.push((CSRF_PROTECTION_ENV.into(), "true".to_string()));
+            }
         }
 
         // Assemble the rustflags by peering into the `.cargo/config.toml` file
@@ -3568,16 +3575,27 @@ impl BuildRequest {
             // Write the chunks that contain shared imports
             // These will be in the format of chunk_0_modulename.wasm - this is hardcoded in wasm-split
             tracing::debug!("Writing split chunks to disk");
+            let mut chunk_urls = Vec::new();
             for (idx, chunk) in modules.chunks.iter().enumerate() {
                 let path = bindgen_outdir.join(format!("chunk_{}_{}.wasm", idx, chunk.module_name));
                 wasm_opt::write_wasm(&chunk.bytes, &path, &wasm_opt_options).await?;
+                let url = format!(
+                    "/assets/{}",
+                    assets
+                        .register_asset(&path, AssetOptions::builder().into_asset_options())?
+                        .bundled_path()
+                );
                 writeln!(
-                    glue, "export const __wasm_split_load_chunk_{idx} = makeLoad(\"/assets/{url}\", [], fusedImports);",
-                    url = assets
-                        .register_asset(&path, AssetOptions::builder().into_asset_options())?.bundled_path(),
+                    glue,
+                    "export const __wasm_split_load_chunk_{idx} = makeLoad(\"{url}\", [], fusedImports);",
                 )?;
+                chunk_urls.push(url);
             }
 
+            // The server reads the assets each module needs from this manifest to preload the modules
+            // for the current route while rendering the page
+            let mut manifest = BTreeMap::new();
+
             // Write the modules that contain the entrypoints
             tracing::debug!("Writing split modules to disk");
             for (idx, module) in modules.modules.iter().enumerate() {
@@ -3594,17 +3612,19 @@ impl BuildRequest {
                     .as_ref()
                     .context("generated wasm-split bindgen module has no hash id?")?;
 
+                // Again, register this wasm with the asset system
+                let url = format!(
+                    "/assets/{}",
+                    assets
+                        .register_asset(&path, AssetOptions::builder().into_asset_options())?
+                        .bundled_path()
+                );
+
                 writeln!(
                     glue,
-                    "export const __wasm_split_load_{module}_{hash_id}_{comp_name} = makeLoad(\"/assets/{url}\", [{deps}], fusedImports);",
+                    "export const __wasm_split_load_{module}_{hash_id}_{comp_name} = makeLoad(\"{url}\", [{deps}], fusedImports);",
                     module = module.module_name,
 
-
-                    // Again, register this wasm with the asset system
-                    url = assets
-                        .register_asset(&path, AssetOptions::builder().into_asset_options())?
-                        .bundled_path(),
-
                     // This time, make sure to write the dependencies of this chunk
                     // The names here are again, hardcoded in wasm-split - fix this eventually.
                     deps = module
@@ -3614,8 +3634,22 @@ impl BuildRequest {
                         .collect::<Vec<_>>()
                         .join(", ")
                 )?;
+
+                let mut urls = vec![url];
+                urls.extend(
+                    module
+                        .relies_on_chunks
+                        .iter()
+                        .map(|idx| chunk_urls[*idx].clone()),
+                );
+                manifest.insert(module.module_name.clone(), urls);
             }
 
+            std::fs::write(
+                self.root_dir().join("__wasm_split_manifest.json"),
+                serde_json::to_string(&manifest)?,
+            )?;
+
             // Write the js binding
             // It's not registered as an asset since it will get included in the main.js file
             let js_output_path = bindgen_outdir.join("__wasm_split.js");
@@ -4307,6 +4341,9 @@ __wbg_init({{module_or_path: "/{}/{wasm_path}"}}).then((wasm) => {{
             if let Some(base_path) = &self.base_path() {
                 head_resources.push_str(&format_base_path_meta_element(base_path));
             }
+            head_resources.push_str(&format_web_history_meta_element(
+                self.config.web.app.history.as_str(),
+            ));
         }
 
         // Inject any resources from manganis into the head
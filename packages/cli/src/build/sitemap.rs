@@ -0,0 +1,379 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::{collections::HashSet, fmt::Write, path::Path};
+
+use crate::{
+    config::{WebRobotsConfig, WebSitemapConfig},
+    BuildRequest, Platform,
+};
+
+use super::{assets::find_symbol_offsets, call_server_fn, spawn_build_server, AppBuilder};
+
+/// The prefix of the symbols `#[derive(Routable)]` embeds the static routes of each enum in
+const SITEMAP_SYMBOL: &str = "__DIOXUS_SITEMAP__";
+
+/// A single entry of the sitemap. This mirrors `SitemapEntry` in dioxus-router.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct SitemapEntry {
+    loc: String,
+    #[serde(default)]
+    changefreq: Option<String>,
+    #[serde(default)]
+    priority: Option<f32>,
+}
+
+/// The static routes of a `Routable` enum embedded in the binary
+#[derive(Debug, Deserialize)]
+struct EmbeddedSitemap {
+    id: String,
+    routes: Vec<SitemapEntry>,
+    children: Vec<EmbeddedChild>,
+}
+
+/// A child router of a `Routable` enum at a static prefix
+#[derive(Debug, Deserialize)]
+struct EmbeddedChild {
+    prefix: String,
+    id: String,
+}
+
+/// Write the `sitemap.xml` and `robots.txt` configured in the `[web.sitemap]` and `[web.robots]`
+/// sections of Dioxus.toml next to the web assets.
+///
+/// The static routes of the app are read from the client executable. If there is a server build, the
+/// dynamic routes are fetched from the app's `/api/sitemap` endpoint.
+pub(crate) async fn write_sitemap_and_robots(
+    client: &BuildRequest,
+    client_exe: &Path,
+    server: Option<&mut AppBuilder>,
+) -> anyhow::Result<()> {
+    if client.platform != Platform::Web {
+        return Ok(());
+    }
+
+    let web = &client.config.web;
+    let root_dir = client.root_dir();
+
+    if let Some(sitemap) = &web.sitemap {
+        let mut entries = sitemap
+            .routes
+            .iter()
+            .map(|route| SitemapEntry {
+                loc: route.clone(),
+                changefreq: None,
+                priority: None,
+            })
+            .collect::<Vec<_>>();
+
+        match read_embedded_sitemaps(client_exe) {
+            Ok(sitemaps) => entries.extend(static_sitemap_entries(&sitemaps)),
+            Err(err) => tracing::warn!("Failed to read the static routes from the client build: {err}. Only the routes listed in `[web.sitemap]` will be included in the sitemap."),
+        }
+
+        if let Some(server) = server {
+            entries.extend(fetch_sitemap_entries(server).await?);
+        }
+
+        // The static routes are also part of the `/api/sitemap` response of apps that use `Sitemap::sitemap`
+        let mut seen = HashSet::new();
+        entries.retain(|entry| seen.insert(entry.loc.clone()));
+
+        let xml = sitemap_xml(sitemap, client.base_path(), &entries);
+        std::fs::write(root_dir.join("sitemap.xml"), xml).context("Failed to write sitemap.xml")?;
+        tracing::info!("Wrote sitemap.xml with {} routes", entries.len());
+    }
+
+    if let Some(robots) = &web.robots {
+        let sitemap_url = web
+            .sitemap
+            .as_ref()
+            .filter(|_| robots.sitemap)
+            .map(|sitemap| absolute_url(sitemap, client.base_path(), "/sitemap.xml"));
+        std::fs::write(
+            root_dir.join("robots.txt"),
+            robots_txt(robots, sitemap_url.as_deref()),
+        )
+        .context("Failed to write robots.txt")?;
+        tracing::info!("Wrote robots.txt");
+    }
+
+    Ok(())
+}
+
+/// Start the server and fetch the sitemap entries from the `/api/sitemap` endpoint
+async fn fetch_sitemap_entries(server: &mut AppBuilder) -> anyhow::Result<Vec<SitemapEntry>> {
+    let (_child, address) = spawn_build_server(None, server)?;
+    let reqwest_client = reqwest::Client::new();
+
+    let response = call_server_fn(&reqwest_client, address, "sitemap")
+        .await
+        .context("Failed to get the sitemap from the server")?;
+
+    // Apps that don't expose a sitemap endpoint only include the static routes and the routes from Dioxus.toml
+    if !response.status().is_success() {
+        tracing::debug!("No server function found at the `/api/sitemap` endpoint. Only static routes will be included in the sitemap.");
+        return Ok(Vec::new());
+    }
+
+    response
+        .json::<Vec<SitemapEntry>>()
+        .await
+        .inspect(|entries| tracing::debug!("Got sitemap entries: {entries:?}"))
+        .context("Failed to parse the sitemap from the server. Make sure your server function returns `Vec<SitemapEntry>` with the (default) json encoding")
+}
+
+/// Read the static routes `#[derive(Routable)]` embedded in the executable. Each section starts with
+/// the length of the json that follows it as a little endian `u32`.
+fn read_embedded_sitemaps(exe: &Path) -> anyhow::Result<Vec<EmbeddedSitemap>> {
+    let file_contents = std::fs::read(exe)?;
+    let object_file = object::File::parse(&*file_contents)?;
+    let offsets = find_symbol_offsets(exe, &file_contents, &object_file, SITEMAP_SYMBOL)?;
+
+    let mut sitemaps = Vec::new();
+    for offset in offsets {
+        let section = file_contents
+            .get(offset as usize..)
+            .and_then(|section| {
+                let len = u32::from_le_bytes(section.get(..4)?.try_into().ok()?) as usize;
+                section.get(4..4 + len)
+            })
+            .context("The sitemap section is out of bounds")?;
+        match serde_json::from_slice::<EmbeddedSitemap>(section) {
+            Ok(sitemap) => sitemaps.push(sitemap),
+            Err(err) => tracing::warn!("Found a sitemap section at offset {offset} that could not be deserialized: {err}. This may be caused by a mismatch between your dioxus and dioxus-cli versions."),
+        }
+    }
+
+    Ok(sitemaps)
+}
+
+/// Collect the static routes of every root router. Routers that are not the child of another router
+/// are roots and the routes of their child routers are prefixed with the route of the child.
+fn static_sitemap_entries(sitemaps: &[EmbeddedSitemap]) -> Vec<SitemapEntry> {
+    fn collect(
+        sitemaps: &[EmbeddedSitemap],
+        sitemap: &EmbeddedSitemap,
+        prefix: &str,
+        entries: &mut Vec<SitemapEntry>,
+    ) {
+        for route in &sitemap.routes {
+            entries.push(SitemapEntry {
+                loc: join_route(prefix, &route.loc),
+                ..route.clone()
+            });
+        }
+        for child in &sitemap.children {
+            if let Some(child_sitemap) = sitemaps.iter().find(|sitemap| sitemap.id == child.id) {
+                let prefix = join_route(prefix, &child.prefix);
+                collect(sitemaps, child_sitemap, &prefix, entries);
+            }
+        }
+    }
+
+    let children = sitemaps
+        .iter()
+        .flat_map(|sitemap| &sitemap.children)
+        .map(|child| child.id.as_str())
+        .collect::<HashSet<_>>();
+    let mut entries = Vec::new();
+    for root in sitemaps
+        .iter()
+        .filter(|sitemap| !children.contains(sitemap.id.as_str()))
+    {
+        collect(sitemaps, root, "", &mut entries);
+    }
+    entries
+}
+
+/// Join the route of a child router to the route it is nested under. The root of the child router is
+/// the prefix itself without a trailing slash.
+fn join_route(prefix: &str, route: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    match route {
+        "/" if !prefix.is_empty() => prefix.to_string(),
+        route => format!("{prefix}{route}"),
+    }
+}
+
+fn sitemap_xml(
+    config: &WebSitemapConfig,
+    base_path: Option<&str>,
+    entries: &[SitemapEntry],
+) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+
+    for entry in entries {
+        let loc = absolute_url(config, base_path, &entry.loc);
+        _ = writeln!(xml, "  <url>");
+        _ = writeln!(xml, "    <loc>{}</loc>", escape_xml(&loc));
+        if let Some(changefreq) = entry.changefreq.as_ref().or(config.changefreq.as_ref()) {
+            _ = writeln!(
+                xml,
+                "    <changefreq>{}</changefreq>",
+                escape_xml(changefreq)
+            );
+        }
+        if let Some(priority) = entry.priority.or(config.priority) {
+            _ = writeln!(xml, "    <priority>{priority}</priority>");
+        }
+        _ = writeln!(xml, "  </url>");
+    }
+
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn robots_txt(config: &WebRobotsConfig, sitemap_url: Option<&str>) -> String {
+    let mut robots = String::new();
+
+    // Without any rules, every crawler is allowed to visit every page
+    if config.rules.is_empty() {
+        robots.push_str("User-agent: *\nAllow: /\n");
+    }
+
+    for (idx, rule) in config.rules.iter().enumerate() {
+        if idx > 0 {
+            robots.push('\n');
+        }
+        for user_agent in &rule.user_agent {
+            _ = writeln!(robots, "User-agent: {user_agent}");
+        }
+        for allow in &rule.allow {
+            _ = writeln!(robots, "Allow: {allow}");
+        }
+        for disallow in &rule.disallow {
+            _ = writeln!(robots, "Disallow: {disallow}");
+        }
+    }
+
+    if let Some(sitemap_url) = sitemap_url {
+        _ = writeln!(robots, "\nSitemap: {sitemap_url}");
+    }
+
+    robots
+}
+
+/// Join a route to the base url of the site and the base path of the app
+fn absolute_url(config: &WebSitemapConfig, base_path: Option<&str>, route: &str) -> String {
+    let mut url = config.base_url.trim_end_matches('/').to_string();
+    if let Some(base_path) = base_path.map(|path| path.trim_matches('/')) {
+        if !base_path.is_empty() {
+            url.push('/');
+            url.push_str(base_path);
+        }
+    }
+    url.push('/');
+    url.push_str(route.trim_start_matches('/'));
+    url
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WebRobotsRule;
+
+    fn sitemap_config() -> WebSitemapConfig {
+        WebSitemapConfig {
+            base_url: "https://example.com/".to_string(),
+            routes: Vec::new(),
+            changefreq: Some("weekly".to_string()),
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn writes_sitemap() {
+        let entries = [
+            SitemapEntry {
+                loc: "/".to_string(),
+                changefreq: Some("daily".to_string()),
+                priority: Some(1.0),
+            },
+            SitemapEntry {
+                loc: "/search?q=a&b".to_string(),
+                changefreq: None,
+                priority: Some(0.5),
+            },
+        ];
+        assert_eq!(
+            sitemap_xml(&sitemap_config(), Some("/docs/"), &entries),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://example.com/docs/</loc>
+    <changefreq>daily</changefreq>
+    <priority>1</priority>
+  </url>
+  <url>
+    <loc>https://example.com/docs/search?q=a&amp;b</loc>
+    <changefreq>weekly</changefreq>
+    <priority>0.5</priority>
+  </url>
+</urlset>
+"#
+        );
+    }
+
+    #[test]
+    fn joins_child_routers() {
+        let sitemaps: Vec<EmbeddedSitemap> = serde_json::from_str(
+            r#"[
+                {"id":"child","routes":[{"loc":"/","priority":0.5},{"loc":"/about"}],"children":[]},
+                {"id":"root","routes":[{"loc":"/","changefreq":"daily"}],"children":[{"prefix":"/child","id":"child"}]}
+            ]"#,
+        )
+        .unwrap();
+        let entry = |loc: &str, changefreq: Option<&str>, priority| SitemapEntry {
+            loc: loc.to_string(),
+            changefreq: changefreq.map(str::to_string),
+            priority,
+        };
+        assert_eq!(
+            static_sitemap_entries(&sitemaps),
+            vec![
+                entry("/", Some("daily"), None),
+                entry("/child", None, Some(0.5)),
+                entry("/child/about", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_robots() {
+        let allow_all = WebRobotsConfig {
+            rules: Vec::new(),
+            sitemap: true,
+        };
+        assert_eq!(robots_txt(&allow_all, None), "User-agent: *\nAllow: /\n");
+
+        let robots = WebRobotsConfig {
+            rules: vec![
+                WebRobotsRule {
+                    user_agent: vec!["*".to_string()],
+                    allow: Vec::new(),
+                    disallow: vec!["/admin".to_string()],
+                },
+                WebRobotsRule {
+                    user_agent: vec!["BadBot".to_string()],
+                    allow: Vec::new(),
+                    disallow: vec!["/".to_string()],
+                },
+            ],
+            sitemap: true,
+        };
+        assert_eq!(
+            robots_txt(&robots, Some("https://example.com/sitemap.xml")),
+            "User-agent: *\nDisallow: /admin\n\nUser-agent: BadBot\nDisallow: /\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+    }
+}
@@ -70,25 +70,31 @@ impl CommandWithPlatformOverrides<BuildArgs> {
         let ssg = self.shared.ssg;
         let targets = self.into_targets().await?;
 
-        AppBuilder::started(&targets.client, BuildMode::Base { run: false })?
+        let client = AppBuilder::started(&targets.client, BuildMode::Base { run: false })?
             .finish_build()
             .await?;
 
         tracing::info!(path = ?targets.client.root_dir(), "Client build completed successfully! 🚀");
 
+        let mut server_build = None;
         if let Some(server) = targets.server.as_ref() {
             // If the server is present, we need to build it as well
-            let mut server_build = AppBuilder::started(server, BuildMode::Base { run: false })?;
+            let server_build =
+                server_build.insert(AppBuilder::started(server, BuildMode::Base { run: false })?);
             server_build.finish_build().await?;
 
             // Run SSG and cache static routes
             if ssg {
-                crate::pre_render_static_routes(None, &mut server_build, None).await?;
+                crate::pre_render_static_routes(None, server_build, None).await?;
             }
 
             tracing::info!(path = ?targets.client.root_dir(), "Server build completed successfully! 🚀");
         }
 
+        // Write the sitemap.xml and robots.txt configured in Dioxus.toml
+        crate::write_sitemap_and_robots(&targets.client, &client.exe, server_build.as_mut())
+            .await?;
+
         Ok(StructuredOutput::BuildsFinished {
             client: targets.client.root_dir(),
             server: targets.server.map(|s| s.root_dir()),
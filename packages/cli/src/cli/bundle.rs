@@ -39,21 +39,27 @@ impl Bundle {
 
         let BuildTargets { client, server } = self.args.into_targets().await?;
 
-        AppBuilder::started(&client, BuildMode::Base { run: false })?
+        let client_artifacts = AppBuilder::started(&client, BuildMode::Base { run: false })?
             .finish_build()
             .await?;
 
         tracing::info!(path = ?client.root_dir(), "Client build completed successfully! 🚀");
 
+        let mut server_build = None;
         if let Some(server) = server.as_ref() {
             // If the server is present, we need to build it as well
-            AppBuilder::started(server, BuildMode::Base { run: false })?
+            server_build
+                .insert(AppBuilder::started(server, BuildMode::Base { run: false })?)
                 .finish_build()
                 .await?;
 
             tracing::info!(path = ?client.root_dir(), "Server build completed successfully! 🚀");
         }
 
+        // Write the sitemap.xml and robots.txt configured in Dioxus.toml
+        crate::write_sitemap_and_robots(&client, &client_artifacts.exe, server_build.as_mut())
+            .await?;
+
         // If we're building for iOS, we need to bundle the iOS bundle
         if client.platform == Platform::Ios && self.package_types.is_none() {
             self.package_types = Some(vec![crate::PackageType::IosBundle]);
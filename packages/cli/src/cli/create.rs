@@ -216,6 +216,11 @@ pub(crate) fn post_create(path: &Path, vcs: &Vcs) -> Result<()> {
             )
         })?;
 
+        // New projects opt into CSRF protection for their server functions unless the template decided otherwise.
+        if toml_path.ends_with("Dioxus.toml") {
+            enable_csrf_protection(&mut toml);
+        }
+
         toml.as_table_mut().fmt();
 
         let as_string = toml.to_string();
@@ -241,6 +246,29 @@ pub(crate) fn post_create(path: &Path, vcs: &Vcs) -> Result<()> {
     Ok(())
 }
 
+/// Set `csrf_protection = true` in the `[web.app]` section of a `Dioxus.toml` if it isn't set yet.
+fn enable_csrf_protection(toml: &mut toml_edit::DocumentMut) {
+    let mut implicit = toml_edit::Table::new();
+    implicit.set_implicit(true);
+    let Some(web) = toml
+        .entry("web")
+        .or_insert(toml_edit::Item::Table(implicit))
+        .as_table_like_mut()
+    else {
+        return;
+    };
+    let Some(app) = web
+        .entry("app")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+    else {
+        return;
+    };
+    if app.get("csrf_protection").is_none() {
+        app.insert("csrf_protection", toml_edit::value(true));
+    }
+}
+
 fn remove_triple_newlines(string: &str) -> String {
     let mut new_string = String::new();
     for char in string.chars() {
@@ -29,6 +29,8 @@ impl Default for DioxusConfig {
                 app: WebAppConfig {
                     title: default_title(),
                     base_path: None,
+                    history: Default::default(),
+                    csrf_protection: false,
                 },
                 proxy: vec![],
                 watcher: Default::default(),
@@ -48,6 +50,8 @@ impl Default for DioxusConfig {
                 },
                 pre_compress: false,
                 wasm_opt: Default::default(),
+                sitemap: None,
+                robots: None,
             },
             bundle: BundleConfig::default(),
         }
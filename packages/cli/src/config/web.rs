@@ -25,6 +25,14 @@ pub(crate) struct WebConfig {
     /// The wasm-opt configuration
     #[serde(default)]
     pub(crate) wasm_opt: WasmOptConfig,
+
+    /// The sitemap.xml configuration. The sitemap is only generated if this is set
+    #[serde(default)]
+    pub(crate) sitemap: Option<WebSitemapConfig>,
+
+    /// The robots.txt configuration. The robots.txt is only generated if this is set
+    #[serde(default)]
+    pub(crate) robots: Option<WebRobotsConfig>,
 }
 
 impl Default for WebConfig {
@@ -37,6 +45,8 @@ impl Default for WebConfig {
             proxy: Default::default(),
             watcher: Default::default(),
             resource: Default::default(),
+            sitemap: None,
+            robots: None,
         }
     }
 }
@@ -106,6 +116,10 @@ pub(crate) struct WebAppConfig {
     #[serde(default = "default_title")]
     pub(crate) title: String,
     pub(crate) base_path: Option<String>,
+    #[serde(default)]
+    pub(crate) history: WebHistoryKind,
+    #[serde(default)]
+    pub(crate) csrf_protection: bool,
 }
 
 impl Default for WebAppConfig {
@@ -113,6 +127,29 @@ impl Default for WebAppConfig {
         Self {
             title: default_title(),
             base_path: None,
+            history: WebHistoryKind::default(),
+            csrf_protection: false,
+        }
+    }
+}
+
+/// Where the router of the app keeps the route in the url [default: web]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WebHistoryKind {
+    /// Keep the route in the path of the url. The server has to serve the app for every route.
+    #[default]
+    Web,
+    /// Keep the route after the `#` of the url. This works on static hosts that only serve the
+    /// files that exist and from `file://` urls.
+    Hash,
+}
+
+impl WebHistoryKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            WebHistoryKind::Web => "web",
+            WebHistoryKind::Hash => "hash",
         }
     }
 }
@@ -171,6 +208,63 @@ pub(crate) struct WebHttpsConfig {
     pub(crate) cert_path: Option<String>,
 }
 
+/// The sitemap.xml configuration
+///
+/// The static routes of every `Routable` enum are read from the client build. Fullstack apps can add
+/// routes with dynamic segments with a `/api/sitemap` server function which returns the entries of
+/// `Sitemap::sitemap()`. Any other routes can be listed in `routes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebSitemapConfig {
+    /// The url the site is deployed at, like `https://dioxuslabs.com`. Every route is joined to this
+    /// url since the sitemap protocol requires absolute urls
+    pub(crate) base_url: String,
+
+    /// Extra routes to include in the sitemap
+    #[serde(default)]
+    pub(crate) routes: Vec<String>,
+
+    /// The change frequency of routes that don't set one with `changefreq`
+    #[serde(default)]
+    pub(crate) changefreq: Option<String>,
+
+    /// The priority of routes that don't set one with `priority`
+    #[serde(default)]
+    pub(crate) priority: Option<f32>,
+}
+
+/// The robots.txt configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebRobotsConfig {
+    /// The rules for each group of user agents. If no rules are set, every crawler is allowed to
+    /// visit every page
+    #[serde(default)]
+    pub(crate) rules: Vec<WebRobotsRule>,
+
+    /// Link to the generated sitemap.xml from the robots.txt [default: true]
+    #[serde(default = "true_bool")]
+    pub(crate) sitemap: bool,
+}
+
+/// A group of rules for a set of user agents in robots.txt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebRobotsRule {
+    /// The user agents the rule applies to [default: *]
+    #[serde(default = "default_user_agents")]
+    pub(crate) user_agent: Vec<String>,
+
+    /// The paths the crawlers are allowed to visit
+    #[serde(default)]
+    pub(crate) allow: Vec<String>,
+
+    /// The paths the crawlers are not allowed to visit
+    #[serde(default)]
+    pub(crate) disallow: Vec<String>,
+}
+
+fn default_user_agents() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
 fn true_bool() -> bool {
     true
 }
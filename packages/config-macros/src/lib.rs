@@ -37,3 +37,43 @@ macro_rules! maybe_wasm_split {
         $right
     };
 }
+
+/// A macro for deciding whether or not to emit items that only exist when the wasm bundle is split.
+/// Used by the internal router-macro code. The contents here are considered to be semver exempt.
+///
+/// Only on wasm with the wasm-split feature will we emit the "lefthand" items. Otherwise, we emit
+/// the non-wasm_split items
+#[doc(hidden)]
+#[cfg(all(feature = "wasm-split", target_arch = "wasm32"))]
+#[macro_export]
+macro_rules! maybe_wasm_split_items {
+    (
+        if wasm_split {
+            $($left:tt)*
+        } else {
+            $($right:tt)*
+        }
+    ) => {
+        $($left)*
+    };
+}
+
+/// A macro for deciding whether or not to emit items that only exist when the wasm bundle is split.
+/// Used by the internal router-macro code. The contents here are considered to be semver exempt.
+///
+/// Only on wasm with the wasm-split feature will we emit the "lefthand" items. Otherwise, we emit
+/// the non-wasm_split items
+#[doc(hidden)]
+#[cfg(any(not(feature = "wasm-split"), not(target_arch = "wasm32")))]
+#[macro_export]
+macro_rules! maybe_wasm_split_items {
+    (
+        if wasm_split {
+            $($left:tt)*
+        } else {
+            $($right:tt)*
+        }
+    ) => {
+        $($right)*
+    };
+}
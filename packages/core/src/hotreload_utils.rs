@@ -1,6 +1,7 @@
 use std::{
     any::{Any, TypeId},
     hash::{Hash, Hasher},
+    sync::{Mutex, OnceLock},
 };
 
 #[cfg(feature = "serialize")]
@@ -345,6 +346,31 @@ pub struct TemplateGlobalKey {
 
 type StaticTemplateArray = &'static [TemplateNode];
 
+/// The locations of the rsx! calls that created each template, keyed by the address of the roots of the template
+fn template_locations() -> &'static Mutex<rustc_hash::FxHashMap<usize, &'static str>> {
+    static LOCATIONS: OnceLock<Mutex<rustc_hash::FxHashMap<usize, &'static str>>> = OnceLock::new();
+    LOCATIONS.get_or_init(Default::default)
+}
+
+/// Register the location of the rsx! call that created a template. This is called by rsx! in debug builds.
+#[doc(hidden)]
+pub fn register_template_location(roots: StaticTemplateArray, location: &'static str) {
+    // Empty templates don't have a unique address
+    if roots.is_empty() {
+        return;
+    }
+    let mut locations = template_locations().lock().unwrap();
+    locations.entry(roots.as_ptr() as usize).or_insert(location);
+}
+
+/// Get the location of the rsx! call that created a template. This is only known in debug builds for templates that
+/// have not been hot reloaded. If identical templates are merged by the compiler, this may point to any of them.
+#[doc(hidden)]
+pub fn template_location(template: &Template) -> Option<&'static str> {
+    let locations = template_locations().lock().unwrap();
+    locations.get(&(template.roots.as_ptr() as usize)).copied()
+}
+
 #[doc(hidden)]
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
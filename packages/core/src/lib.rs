@@ -35,10 +35,10 @@ mod hotreload_utils;
 pub mod internal {
     #[doc(hidden)]
     pub use crate::hotreload_utils::{
-        DynamicLiteralPool, DynamicValuePool, FmtSegment, FmtedSegments, HotReloadAttributeValue,
-        HotReloadDynamicAttribute, HotReloadDynamicNode, HotReloadLiteral,
-        HotReloadTemplateWithLocation, HotReloadedTemplate, HotreloadedLiteral, NamedAttribute,
-        TemplateGlobalKey,
+        register_template_location, template_location, DynamicLiteralPool, DynamicValuePool,
+        FmtSegment, FmtedSegments, HotReloadAttributeValue, HotReloadDynamicAttribute,
+        HotReloadDynamicNode, HotReloadLiteral, HotReloadTemplateWithLocation, HotReloadedTemplate,
+        HotreloadedLiteral, NamedAttribute, TemplateGlobalKey,
     };
 
     #[doc(hidden)]
@@ -0,0 +1,30 @@
+//! In debug builds, templates remember the rsx! call that created them so renderers can point to it in diagnostics
+
+use dioxus::dioxus_core::{internal::template_location, DynamicNode};
+use dioxus::prelude::*;
+
+#[test]
+fn templates_know_where_they_were_created() {
+    fn app() -> Element {
+        rsx! { Child {} }
+    }
+
+    #[component]
+    fn Child() -> Element {
+        rsx! {
+            div { "hello" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let root = dom.get_scope(ScopeId::APP).unwrap().root_node();
+    let DynamicNode::Component(child) = &root.dynamic_nodes[0] else {
+        panic!("expected a component");
+    };
+    let child = child.mounted_scope(0, root, &dom).unwrap();
+    assert!(child.name().ends_with("::Child"));
+    let location = template_location(&child.root_node().template).unwrap();
+    assert_eq!(location, concat!(file!(), ":14:9"));
+}
@@ -10,6 +10,28 @@ use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
 /// Code for the Dioxus channel used to communicate between the dioxus and javascript code
 pub const NATIVE_EVAL_JS: &str = include_str!("./js/native_eval.js");
 
+/// Wraps the next flush of edits in the webview in a view transition. Webviews without view
+/// transitions apply the edits right away.
+const VIEW_TRANSITION_JS: &str = r#"
+(function (types) {
+    const interpreter = window.interpreter;
+    if (!document.startViewTransition || !interpreter) return;
+    interpreter.viewTransitionTypes = (interpreter.viewTransitionTypes || []).concat(types);
+    if (interpreter.flushQueuedBytesWithoutTransition) return;
+    const flush = interpreter.flushQueuedBytes.bind(interpreter);
+    interpreter.flushQueuedBytesWithoutTransition = flush;
+    interpreter.flushQueuedBytes = () => {
+        const types = interpreter.viewTransitionTypes;
+        if (!types) return flush();
+        interpreter.viewTransitionTypes = null;
+        try {
+            document.startViewTransition({ update: flush, types });
+        } catch (_) {
+            document.startViewTransition(flush);
+        }
+    };
+})"#;
+
 /// Represents the desktop-target's provider of evaluators.
 #[derive(Clone)]
 pub struct DesktopDocument {
@@ -33,6 +55,11 @@ impl Document for DesktopDocument {
         ))
     }
 
+    fn start_view_transition(&self, types: Vec<String>) {
+        let types = serde_json::to_string(&types).unwrap_or_else(|_| "[]".to_string());
+        self.eval(format!("{VIEW_TRANSITION_JS}({types});"));
+    }
+
     fn set_title(&self, title: String) {
         if let Some(ctx) = self.desktop_ctx.upgrade() {
             ctx.set_title(&title);
@@ -356,28 +356,37 @@ impl LaunchBuilder {
                     .unwrap_or_default()
                     .hydrate(true);
 
-                let mut vdom = dioxus_core::VirtualDom::new(app);
-
+                // The document and history are shared between the virtual doms of every island on the page
                 #[cfg(feature = "document")]
-                {
-                    use dioxus_fullstack::FullstackWebDocument;
-                    let document = std::rc::Rc::new(FullstackWebDocument)
-                        as std::rc::Rc<dyn crate::prelude::document::Document>;
-                    vdom.provide_root_context(document);
-                }
-
+                let document = std::rc::Rc::new(dioxus_fullstack::FullstackWebDocument)
+                    as std::rc::Rc<dyn crate::prelude::document::Document>;
                 #[cfg(feature = "document")]
-                {
-                    use dioxus_fullstack::FullstackHistory;
-                    let history =
-                        std::rc::Rc::new(FullstackHistory::new(dioxus_web::WebHistory::default()))
-                            as std::rc::Rc<dyn crate::prelude::History>;
-                    vdom.provide_root_context(history);
+                let history = std::rc::Rc::new(dioxus_fullstack::FullstackHistory::new(
+                    dioxus_web::WebHistory::default(),
+                )) as std::rc::Rc<dyn crate::prelude::History>;
+
+                let provide_root_contexts = |vdom: &mut dioxus_core::VirtualDom| {
+                    #[cfg(feature = "document")]
+                    {
+                        vdom.provide_root_context(document.clone());
+                        vdom.provide_root_context(history.clone());
+                    }
+
+                    for context in &contexts {
+                        vdom.insert_any_root_context(context());
+                    }
+                };
+
+                // Pages the server rendered with islands only hydrate each island instead of the whole app
+                if dioxus_fullstack::islands::page_has_islands() {
+                    return dioxus_fullstack::islands::launch_islands(
+                        platform_config,
+                        provide_root_contexts,
+                    );
                 }
 
-                for context in contexts {
-                    vdom.insert_any_root_context(context());
-                }
+                let mut vdom = dioxus_core::VirtualDom::new(app);
+                provide_root_contexts(&mut vdom);
 
                 return dioxus_web::launch::launch_virtual_dom(vdom, platform_config);
             }
@@ -188,9 +188,14 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
     #[doc(inline)]
     pub use dioxus_fullstack::{
-        server, server_fn, use_server_cached, use_server_future, ServerFnError, ServerFnResult,
+        island, server, server_fn, use_server_cached, use_server_future, HydrationTrigger,
+        LazyHydrate, ServerFnError, ServerFnResult,
     };
 
+    #[cfg(feature = "fullstack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
+    pub use dioxus_fullstack;
+
     #[cfg(feature = "server")]
     #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
     #[doc(inline)]
@@ -206,8 +211,8 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
     #[doc(inline)]
     pub use dioxus_router::{
-        hooks::*, navigator, use_navigator, GoBackButton, GoForwardButton, Link, NavigationTarget,
-        Outlet, Routable, Router,
+        hooks::*, navigator, use_navigator, GoBackButton, GoForwardButton, Link, LinkPrefetch,
+        NavigationTarget, Outlet, Routable, Router, Sitemap,
     };
 
     #[cfg(feature = "asset")]
@@ -106,6 +106,13 @@ pub trait Document: 'static {
         self.create_head_element("link", &attributes, None);
     }
 
+    /// Animate the next update of the page with the [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API).
+    ///
+    /// The `types` are added to the transition so styles can select it with the
+    /// `:active-view-transition-type()` pseudo-class. Renderers without view transitions update the
+    /// page without an animation.
+    fn start_view_transition(&self, _types: Vec<String>) {}
+
     /// Check if we should create a new head component at all. If it returns false, the head component will be skipped.
     ///
     /// This runs once per head component and is used to hydrate head components in fullstack.
@@ -0,0 +1,63 @@
+use dioxus_core::try_consume_context;
+
+/// The context dioxus fullstack provides with the languages from the `Accept-Language` header of
+/// the request that is being rendered on the server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AcceptLanguage {
+    languages: Vec<String>,
+}
+
+impl AcceptLanguage {
+    /// Parse the value of an `Accept-Language` header. The languages are sorted by their quality
+    /// value from most to least preferred and the `*` wildcard is ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_fullstack_hooks::AcceptLanguage;
+    ///
+    /// let accept = AcceptLanguage::parse("fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5");
+    /// assert_eq!(accept.languages(), ["fr-CH", "fr", "en"]);
+    /// ```
+    pub fn parse(header: &str) -> Self {
+        let mut languages: Vec<(String, f32)> = header
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+                let language = parts.next()?.trim();
+                if language.is_empty() || language == "*" {
+                    return None;
+                }
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|q| q.trim().parse().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then(|| (language.to_string(), quality))
+            })
+            .collect();
+        // The sort is stable so languages with the same quality keep their order
+        languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        Self {
+            languages: languages
+                .into_iter()
+                .map(|(language, _)| language)
+                .collect(),
+        }
+    }
+
+    /// Get the accepted languages from most to least preferred.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+}
+
+/// Get the languages the client accepts from most to least preferred. This is used by the dioxus
+/// router to pick the locale of routes without a locale prefix.
+///
+/// During server side rendering, the languages come from the `Accept-Language` header of the
+/// request. On the client, this will return an empty list.
+pub fn accepted_languages() -> Vec<String> {
+    try_consume_context::<AcceptLanguage>()
+        .map(|accept| accept.languages)
+        .unwrap_or_default()
+}
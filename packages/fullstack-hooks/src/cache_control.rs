@@ -0,0 +1,110 @@
+use dioxus_core::try_consume_context;
+use dioxus_signals::{Readable, Signal, Writable};
+
+/// The context dioxus fullstack provides to collect the `Cache-Control` header of the page that is being
+/// rendered on the server. The header is applied to the response when the initial chunk is sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CacheControlContext {
+    cache_control: Signal<Option<String>>,
+    private: Signal<bool>,
+    vary: Signal<Vec<String>>,
+}
+
+impl Default for CacheControlContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CacheControlContext {
+    /// Create a new cache control context. You should not need to call this directly. Dioxus
+    /// fullstack will provide this context for you.
+    pub fn new() -> Self {
+        Self {
+            cache_control: Signal::new(None),
+            private: Signal::new(false),
+            vary: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Set the `Cache-Control` header of the current response. The last value set before the initial
+    /// chunk is sent wins.
+    pub fn set(&mut self, cache_control: impl Into<String>) {
+        self.cache_control.set(Some(cache_control.into()));
+    }
+
+    /// Get the `Cache-Control` header the page set, if any.
+    pub fn current(&self) -> Option<String> {
+        self.cache_control.peek().clone()
+    }
+
+    /// Mark the current response as specific to the visitor.
+    pub fn set_private(&mut self) {
+        self.private.set(true);
+    }
+
+    /// Check if the current response is specific to the visitor.
+    pub fn is_private(&self) -> bool {
+        *self.private.peek()
+    }
+
+    /// Add a request header to the `Vary` header of the current response.
+    pub fn add_vary(&mut self, header: impl Into<String>) {
+        let header = header.into();
+        if !self.vary.peek().contains(&header) {
+            self.vary.write().push(header);
+        }
+    }
+
+    /// Get the request headers the current response varies on.
+    pub fn vary(&self) -> Vec<String> {
+        self.vary.peek().clone()
+    }
+}
+
+/// Set the `Cache-Control` header of the response for the page that is being rendered on the server every
+/// time the component renders. This replaces the header incremental rendering sets for the page.
+///
+/// The header is only sent if this is called during the initial chunk of the response. Pages served from the
+/// incremental cache use the `Cache-Control` header of the cache instead. On the client, this will do nothing.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_fullstack_hooks::use_cache_control;
+/// #[component]
+/// fn Pricing() -> Element {
+///     // Let browsers and CDNs cache the page for five minutes
+///     use_cache_control("public, max-age=300");
+///     rsx! { "Pricing" }
+/// }
+/// ```
+pub fn use_cache_control(cache_control: impl Into<String>) {
+    if let Some(mut context) = try_consume_context::<CacheControlContext>() {
+        context.set(cache_control);
+    }
+}
+
+/// Mark the response of the page that is being rendered on the server as specific to the visitor. The
+/// response is sent with `Cache-Control: private, no-store`, overriding [`use_cache_control`], and it is
+/// never stored in the incremental cache. The dioxus router calls this for routes with a guard.
+///
+/// The response is only marked if this is called during the initial chunk of the response. On the
+/// client, this will do nothing.
+pub fn mark_response_private() {
+    if let Some(mut context) = try_consume_context::<CacheControlContext>() {
+        context.set_private();
+    }
+}
+
+/// Add a request header the page that is being rendered on the server depends on to the `Vary` header of the
+/// response. The incremental cache only stores one render per route, so pages that vary on a request header are
+/// never stored in it. The dioxus router calls this with `Accept-Language` for routes without a locale prefix.
+///
+/// The header is only added if this is called during the initial chunk of the response. On the client, this
+/// will do nothing.
+pub fn vary_response(header: impl Into<String>) {
+    if let Some(mut context) = try_consume_context::<CacheControlContext>() {
+        context.add_vary(header);
+    }
+}
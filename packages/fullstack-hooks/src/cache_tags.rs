@@ -0,0 +1,62 @@
+use dioxus_core::try_consume_context;
+use dioxus_signals::{Readable, Signal, Writable};
+
+/// The context dioxus fullstack provides to collect the cache tags of the page that is being
+/// rendered on the server. The tags are attached to the page in the incremental cache.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CacheTagsContext {
+    tags: Signal<Vec<String>>,
+}
+
+impl Default for CacheTagsContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CacheTagsContext {
+    /// Create a new cache tags context. You should not need to call this directly. Dioxus
+    /// fullstack will provide this context for you.
+    pub fn new() -> Self {
+        Self {
+            tags: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Add a tag to the page that is being rendered.
+    pub fn add(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.tags.peek().contains(&tag) {
+            self.tags.write().push(tag);
+        }
+    }
+
+    /// Get the tags the page added.
+    pub fn current(&self) -> Vec<String> {
+        self.tags.peek().clone()
+    }
+}
+
+/// Tag the page that is being rendered on the server. When incremental rendering is enabled, every page
+/// with the tag can be removed from the cache at once with `IncrementalCache::invalidate_tag`.
+///
+/// On the client, this will do nothing.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_fullstack_hooks::use_cache_tag;
+/// #[component]
+/// fn Post(id: usize) -> Element {
+///     // Pages that show the post can be invalidated when the post changes
+///     use_cache_tag(format!("post:{id}"));
+///     let post = use_server_future(move || get_post(id))?;
+///     rsx! { "{post:?}" }
+/// }
+/// # async fn get_post(id: usize) -> Result<String, ServerFnError> { Ok(String::new()) }
+/// ```
+pub fn use_cache_tag(tag: impl Into<String>) {
+    if let Some(mut context) = try_consume_context::<CacheTagsContext>() {
+        context.add(tag);
+    }
+}
@@ -0,0 +1,10 @@
+//! The names dioxus fullstack uses to share the CSRF token between the server and the client.
+
+/// The name of the cookie the server stores the CSRF token in.
+pub const CSRF_COOKIE_NAME: &str = "dioxus-csrf-token";
+
+/// The name of the meta tag the server renders the CSRF token into while server side rendering a page.
+pub const CSRF_META_NAME: &str = "dioxus-csrf-token";
+
+/// The name of the header the client sends the CSRF token in when it calls a server function.
+pub const CSRF_HEADER_NAME: &str = "x-dioxus-csrf-token";
@@ -86,6 +86,10 @@ impl<H: History> History for FullstackHistory<H> {
         self.history.external(url)
     }
 
+    fn disable_scroll_restoration(&self) {
+        self.history.disable_scroll_restoration()
+    }
+
     fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         self.history.updater(callback)
     }
@@ -113,4 +117,16 @@ impl<H: History> History for FullstackHistory<H> {
     fn replace(&self, path: String) {
         self.history.replace(path);
     }
+
+    fn push_with_state(&self, route: String, state: String) {
+        self.history.push_with_state(route, state);
+    }
+
+    fn replace_with_state(&self, path: String, state: String) {
+        self.history.replace_with_state(path, state);
+    }
+
+    fn current_state(&self) -> Option<String> {
+        match_hydration(|| None, || self.history.current_state())
+    }
 }
@@ -136,3 +136,52 @@ where
 
     Ok(resource)
 }
+
+/// Wraps a future so that its output is serialized into the page on the server and taken from the
+/// hydration data on the client instead of running the future again. Unlike [`use_server_future`],
+/// this is not a hook and may be called outside of the rules of hooks, but like every other
+/// serialized value, it must be called in the same order on the server and the client.
+///
+/// This is used by the router to hydrate the data returned from route loaders.
+#[track_caller]
+pub fn server_future<T, F>(future: F) -> impl Future<Output = T> + 'static
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: Future<Output = T> + 'static,
+{
+    let serialize_context = dioxus_fullstack_protocol::serialize_context();
+    #[allow(unused)]
+    let storage_entry: dioxus_fullstack_protocol::SerializeContextEntry<T> =
+        serialize_context.create_entry();
+
+    #[cfg(feature = "server")]
+    let caller = std::panic::Location::caller();
+
+    // The data needs to be taken while the hydration context is active
+    #[cfg(feature = "web")]
+    let initial_web_result = storage_entry.get();
+
+    #[allow(clippy::let_and_return)]
+    async move {
+        #[cfg(feature = "web")]
+        match initial_web_result {
+            // The data was deserialized successfully from the server
+            Ok(o) => return o,
+
+            // The data is still pending from the server. Don't try to resolve it on the client
+            Err(dioxus_fullstack_protocol::TakeDataError::DataPending) => {
+                std::future::pending::<()>().await
+            }
+
+            // The data was not available on the server, run the future
+            Err(_) => {}
+        }
+
+        let out = future.await;
+
+        #[cfg(feature = "server")]
+        storage_entry.insert(&out, caller);
+
+        out
+    }
+}
@@ -0,0 +1,124 @@
+//! Islands let a mostly static page only hydrate the components that need to be interactive.
+//!
+//! When islands are enabled on the server, the page is rendered to static html without hydration ids. Components
+//! marked with `#[island]` are rendered with hydration ids inside a `<dx-island>` element along with their
+//! serialized props and the data their server futures resolved with. The client creates a separate virtual dom
+//! for each island and hydrates it.
+
+use base64::Engine;
+use dioxus_core::VirtualDom;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::Cursor;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// The name of the meta tag the server renders into pages that only hydrate islands.
+pub const ISLANDS_META_NAME: &str = "dioxus-islands";
+
+/// The name of the element the server renders each island into.
+pub const ISLAND_ELEMENT_NAME: &str = "dx-island";
+
+/// An island component that can be hydrated on the client. Islands are registered with the `#[island]` macro.
+pub struct IslandRegistration {
+    name: &'static str,
+    build: fn(&[u8]) -> Result<VirtualDom, IslandPropsError>,
+}
+
+impl IslandRegistration {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        build: fn(&[u8]) -> Result<VirtualDom, IslandPropsError>,
+    ) -> Self {
+        Self { name, build }
+    }
+
+    /// The unique name of the island. This is the path of the component.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Create a virtual dom that renders the island with the props the server serialized into the page.
+    pub fn build(&self, props: &str) -> Result<VirtualDom, IslandPropsError> {
+        let props = base64::engine::general_purpose::STANDARD
+            .decode(props)
+            .map_err(IslandPropsError::Base64)?;
+        (self.build)(&props)
+    }
+}
+
+inventory::collect!(IslandRegistration);
+
+/// Find the island registered with the `#[island]` macro under a name.
+pub fn find_island(name: &str) -> Option<&'static IslandRegistration> {
+    inventory::iter::<IslandRegistration>().find(|island| island.name == name)
+}
+
+/// The island a component renders on the server. This context is only provided on the scope of the island
+/// itself. You should not need to use this directly; it is provided by the `#[island]` macro.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IslandContext {
+    name: &'static str,
+    props: String,
+}
+
+impl IslandContext {
+    /// The unique name of the island
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The base64 encoded props of the island
+    pub fn props(&self) -> &str {
+        &self.props
+    }
+}
+
+/// Mark the current component as an island with the props it was rendered with. On the client, this will do
+/// nothing.
+#[doc(hidden)]
+pub fn use_island<P: Serialize>(name: &'static str, props: impl FnOnce() -> P) {
+    #[cfg(feature = "server")]
+    {
+        let mut serialized = Vec::new();
+        match ciborium::into_writer(&props(), &mut serialized) {
+            Ok(()) => {
+                let props = base64::engine::general_purpose::STANDARD.encode(serialized);
+                // The props are serialized every render so they stay up to date if the parent rerenders
+                dioxus_core::provide_context(IslandContext { name, props });
+            }
+            Err(err) => tracing::error!("Failed to serialize the props of island {name}: {err}"),
+        }
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        _ = (name, props);
+    }
+}
+
+/// Decode the props of an island that were serialized on the server.
+#[doc(hidden)]
+pub fn decode_island_props<P: DeserializeOwned>(props: &[u8]) -> Result<P, IslandPropsError> {
+    ciborium::from_reader(Cursor::new(props)).map_err(IslandPropsError::Deserialize)
+}
+
+/// An error that can occur when decoding the props of an island on the client
+#[derive(Debug)]
+pub enum IslandPropsError {
+    /// The props were not valid base64
+    Base64(base64::DecodeError),
+    /// Deserializing the props failed
+    Deserialize(ciborium::de::Error<std::io::Error>),
+}
+
+impl std::fmt::Display for IslandPropsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base64(e) => write!(f, "The island props are not valid base64: {}", e),
+            Self::Deserialize(e) => write!(f, "Failed to deserialize the island props: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IslandPropsError {}
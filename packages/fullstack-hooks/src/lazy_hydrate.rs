@@ -0,0 +1,71 @@
+#![allow(non_snake_case)]
+
+use dioxus_core::{consume_context, use_hook, Element};
+use dioxus_core_macro::{component, rsx, Props};
+use dioxus_fullstack_protocol::LazyHydration;
+use dioxus_hooks::use_context_provider;
+use dioxus_signals::{GlobalSignal, Readable};
+
+pub use dioxus_fullstack_protocol::HydrationTrigger;
+
+/// A boundary that hydrates its children later than the rest of the page.
+///
+/// The server renders the children with the rest of the page, so they are visible right away. The client skips
+/// the children while it hydrates the page and hydrates them on their own once the [`HydrationTrigger`] fires.
+/// This is useful for widgets below the fold that would otherwise slow down the hydration of the whole page.
+///
+/// On the client, the boundary catches any suspense from its children. If the children suspend after they are
+/// hydrated, nothing is rendered until they resolve.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # fn Hero() -> Element { unimplemented!() }
+/// # fn Comments() -> Element { unimplemented!() }
+/// fn Article() -> Element {
+///     rsx! {
+///         Hero {}
+///         // The comments are only hydrated once the user scrolls down to them
+///         LazyHydrate { trigger: HydrationTrigger::Visible, Comments {} }
+///     }
+/// }
+/// ```
+#[component]
+pub fn LazyHydrate(
+    /// When the children are hydrated. Defaults to [`HydrationTrigger::Idle`].
+    #[props(default)]
+    trigger: HydrationTrigger,
+    /// The children that are hydrated lazily
+    children: Element,
+) -> Element {
+    use_context_provider(|| LazyHydration::new(trigger));
+
+    // The client suspends the children until they are hydrated so they don't run before the trigger fires
+    #[cfg(all(feature = "web", not(feature = "server")))]
+    {
+        use dioxus_core::{SuspenseBoundary, VNode};
+        rsx! {
+            dx-lazy { style: "display: contents",
+                SuspenseBoundary { fallback: |_| VNode::empty(), LazyHydrateChildren { children } }
+            }
+        }
+    }
+    #[cfg(not(all(feature = "web", not(feature = "server"))))]
+    rsx! {
+        dx-lazy { style: "display: contents", LazyHydrateChildren { children } }
+    }
+}
+
+/// The children of a [`LazyHydrate`] boundary. The children suspend while the boundary is deferred.
+#[component]
+fn LazyHydrateChildren(children: Element) -> Element {
+    let lazy = use_hook(consume_context::<LazyHydration>);
+    let deferred = use_hook(|| {
+        lazy.is_deferred()
+            .then(|| dioxus_core::spawn(std::future::pending::<()>()))
+    });
+    match deferred {
+        Some(task) if lazy.is_deferred() => dioxus_core::suspend(task),
+        _ => children,
+    }
+}
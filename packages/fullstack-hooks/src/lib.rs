@@ -1,9 +1,28 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod accept_language;
+mod cache_control;
+mod cache_tags;
+mod csrf;
 pub mod history;
 mod hooks;
+pub mod islands;
+mod lazy_hydrate;
+mod redirect;
+mod site_origin;
+mod status;
 mod streaming;
+mod wasm_split;
 
+pub use crate::accept_language::*;
+pub use crate::cache_control::*;
+pub use crate::cache_tags::*;
+pub use crate::csrf::*;
 pub use crate::hooks::*;
+pub use crate::lazy_hydrate::*;
+pub use crate::redirect::*;
+pub use crate::site_origin::*;
+pub use crate::status::*;
 pub use crate::streaming::*;
+pub use crate::wasm_split::*;
@@ -0,0 +1,78 @@
+use dioxus_core::try_consume_context;
+use dioxus_signals::{Readable, Signal, Writable};
+
+/// The kind of redirect the server answers a request with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedirectStatus {
+    /// A `307 Temporary Redirect`. Clients keep requesting the original URL.
+    Temporary,
+    /// A `308 Permanent Redirect`. Clients and search engines replace the original URL with the
+    /// new location.
+    Permanent,
+}
+
+impl RedirectStatus {
+    /// Get the http status code of the redirect.
+    pub fn code(self) -> u16 {
+        match self {
+            RedirectStatus::Temporary => 307,
+            RedirectStatus::Permanent => 308,
+        }
+    }
+}
+
+/// A redirect the page that is being rendered on the server asked for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerRedirect {
+    /// The URL for the `Location` header of the response.
+    pub location: String,
+    /// The status of the response.
+    pub status: RedirectStatus,
+}
+
+/// The context dioxus fullstack provides to collect the redirect of the page that is being rendered
+/// on the server. If the page redirects while the initial chunk renders, the response is sent with
+/// the redirect status and a `Location` header instead of the page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RedirectContext {
+    redirect: Signal<Option<ServerRedirect>>,
+}
+
+impl Default for RedirectContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedirectContext {
+    /// Create a new redirect context. You should not need to call this directly. Dioxus fullstack
+    /// will provide this context for you.
+    pub fn new() -> Self {
+        Self {
+            redirect: Signal::new(None),
+        }
+    }
+
+    /// Redirect the current request. The first redirect wins if this is called more than once.
+    pub fn redirect(&mut self, location: String, status: RedirectStatus) {
+        if self.redirect.peek().is_none() {
+            self.redirect.set(Some(ServerRedirect { location, status }));
+        }
+    }
+
+    /// Get the redirect of the current request, if any.
+    pub fn current(&self) -> Option<ServerRedirect> {
+        self.redirect.peek().clone()
+    }
+}
+
+/// Answer the request that is being rendered on the server with a redirect to `location`. This will
+/// be called automatically by the dioxus router for `#[redirect]` routes and route guards.
+///
+/// The redirect is only sent if this is called during the initial chunk of the response. On the
+/// client, this will do nothing.
+pub fn redirect(location: impl Into<String>, status: RedirectStatus) {
+    if let Some(mut context) = try_consume_context::<RedirectContext>() {
+        context.redirect(location.into(), status);
+    }
+}
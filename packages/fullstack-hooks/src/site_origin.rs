@@ -0,0 +1,29 @@
+use dioxus_core::try_consume_context;
+
+/// The context dioxus fullstack provides with the origin of the site that is configured on the
+/// server, like `https://example.com`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SiteOrigin {
+    origin: Option<String>,
+}
+
+impl SiteOrigin {
+    /// Create a new site origin context. You should not need to call this directly. Dioxus
+    /// fullstack will provide this context for you.
+    pub fn new(origin: Option<String>) -> Self {
+        Self { origin }
+    }
+
+    /// Get the origin of the site, if one is configured.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+}
+
+/// Get the origin of the site that is configured on the server, like `https://example.com`.
+/// This is used by the dioxus router to create absolute `hreflang` links.
+///
+/// On the client or if the server doesn't configure an origin, this will return `None`.
+pub fn site_origin() -> Option<String> {
+    try_consume_context::<SiteOrigin>().and_then(|origin| origin.origin)
+}
@@ -0,0 +1,82 @@
+use dioxus_core::try_consume_context;
+use dioxus_signals::{Readable, Signal, Writable};
+
+/// The context dioxus fullstack provides to collect the http status of the page that is being
+/// rendered on the server. The status is applied to the response when the initial chunk is sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HttpStatusContext {
+    status: Signal<Option<u16>>,
+}
+
+impl Default for HttpStatusContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpStatusContext {
+    /// Create a new http status context. You should not need to call this directly. Dioxus
+    /// fullstack will provide this context for you.
+    pub fn new() -> Self {
+        Self {
+            status: Signal::new(None),
+        }
+    }
+
+    /// Set the status of the current response. The last status set before the initial chunk is
+    /// sent wins.
+    pub fn set(&mut self, status: u16) {
+        self.status.set(Some(status));
+    }
+
+    /// Get the status the page set, if any.
+    pub fn current(&self) -> Option<u16> {
+        *self.status.peek()
+    }
+}
+
+/// Set the http status of the response for the page that is being rendered on the server. The dioxus
+/// router calls this for routes marked `not_found` or `error`.
+///
+/// The status is only sent if this is called during the initial chunk of the response. On the
+/// client, this will do nothing.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_fullstack_hooks::set_status;
+/// #[component]
+/// fn Post(id: usize) -> Element {
+///     let post = use_server_future(move || get_post(id))?;
+///     match post() {
+///         Some(Ok(Some(post))) => rsx! { "{post}" },
+///         _ => {
+///             set_status(404);
+///             rsx! { "This post does not exist" }
+///         }
+///     }
+/// }
+/// # async fn get_post(id: usize) -> Result<Option<String>, ServerFnError> { Ok(None) }
+/// ```
+pub fn set_status(status: u16) {
+    if let Some(mut context) = try_consume_context::<HttpStatusContext>() {
+        context.set(status);
+    }
+}
+
+/// Set the http status of the response every time the component renders on the server.
+///
+/// This is a shorthand for calling [`set_status`] in the body of a component, like a not found page:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_fullstack_hooks::use_http_status;
+/// #[component]
+/// fn NotFound() -> Element {
+///     use_http_status(404);
+///     rsx! { "Page not found" }
+/// }
+/// ```
+pub fn use_http_status(status: u16) {
+    set_status(status);
+}
@@ -0,0 +1,49 @@
+use dioxus_core::try_consume_context;
+use dioxus_signals::{Readable, Signal, Writable};
+
+/// The context dioxus fullstack provides to collect the wasm-split modules the page that is being
+/// rendered on the server needs. The modules are preloaded in the head of the page so the browser
+/// can start downloading them before the main wasm bundle starts running.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SplitModuleContext {
+    modules: Signal<Vec<String>>,
+}
+
+impl Default for SplitModuleContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SplitModuleContext {
+    /// Create a new split module context. You should not need to call this directly. Dioxus fullstack
+    /// will provide this context for you.
+    pub fn new() -> Self {
+        Self {
+            modules: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Mark a wasm-split module as required by the current page.
+    pub fn preload(&mut self, module: &str) {
+        if !self.modules.peek().iter().any(|m| m == module) {
+            self.modules.write().push(module.to_string());
+        }
+    }
+
+    /// Get the wasm-split modules that are required by the current page.
+    pub fn modules(&self) -> Vec<String> {
+        self.modules.peek().clone()
+    }
+}
+
+/// Preload a wasm-split module on the server. This will be called automatically by the dioxus router
+/// for routes marked with `#[route("/path", lazy)]`.
+///
+/// The module is only preloaded if this is called during the initial chunk of the response. On the
+/// client, this will do nothing.
+pub fn preload_split_module(module: &str) {
+    if let Some(mut context) = try_consume_context::<SplitModuleContext>() {
+        context.preload(module);
+    }
+}
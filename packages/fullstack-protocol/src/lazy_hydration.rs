@@ -0,0 +1,85 @@
+use crate::{serialize_context, SerializeContextEntry};
+use std::{cell::Cell, rc::Rc};
+
+/// The name of the element the children of a lazy hydration boundary are rendered into.
+pub const LAZY_HYDRATION_ELEMENT_NAME: &str = "dx-lazy";
+
+/// When the client hydrates the children of a lazy hydration boundary.
+///
+/// Until the children are hydrated, the server rendered html is shown, but none of the components inside of the
+/// boundary run on the client and none of their event handlers are attached.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HydrationTrigger {
+    /// Hydrate the children once the browser is idle after the rest of the page is hydrated.
+    #[default]
+    Idle,
+    /// Hydrate the children when they scroll into view.
+    Visible,
+    /// Hydrate the children the first time the user interacts with them. The event that triggered hydration is
+    /// replayed once the children are hydrated.
+    Interaction,
+}
+
+impl HydrationTrigger {
+    /// The name of the trigger the client uses to schedule hydration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Visible => "visible",
+            Self::Interaction => "interaction",
+        }
+    }
+}
+
+/// The context a lazy hydration boundary provides on its own scope. The server and the client use it to agree on
+/// whether the hydration of the children of the boundary is deferred.
+#[derive(Clone)]
+pub struct LazyHydration {
+    trigger: HydrationTrigger,
+    deferred: SerializeContextEntry<bool>,
+    hydrated: Rc<Cell<bool>>,
+}
+
+impl LazyHydration {
+    /// Create a new lazy hydration boundary. This creates an entry in the current hydration context for the server
+    /// to tell the client if the hydration of the children is deferred.
+    pub fn new(trigger: HydrationTrigger) -> Self {
+        Self {
+            trigger,
+            deferred: serialize_context().create_entry(),
+            hydrated: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Get the trigger that hydrates the children of the boundary
+    pub fn trigger(&self) -> HydrationTrigger {
+        self.trigger
+    }
+
+    /// Record if the server rendered the children of the boundary to be hydrated later. This is only called on the
+    /// server as it serializes the hydration data of the page.
+    #[track_caller]
+    pub fn set_deferred(&self, deferred: bool) {
+        self.deferred
+            .clone()
+            .insert(&deferred, std::panic::Location::caller());
+    }
+
+    /// Check if the children of the boundary are still waiting for their trigger before they hydrate. This is
+    /// always false on the server and for boundaries that are created after the page is hydrated.
+    pub fn is_deferred(&self) -> bool {
+        #[cfg(feature = "web")]
+        {
+            !self.hydrated.get() && self.deferred.get().unwrap_or(false)
+        }
+        #[cfg(not(feature = "web"))]
+        {
+            false
+        }
+    }
+
+    /// Mark the children of the boundary as hydrated. After this is called, the children will render normally.
+    pub fn hydrate(&self) {
+        self.hydrated.set(true);
+    }
+}
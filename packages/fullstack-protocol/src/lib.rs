@@ -6,6 +6,9 @@ use dioxus_core::CapturedError;
 use serde::Serialize;
 use std::{cell::RefCell, io::Cursor, rc::Rc};
 
+mod lazy_hydration;
+pub use lazy_hydration::*;
+
 #[cfg(feature = "web")]
 thread_local! {
     static CONTEXT: RefCell<Option<HydrationContext>> = const { RefCell::new(None) };
@@ -0,0 +1,75 @@
+//! The web client for server functions. It sends the CSRF token the server rendered into the page with
+//! every server function call.
+
+use dioxus_fullstack_hooks::{CSRF_COOKIE_NAME, CSRF_HEADER_NAME, CSRF_META_NAME};
+use server_fn::{
+    client::{browser::BrowserClient, Client},
+    error::FromServerFnError,
+    request::browser::BrowserRequest,
+    response::browser::BrowserResponse,
+};
+use std::future::Future;
+use web_sys::wasm_bindgen::JsCast;
+
+/// The client `#[server]` functions use in the browser. It behaves like [`BrowserClient`], but also sends the
+/// CSRF token of the page in a header so server functions keep working when the server enables CSRF protection.
+pub struct CsrfClient;
+
+impl<Error, InputStreamError, OutputStreamError> Client<Error, InputStreamError, OutputStreamError>
+    for CsrfClient
+where
+    Error: FromServerFnError,
+    InputStreamError: FromServerFnError,
+    OutputStreamError: FromServerFnError,
+{
+    type Request = BrowserRequest;
+    type Response = BrowserResponse;
+
+    fn send(req: Self::Request) -> impl Future<Output = Result<Self::Response, Error>> + Send {
+        if let Some(token) = csrf_token() {
+            req.headers().set(CSRF_HEADER_NAME, &token);
+        }
+        <BrowserClient as Client<Error, InputStreamError, OutputStreamError>>::send(req)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn open_websocket(
+        path: &str,
+    ) -> impl Future<
+        Output = Result<
+            (
+                impl futures_util::Stream<Item = Result<bytes::Bytes, bytes::Bytes>> + Send + 'static,
+                impl futures_util::Sink<bytes::Bytes> + Send + 'static,
+            ),
+            Error,
+        >,
+    > + Send {
+        <BrowserClient as Client<Error, InputStreamError, OutputStreamError>>::open_websocket(path)
+    }
+
+    fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+        <BrowserClient as Client<Error, InputStreamError, OutputStreamError>>::spawn(future)
+    }
+}
+
+/// Read the CSRF token from the meta tag the server rendered. Pages served from the incremental cache don't
+/// include the meta tag, so fall back to the cookie.
+fn csrf_token() -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    if let Some(meta) = document
+        .query_selector(&format!(r#"meta[name="{CSRF_META_NAME}"]"#))
+        .ok()
+        .flatten()
+    {
+        return meta.get_attribute("content");
+    }
+    let cookies = document
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()?;
+    cookies.split(';').find_map(|cookie| {
+        let (name, value) = cookie.trim().split_once('=')?;
+        (name == CSRF_COOKIE_NAME).then(|| value.to_string())
+    })
+}
@@ -0,0 +1,67 @@
+//! Islands only hydrate the components marked with `#[island]`. See
+//! [`ServeConfigBuilder::enable_islands`](https://docs.rs/dioxus-server/latest/dioxus_server/struct.ServeConfigBuilder.html#method.enable_islands).
+
+pub use dioxus_fullstack_hooks::islands::*;
+
+#[cfg(feature = "web")]
+pub use web::*;
+
+#[cfg(feature = "web")]
+mod web {
+    use dioxus_core::VirtualDom;
+    use dioxus_fullstack_hooks::islands::{find_island, ISLANDS_META_NAME, ISLAND_ELEMENT_NAME};
+    use web_sys::wasm_bindgen::JsCast;
+
+    /// Check if the server rendered the current page with islands enabled
+    pub fn page_has_islands() -> bool {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| {
+                document
+                    .query_selector(&format!(r#"meta[name="{ISLANDS_META_NAME}"]"#))
+                    .ok()
+                    .flatten()
+            })
+            .is_some()
+    }
+
+    /// Hydrate every island on the page in its own virtual dom. `setup` is called with the virtual dom of each
+    /// island before it is hydrated to provide any root contexts.
+    ///
+    /// Every island is launched with a copy of `platform_config` mounted to the island element. The islands share
+    /// the connection to the devserver and the default history of the page.
+    pub fn launch_islands(platform_config: dioxus_web::Config, setup: impl Fn(&mut VirtualDom)) {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let Ok(islands) = document.query_selector_all(ISLAND_ELEMENT_NAME) else {
+            return;
+        };
+
+        for index in 0..islands.length() {
+            let Some(element) = islands
+                .get(index)
+                .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+            else {
+                continue;
+            };
+            let name = element.get_attribute("data-name").unwrap_or_default();
+            let Some(island) = find_island(&name) else {
+                tracing::error!("The island {name} was rendered on the server, but it isn't registered on the client");
+                continue;
+            };
+            let props = element.get_attribute("data-props").unwrap_or_default();
+            let mut virtual_dom = match island.build(&props) {
+                Ok(virtual_dom) => virtual_dom,
+                Err(err) => {
+                    tracing::error!("Failed to hydrate the island {name}: {err}");
+                    continue;
+                }
+            };
+            setup(&mut virtual_dom);
+
+            let config = platform_config.clone().rootelement(element).hydrate(true);
+            dioxus_web::launch::launch_virtual_dom(virtual_dom, config);
+        }
+    }
+}
@@ -7,11 +7,34 @@
 #[cfg(all(feature = "web", feature = "document"))]
 mod web;
 
+#[cfg(feature = "web")]
+mod csrf;
 mod error;
+pub mod islands;
 
 #[cfg(all(feature = "web", feature = "document"))]
 pub use web::FullstackWebDocument;
 
+#[cfg(feature = "web")]
+pub use csrf::CsrfClient;
+
+/// The client `#[server]` functions use when they don't set one. Browser builds use [`CsrfClient`] and every
+/// other build uses the default client `C` of the macro.
+#[doc(hidden)]
+pub type DefaultClient<C> = <C as PickClient>::Client;
+
+#[doc(hidden)]
+pub trait PickClient {
+    type Client;
+}
+
+impl<C> PickClient for C {
+    #[cfg(all(feature = "web", target_arch = "wasm32"))]
+    type Client = CsrfClient;
+    #[cfg(not(all(feature = "web", target_arch = "wasm32")))]
+    type Client = C;
+}
+
 pub use dioxus_fullstack_hooks::history::FullstackHistory;
 
 pub use crate::error::{ServerFnError, ServerFnResult};
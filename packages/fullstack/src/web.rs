@@ -44,6 +44,11 @@ impl Document for FullstackWebDocument {
         WebDocument.create_link(props);
     }
 
+    /// Apply the edits of the next render inside of a view transition
+    fn start_view_transition(&self, types: Vec<String>) {
+        WebDocument.start_view_transition(types);
+    }
+
     fn create_head_component(&self) -> bool {
         !head_element_written_on_server()
     }
@@ -235,6 +235,54 @@ pub trait History {
     /// ```
     fn replace(&self, path: String);
 
+    /// Go to another page and attach a state to the new history entry.
+    ///
+    /// The state is a serialized payload (the router uses JSON) that belongs to the entry instead
+    /// of the URL. It is returned by [`History::current_state`] while the entry is active, including
+    /// after going back or forward to it. [`History::push`] creates entries without a state.
+    ///
+    /// [`History`]s that can't store state fall back to [`History::push`].
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let mut history = MemoryHistory::default();
+    /// history.push_with_state("/details".to_string(), r#""search""#.to_string());
+    /// assert_eq!(history.current_state().as_deref(), Some(r#""search""#));
+    ///
+    /// history.push("/other".to_string());
+    /// assert_eq!(history.current_state(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.current_state().as_deref(), Some(r#""search""#));
+    /// ```
+    #[allow(unused_variables)]
+    fn push_with_state(&self, route: String, state: String) {
+        self.push(route)
+    }
+
+    /// Replace the current page with another one and attach a state to the history entry.
+    ///
+    /// This works like [`History::replace`], but replaces the state of the entry as well.
+    ///
+    /// [`History`]s that can't store state fall back to [`History::replace`].
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let mut history = MemoryHistory::default();
+    /// history.replace_with_state("/".to_string(), "1".to_string());
+    /// assert_eq!(history.current_state().as_deref(), Some("1"));
+    /// assert!(!history.can_go_back());
+    /// ```
+    #[allow(unused_variables)]
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace(path)
+    }
+
+    /// Get the state attached to the current history entry, if any.
+    fn current_state(&self) -> Option<String> {
+        None
+    }
+
     /// Navigate to an external URL.
     ///
     /// This should navigate to an external URL, which isn't controlled by the router. If a
@@ -246,6 +294,13 @@ pub trait History {
         false
     }
 
+    /// Stop saving and restoring scroll positions when the route changes.
+    ///
+    /// The router calls this when it manages scrolling itself, so the [`History`] and the router
+    /// don't both move the scroll position. [`History`]s that don't restore scroll positions can
+    /// ignore this.
+    fn disable_scroll_restoration(&self) {}
+
     /// Provide the [`History`] with an update callback.
     ///
     /// Some [`History`]s may receive URL updates from outside the router. When such
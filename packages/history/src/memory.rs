@@ -2,10 +2,31 @@ use std::cell::RefCell;
 
 use crate::History;
 
+/// A history entry and the state attached to it
+struct MemoryHistoryEntry {
+    route: String,
+    state: Option<String>,
+}
+
 struct MemoryHistoryState {
-    current: String,
-    history: Vec<String>,
-    future: Vec<String>,
+    current: MemoryHistoryEntry,
+    history: Vec<MemoryHistoryEntry>,
+    future: Vec<MemoryHistoryEntry>,
+}
+
+impl MemoryHistoryState {
+    fn push(&mut self, route: String, state: Option<String>) {
+        // don't push the same route twice
+        if self.current.route == route {
+            if state.is_some() {
+                self.current.state = state;
+            }
+            return;
+        }
+        let old = std::mem::replace(&mut self.current, MemoryHistoryEntry { route, state });
+        self.history.push(old);
+        self.future.clear();
+    }
 }
 
 /// A [`History`] provider that stores all navigation information in memory.
@@ -44,9 +65,12 @@ impl MemoryHistory {
     pub fn with_initial_path(path: impl ToString) -> Self {
         Self {
             state: MemoryHistoryState{
-                current: path.to_string().parse().unwrap_or_else(|err| {
-                    panic!("index route does not exist:\n{err}\n use MemoryHistory::with_initial_path to set a custom path")
-                }),
+                current: MemoryHistoryEntry {
+                    route: path.to_string().parse().unwrap_or_else(|err| {
+                        panic!("index route does not exist:\n{err}\n use MemoryHistory::with_initial_path to set a custom path")
+                    }),
+                    state: None,
+                },
                 history: Vec::new(),
                 future: Vec::new(),
             }.into(),
@@ -75,7 +99,7 @@ impl History for MemoryHistory {
     }
 
     fn current_route(&self) -> String {
-        self.state.borrow().current.clone()
+        self.state.borrow().current.route.clone()
     }
 
     fn can_go_back(&self) -> bool {
@@ -103,18 +127,30 @@ impl History for MemoryHistory {
     }
 
     fn push(&self, new: String) {
-        let mut write = self.state.borrow_mut();
-        // don't push the same route twice
-        if write.current == new {
-            return;
-        }
-        let old = std::mem::replace(&mut write.current, new);
-        write.history.push(old);
-        write.future.clear();
+        self.state.borrow_mut().push(new, None);
     }
 
     fn replace(&self, path: String) {
         let mut write = self.state.borrow_mut();
-        write.current = path;
+        write.current = MemoryHistoryEntry {
+            route: path,
+            state: None,
+        };
+    }
+
+    fn push_with_state(&self, route: String, state: String) {
+        self.state.borrow_mut().push(route, Some(state));
+    }
+
+    fn replace_with_state(&self, path: String, state: String) {
+        let mut write = self.state.borrow_mut();
+        write.current = MemoryHistoryEntry {
+            route: path,
+            state: Some(state),
+        };
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.state.borrow().current.state.clone()
     }
 }
@@ -1506,6 +1506,12 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/vertical-align>
     vertical_align: "vertical-align" in "style";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/view-transition-class>
+    view_transition_class: "view-transition-class" in "style";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/view-transition-name>
+    view_transition_name: "view-transition-name" in "style";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/visibility>
     visibility in "style";
 
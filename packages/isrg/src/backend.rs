@@ -0,0 +1,70 @@
+//! The storage the incremental renderer caches rendered routes in
+
+use chrono::{DateTime, Utc};
+
+use crate::IncrementalRendererError;
+
+/// A rendered route stored in a [`RenderCacheBackend`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// The time the route was rendered
+    pub timestamp: DateTime<Utc>,
+    /// The rendered html
+    pub html: Vec<u8>,
+    /// The tags the route was rendered with
+    pub tags: Vec<String>,
+}
+
+/// The storage the [`IncrementalRenderer`](crate::IncrementalRenderer) caches rendered routes in. Backends
+/// only store entries; the renderer decides when an entry is stale or expired based on its timestamp.
+///
+/// Dioxus provides a [`MemoryBackend`](crate::MemoryBackend), a [`FileSystemBackend`](crate::FileSystemBackend)
+/// and a [`LayeredBackend`](crate::LayeredBackend) that chains two backends. Stores shared between multiple
+/// servers can be plugged in with the [`KeyValueBackend`](crate::KeyValueBackend).
+#[async_trait::async_trait]
+pub trait RenderCacheBackend: Send + Sync + 'static {
+    /// Get the cached entry for a route
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError>;
+
+    /// Cache an entry for a route, replacing any entry that was cached before
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError>;
+
+    /// Remove the entry for a route
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError>;
+
+    /// List every cached route
+    async fn list(&self) -> Result<Vec<String>, IncrementalRendererError>;
+
+    /// Whether the backend is shared between multiple servers. Entries in a shared backend can be invalidated
+    /// by another server at any time, so a [`LayeredBackend`](crate::LayeredBackend) only keeps copies of
+    /// them in a per server backend for a short time.
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    /// Remove every entry that was cached with a tag. Returns the routes that were removed.
+    ///
+    /// The default implementation reads every entry in the backend. Backends that can look up routes
+    /// by tag should override this.
+    async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut invalidated = Vec::new();
+        for route in self.list().await? {
+            let Some(entry) = self.get(&route).await? else {
+                continue;
+            };
+            if entry.tags.iter().any(|entry_tag| entry_tag == tag) {
+                self.invalidate(&route).await?;
+                invalidated.push(route);
+            }
+        }
+        Ok(invalidated)
+    }
+
+    /// Remove every entry from the backend
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
+        for route in self.list().await? {
+            self.invalidate(&route).await?;
+        }
+        Ok(())
+    }
+}
@@ -3,11 +3,11 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::fs_cache::PathMapFn;
 
-use crate::memory_cache::InMemoryCache;
-use crate::IncrementalRenderer;
+use crate::{IncrementalRenderer, MemoryBackend, RenderCacheBackend};
 
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -17,8 +17,10 @@ pub struct IncrementalRendererConfig {
     static_dir: PathBuf,
     memory_cache_limit: usize,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
     clear_cache: bool,
     pre_render: bool,
+    backend: Option<Arc<dyn RenderCacheBackend>>,
 
     #[cfg(not(target_arch = "wasm32"))]
     map_path: Option<PathMapFn>,
@@ -37,8 +39,10 @@ impl IncrementalRendererConfig {
             static_dir: PathBuf::from("./static"),
             memory_cache_limit: 10000,
             invalidate_after: None,
+            stale_while_revalidate: None,
             clear_cache: false,
             pre_render: false,
+            backend: None,
             #[cfg(not(target_arch = "wasm32"))]
             map_path: None,
         }
@@ -54,7 +58,7 @@ impl IncrementalRendererConfig {
     /// The function should return the path to the folder to store the index.html file in.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn map_path<F: Fn(&str) -> PathBuf + Send + Sync + 'static>(mut self, map_path: F) -> Self {
-        self.map_path = Some(std::sync::Arc::new(map_path));
+        self.map_path = Some(Arc::new(map_path));
         self
     }
 
@@ -76,6 +80,35 @@ impl IncrementalRendererConfig {
         self
     }
 
+    /// Keep serving a page for up to `stale_while_revalidate` after it is invalidated by [`Self::invalidate_after`].
+    /// The stale page is sent immediately while a fresh version is rendered in the background.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRendererConfig;
+    /// # use std::time::Duration;
+    /// // Pages are fresh for a minute and are served while they are regenerated for another hour
+    /// let config = IncrementalRendererConfig::new()
+    ///     .invalidate_after(Duration::from_secs(60))
+    ///     .stale_while_revalidate(Duration::from_secs(60 * 60));
+    /// ```
+    pub fn stale_while_revalidate(mut self, stale_while_revalidate: Duration) -> Self {
+        self.stale_while_revalidate = Some(stale_while_revalidate);
+        self
+    }
+
+    /// Set the backend rendered routes are cached in. This replaces the default memory and file system cache,
+    /// so [`Self::static_dir`], [`Self::memory_cache_limit`] and [`Self::map_path`] have no effect.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{IncrementalRendererConfig, MemoryBackend};
+    /// // Only cache routes in memory
+    /// let config = IncrementalRendererConfig::new().backend(MemoryBackend::new(1000));
+    /// ```
+    pub fn backend(mut self, backend: impl RenderCacheBackend) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
     /// Set whether to include hydration ids in the pre-rendered html.
     pub fn pre_render(mut self, pre_render: bool) -> Self {
         self.pre_render = pre_render;
@@ -84,21 +117,32 @@ impl IncrementalRendererConfig {
 
     /// Build the incremental renderer.
     pub fn build(self) -> IncrementalRenderer {
-        let mut renderer = IncrementalRenderer {
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache: crate::fs_cache::FileSystemCache::new(
-                self.static_dir.clone(),
-                self.map_path,
-                self.invalidate_after,
-            ),
-            memory_cache: InMemoryCache::new(self.memory_cache_limit, self.invalidate_after),
+        IncrementalRenderer {
+            backend: self.build_backend(),
             invalidate_after: self.invalidate_after,
-        };
+            stale_while_revalidate: self.stale_while_revalidate,
+            clear_on_start: self.clear_cache.into(),
+        }
+    }
 
-        if self.clear_cache {
-            renderer.invalidate_all();
+    /// Create the configured backend or the default memory and file system cache
+    fn build_backend(&self) -> Arc<dyn RenderCacheBackend> {
+        if let Some(backend) = &self.backend {
+            return backend.clone();
         }
 
-        renderer
+        let memory = MemoryBackend::new(self.memory_cache_limit);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let file_system = crate::FileSystemBackend::new(&self.static_dir)
+                .with_path_map(self.map_path.clone())
+                .track_timestamps(self.invalidate_after.is_some());
+            if self.memory_cache_limit == 0 {
+                return Arc::new(file_system);
+            }
+            Arc::new(crate::LayeredBackend::new(memory, file_system))
+        }
+        #[cfg(target_arch = "wasm32")]
+        Arc::new(memory)
     }
 }
@@ -63,6 +63,12 @@ impl RenderFreshness {
         self.max_age
     }
 
+    /// Check if the rendered response is older than its maximum age. Stale responses can still be served
+    /// while a fresh response is rendered if stale while revalidate is enabled.
+    pub fn is_stale(&self) -> bool {
+        matches!(self.max_age, Some(max_age) if self.age > max_age)
+    }
+
     /// Get the time the response was rendered
     pub fn timestamp(&self) -> DateTime<Utc> {
         self.timestamp
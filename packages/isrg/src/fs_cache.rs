@@ -2,47 +2,166 @@
 
 use chrono::{DateTime, Utc};
 
-use super::{IncrementalRendererError, RenderFreshness};
-use std::{path::PathBuf, sync::Arc, time::SystemTime};
+use crate::{CacheEntry, IncrementalRendererError, RenderCacheBackend};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
 
 pub(crate) type PathMapFn = Arc<dyn Fn(&str) -> PathBuf + Send + Sync>;
 
-pub(crate) struct FileSystemCache {
+/// The name of the file the route and cache tags of a cached page are stored in. The first line of the file
+/// is the route and every other line is a tag.
+const TAGS_FILE_NAME: &str = "index.tags";
+
+/// A [`RenderCacheBackend`] that stores rendered routes as html files. By default, the route `/blog/1` is
+/// stored in `{static_dir}/blog/1/index.html` so the files can be served as static assets.
+pub struct FileSystemBackend {
     static_dir: PathBuf,
     map_path: PathMapFn,
-    invalidate_after: Option<std::time::Duration>,
+    track_timestamps: bool,
 }
 
-impl FileSystemCache {
-    pub fn new(
-        static_dir: PathBuf,
-        map_path: Option<PathMapFn>,
-        invalidate_after: Option<std::time::Duration>,
-    ) -> Self {
+impl FileSystemBackend {
+    /// Create a file system backend that stores routes in `static_dir`
+    pub fn new(static_dir: impl AsRef<Path>) -> Self {
+        let static_dir = static_dir.as_ref().to_path_buf();
         Self {
             static_dir: static_dir.clone(),
-            map_path: map_path.unwrap_or_else(move || {
-                Arc::new(move |route: &str| {
-                    let (before_query, _) = route.split_once('?').unwrap_or((route, ""));
-                    let mut path = static_dir.clone();
-                    for segment in before_query.split('/') {
-                        path.push(segment);
-                    }
-                    path
-                })
+            map_path: Arc::new(move |route: &str| {
+                let (before_query, _) = route.split_once('?').unwrap_or((route, ""));
+                let mut path = static_dir.clone();
+                for segment in before_query.split('/') {
+                    path.push(segment);
+                }
+                path
             }),
-            invalidate_after,
+            track_timestamps: false,
+        }
+    }
+
+    /// Set a mapping from the route to the file path. The function should return the path to the folder to
+    /// store the index.html file in.
+    pub fn map_path<F: Fn(&str) -> PathBuf + Send + Sync + 'static>(mut self, map_path: F) -> Self {
+        self.map_path = Arc::new(map_path);
+        self
+    }
+
+    pub(crate) fn with_path_map(mut self, map_path: Option<PathMapFn>) -> Self {
+        if let Some(map_path) = map_path {
+            self.map_path = map_path;
+        }
+        self
+    }
+
+    /// Store the time each route was rendered in the file name (`index/{timestamp}.html`) instead of relying on
+    /// the modified time of `index.html`. This is enabled automatically when the renderer invalidates routes
+    /// after a duration.
+    pub fn track_timestamps(mut self, track_timestamps: bool) -> Self {
+        self.track_timestamps = track_timestamps;
+        self
+    }
+
+    fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
+        let mut file_path = (self.map_path)(route);
+        if self.track_timestamps {
+            // find the newest file that matches the route and is a html file
+            file_path.push("index");
+            std::fs::read_dir(file_path)
+                .ok()?
+                .flatten()
+                .filter_map(|entry| ValidCachedPath::try_from_path(entry.path()))
+                .max_by_key(|cached_path| cached_path.timestamp)
+        } else {
+            file_path.push("index.html");
+            let timestamp = std::fs::metadata(&file_path).ok()?.modified().ok()?;
+            Some(ValidCachedPath {
+                full_path: file_path,
+                timestamp,
+            })
+        }
+    }
+
+    fn route_as_path(&self, route: &str, timestamp: DateTime<Utc>) -> PathBuf {
+        let mut file_path = (self.map_path)(route);
+        if self.track_timestamps {
+            file_path.push("index");
+            file_path.push(timestamp_to_string(timestamp));
+        } else {
+            file_path.push("index");
+        }
+        file_path.set_extension("html");
+        file_path
+    }
+
+    fn tags_path(&self, route: &str) -> PathBuf {
+        let mut file_path = (self.map_path)(route);
+        file_path.push(TAGS_FILE_NAME);
+        file_path
+    }
+
+    /// Remove every render of a route
+    fn remove_renders(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        if self.track_timestamps {
+            let mut dir = (self.map_path)(route);
+            dir.push("index");
+            for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+                if ValidCachedPath::try_from_path(entry.path()).is_some() {
+                    std::fs::remove_file(entry.path())?;
+                }
+            }
+        } else if let Some(file_path) = self.find_file(route) {
+            std::fs::remove_file(file_path.full_path)?;
         }
+        Ok(())
     }
 
-    pub fn put(
-        &mut self,
-        route: String,
-        timestamp: DateTime<Utc>,
-        data: Vec<u8>,
-    ) -> Result<(), IncrementalRendererError> {
+    /// Read the route and tags of every route stored in the static directory
+    fn read_tags(&self) -> Vec<(String, Vec<String>)> {
+        walkdir::WalkDir::new(&self.static_dir)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file() && entry.file_name() == TAGS_FILE_NAME)
+            .filter_map(|entry| {
+                let contents = std::fs::read_to_string(entry.path()).ok()?;
+                let mut lines = contents.lines();
+                let route = lines.next()?.to_string();
+                Some((route, lines.map(String::from).collect()))
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl RenderCacheBackend for FileSystemBackend {
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        let Some(file_path) = self.find_file(route) else {
+            return Ok(None);
+        };
+        let html = match std::fs::read(&file_path.full_path) {
+            Ok(html) => html,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let tags = std::fs::read_to_string(self.tags_path(route))
+            .map(|contents| contents.lines().skip(1).map(String::from).collect())
+            .unwrap_or_default();
+        tracing::trace!("file cache hit {:?}", route);
+        Ok(Some(CacheEntry {
+            timestamp: file_path.timestamp.into(),
+            html,
+            tags,
+        }))
+    }
+
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
         use std::io::Write;
-        let file_path = self.route_as_path(&route, timestamp);
+        // Remove older renders of the route so they are not found instead of the new render
+        if self.track_timestamps {
+            self.remove_renders(route)?;
+        }
+        let file_path = self.route_as_path(route, entry.timestamp);
         if let Some(parent) = file_path.parent() {
             if !parent.exists() {
                 std::fs::create_dir_all(parent)?;
@@ -50,11 +169,46 @@ impl FileSystemCache {
         }
         let file = std::fs::File::create(file_path)?;
         let mut file = std::io::BufWriter::new(file);
-        file.write_all(&data)?;
+        file.write_all(&entry.html)?;
+
+        let mut tags = route.to_string();
+        for tag in &entry.tags {
+            tags.push('\n');
+            tags.push_str(tag);
+        }
+        std::fs::write(self.tags_path(route), tags)?;
         Ok(())
     }
 
-    pub fn clear(&mut self) {
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.remove_renders(route)?;
+        let tags_path = self.tags_path(route);
+        if tags_path.exists() {
+            std::fs::remove_file(tags_path)?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        Ok(self
+            .read_tags()
+            .into_iter()
+            .map(|(route, _)| route)
+            .collect())
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut invalidated = Vec::new();
+        for (route, tags) in self.read_tags() {
+            if tags.iter().any(|route_tag| route_tag == tag) {
+                self.invalidate(&route).await?;
+                invalidated.push(route);
+            }
+        }
+        Ok(invalidated)
+    }
+
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
         // clear the static directory of index.html files contained within folders
         for entry in std::fs::read_dir(&self.static_dir)
             .into_iter()
@@ -65,7 +219,7 @@ impl FileSystemCache {
                 for entry in walkdir::WalkDir::new(entry.path()).into_iter().flatten() {
                     if entry.file_type().is_file() {
                         if let Some(fnmae) = entry.file_name().to_str() {
-                            if fnmae.ends_with(".html") {
+                            if fnmae.ends_with(".html") || fnmae == TAGS_FILE_NAME {
                                 if let Err(err) = std::fs::remove_file(entry.path()) {
                                     tracing::error!("Failed to remove file: {}", err);
                                 }
@@ -75,83 +229,7 @@ impl FileSystemCache {
                 }
             }
         }
-    }
-
-    pub fn invalidate(&mut self, route: &str) {
-        let file_path = self.find_file(route).unwrap().full_path;
-        if let Err(err) = std::fs::remove_file(file_path) {
-            tracing::error!("Failed to remove file: {}", err);
-        }
-    }
-
-    pub fn get(
-        &self,
-        route: &str,
-    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
-        if let Some(file_path) = self.find_file(route) {
-            if let Some(freshness) = file_path.freshness(self.invalidate_after) {
-                if let Ok(file) = std::fs::File::open(file_path.full_path) {
-                    let mut file = std::io::BufReader::new(file);
-                    let mut cache_hit = Vec::new();
-                    std::io::copy(&mut file, &mut cache_hit)?;
-                    tracing::trace!("file cache hit {:?}", route);
-                    return Ok(Some((freshness, cache_hit)));
-                }
-            }
-        }
-
-        Ok(None)
-    }
-
-    fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
-        let mut file_path = (self.map_path)(route);
-        if let Some(deadline) = self.invalidate_after {
-            // find the first file that matches the route and is a html file
-            file_path.push("index");
-            if let Ok(dir) = std::fs::read_dir(file_path) {
-                for entry in dir.flatten() {
-                    if let Some(cached_path) = ValidCachedPath::try_from_path(entry.path()) {
-                        if let Ok(elapsed) = cached_path.timestamp.elapsed() {
-                            if elapsed < deadline {
-                                // The timestamp is valid, return the file
-                                return Some(cached_path);
-                            }
-                        }
-                        // if the timestamp is invalid or passed, delete the file
-                        if let Err(err) = std::fs::remove_file(entry.path()) {
-                            tracing::error!("Failed to remove file: {}", err);
-                        }
-                    }
-                }
-                None
-            } else {
-                None
-            }
-        } else {
-            file_path.push("index.html");
-            file_path.exists().then_some({
-                ValidCachedPath {
-                    full_path: file_path,
-                    timestamp: SystemTime::now(),
-                }
-            })
-        }
-    }
-
-    fn route_as_path(&self, route: &str, timestamp: DateTime<Utc>) -> PathBuf {
-        let mut file_path = (self.map_path)(route);
-        if self.track_timestamps() {
-            file_path.push("index");
-            file_path.push(timestamp_to_string(timestamp));
-        } else {
-            file_path.push("index");
-        }
-        file_path.set_extension("html");
-        file_path
-    }
-
-    fn track_timestamps(&self) -> bool {
-        self.invalidate_after.is_some()
+        Ok(())
     }
 }
 
@@ -172,15 +250,9 @@ impl ValidCachedPath {
             timestamp,
         })
     }
-
-    pub fn freshness(&self, max_age: Option<std::time::Duration>) -> Option<RenderFreshness> {
-        let age = self.timestamp.elapsed().ok()?.as_secs();
-        let max_age = max_age.map(|max_age| max_age.as_secs());
-        Some(RenderFreshness::new(age, max_age?, self.timestamp.into()))
-    }
 }
 
-fn decode_timestamp(timestamp: &str) -> Option<std::time::SystemTime> {
+fn decode_timestamp(timestamp: &str) -> Option<SystemTime> {
     let timestamp = u64::from_str_radix(timestamp, 16).ok()?;
     Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(timestamp))
 }
@@ -0,0 +1,319 @@
+//! A cache backend for key-value stores that can be shared between servers
+
+use chrono::{DateTime, Utc};
+
+use crate::{CacheEntry, IncrementalRendererError, RenderCacheBackend};
+
+/// A key-value store that a [`KeyValueBackend`] caches routes in. Implement this for a client of a store
+/// like Redis to share rendered routes between servers.
+#[async_trait::async_trait]
+pub trait KeyValueStore: Send + Sync + 'static {
+    /// Get the value of a key
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, IncrementalRendererError>;
+
+    /// Set the value of a key
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), IncrementalRendererError>;
+
+    /// Delete a key. Deleting a key that doesn't exist should succeed.
+    async fn delete(&self, key: &str) -> Result<(), IncrementalRendererError>;
+
+    /// List every key that starts with a prefix
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>, IncrementalRendererError>;
+}
+
+#[async_trait::async_trait]
+impl<S: KeyValueStore> KeyValueStore for std::sync::Arc<S> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, IncrementalRendererError> {
+        S::get(self, key).await
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), IncrementalRendererError> {
+        S::set(self, key, value).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), IncrementalRendererError> {
+        S::delete(self, key).await
+    }
+
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        S::keys(self, prefix).await
+    }
+}
+
+/// A [`RenderCacheBackend`] that stores routes in a [`KeyValueStore`].
+///
+/// Each route is stored under `{prefix}page:{route}`. Every tag of the route adds an empty key at
+/// `{prefix}tag:{tag}:{route}` so routes can be invalidated by tag without reading every page.
+pub struct KeyValueBackend<S> {
+    store: S,
+    prefix: String,
+}
+
+impl<S: KeyValueStore> KeyValueBackend<S> {
+    /// Create a backend that stores routes in a key-value store with the prefix `dioxus:`
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            prefix: "dioxus:".to_string(),
+        }
+    }
+
+    /// Set the prefix of every key the backend stores. Use a different prefix for each application that
+    /// shares the store.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn page_key(&self, route: &str) -> String {
+        format!("{}page:{route}", self.prefix)
+    }
+
+    fn tag_prefix(&self, tag: &str) -> String {
+        format!("{}tag:{tag}:", self.prefix)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: KeyValueStore> RenderCacheBackend for KeyValueBackend<S> {
+    fn is_shared(&self) -> bool {
+        true
+    }
+
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        let Some(value) = self.store.get(&self.page_key(route)).await? else {
+            return Ok(None);
+        };
+        decode_entry(&value).map(Some)
+    }
+
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        // Remove the tags of the previous render before tagging the new one
+        self.invalidate(route).await?;
+        for tag in &entry.tags {
+            let key = format!("{}{route}", self.tag_prefix(tag));
+            self.store.set(&key, Vec::new()).await?;
+        }
+        self.store
+            .set(&self.page_key(route), encode_entry(&entry))
+            .await
+    }
+
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        let page_key = self.page_key(route);
+        if let Some(value) = self.store.get(&page_key).await? {
+            for tag in decode_entry(&value)?.tags {
+                let key = format!("{}{route}", self.tag_prefix(&tag));
+                self.store.delete(&key).await?;
+            }
+        }
+        self.store.delete(&page_key).await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        let prefix = self.page_key("");
+        let keys = self.store.keys(&prefix).await?;
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&prefix).map(String::from))
+            .collect())
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let prefix = self.tag_prefix(tag);
+        let mut invalidated = Vec::new();
+        for key in self.store.keys(&prefix).await? {
+            // Routes start with a slash. Other keys belong to a longer tag that starts with this tag
+            let Some(route) = key
+                .strip_prefix(&prefix)
+                .filter(|route| route.starts_with('/'))
+            else {
+                continue;
+            };
+            self.invalidate(route).await?;
+            // The tag key is removed with the page, unless the page was already gone
+            self.store.delete(&key).await?;
+            invalidated.push(route.to_string());
+        }
+        Ok(invalidated)
+    }
+}
+
+/// Encode an entry as the timestamp in milliseconds, the number of tags, each tag prefixed with its length
+/// and then the html. Numbers are big endian.
+fn encode_entry(entry: &CacheEntry) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(entry.html.len() + 12);
+    bytes.extend_from_slice(&entry.timestamp.timestamp_millis().to_be_bytes());
+    bytes.extend_from_slice(&(entry.tags.len() as u32).to_be_bytes());
+    for tag in &entry.tags {
+        bytes.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(tag.as_bytes());
+    }
+    bytes.extend_from_slice(&entry.html);
+    bytes
+}
+
+fn decode_entry(mut bytes: &[u8]) -> Result<CacheEntry, IncrementalRendererError> {
+    #[derive(Debug, thiserror::Error)]
+    #[error("The cached entry is corrupted")]
+    struct CorruptedEntry;
+
+    fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], CorruptedEntry> {
+        if bytes.len() < len {
+            return Err(CorruptedEntry);
+        }
+        let (taken, rest) = bytes.split_at(len);
+        *bytes = rest;
+        Ok(taken)
+    }
+    fn take_u32(bytes: &mut &[u8]) -> Result<usize, CorruptedEntry> {
+        let number = take(bytes, 4)?.try_into().map_err(|_| CorruptedEntry)?;
+        Ok(u32::from_be_bytes(number) as usize)
+    }
+
+    let decode = |bytes: &mut &[u8]| {
+        let millis = take(bytes, 8)?.try_into().map_err(|_| CorruptedEntry)?;
+        let timestamp = DateTime::<Utc>::from_timestamp_millis(i64::from_be_bytes(millis))
+            .ok_or(CorruptedEntry)?;
+        let mut tags = Vec::new();
+        for _ in 0..take_u32(bytes)? {
+            let len = take_u32(bytes)?;
+            let tag = std::str::from_utf8(take(bytes, len)?).map_err(|_| CorruptedEntry)?;
+            tags.push(tag.to_string());
+        }
+        Ok(CacheEntry {
+            timestamp,
+            html: bytes.to_vec(),
+            tags,
+        })
+    };
+    decode(&mut bytes).map_err(|err: CorruptedEntry| IncrementalRendererError::Other(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    /// An in process key-value store that stands in for a store shared between servers
+    #[derive(Default)]
+    struct FakeStore(Mutex<BTreeMap<String, Vec<u8>>>);
+
+    #[async_trait::async_trait]
+    impl KeyValueStore for FakeStore {
+        async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, IncrementalRendererError> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), IncrementalRendererError> {
+            self.0.lock().unwrap().insert(key.to_string(), value);
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), IncrementalRendererError> {
+            self.0.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn keys(&self, prefix: &str) -> Result<Vec<String>, IncrementalRendererError> {
+            let store = self.0.lock().unwrap();
+            let keys = store.keys().filter(|key| key.starts_with(prefix));
+            Ok(keys.cloned().collect())
+        }
+    }
+
+    fn entry(html: &str, tags: &[&str]) -> CacheEntry {
+        CacheEntry {
+            timestamp: DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap(),
+            html: html.as_bytes().to_vec(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn key_value_backend() {
+        let backend = KeyValueBackend::new(FakeStore::default()).with_prefix("app:");
+        let post = entry("<p>post</p>", &["post:1", "posts"]);
+        backend.put("/posts/1", post.clone()).await.unwrap();
+        backend
+            .put("/posts/10", entry("<p>post 10</p>", &["post:10", "posts"]))
+            .await
+            .unwrap();
+        backend.put("/", entry("home", &[])).await.unwrap();
+
+        assert_eq!(backend.get("/posts/1").await.unwrap(), Some(post));
+        assert_eq!(backend.get("/missing").await.unwrap(), None);
+        let mut routes = backend.list().await.unwrap();
+        routes.sort();
+        assert_eq!(routes, ["/", "/posts/1", "/posts/10"]);
+
+        // Tags that start with another tag are not invalidated with it
+        assert_eq!(
+            backend.invalidate_tag("post:1").await.unwrap(),
+            ["/posts/1"]
+        );
+        assert_eq!(backend.get("/posts/1").await.unwrap(), None);
+        assert!(backend.get("/posts/10").await.unwrap().is_some());
+
+        // Rendering a route again replaces its tags
+        backend
+            .put("/posts/10", entry("<p>post 10</p>", &["post:10"]))
+            .await
+            .unwrap();
+        assert!(backend.invalidate_tag("posts").await.unwrap().is_empty());
+
+        backend.clear().await.unwrap();
+        assert!(backend.store.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn shared_between_renderers() {
+        use crate::{IncrementalRendererConfig, LayeredBackend, MemoryBackend};
+        use std::{sync::Arc, time::Duration};
+
+        // Two servers with their own memory cache in front of the same store
+        let ttl = Duration::from_millis(200);
+        let store = Arc::new(FakeStore::default());
+        let server = |store: &Arc<FakeStore>| {
+            IncrementalRendererConfig::new()
+                .backend(
+                    LayeredBackend::new(
+                        MemoryBackend::new(10),
+                        KeyValueBackend::new(store.clone()),
+                    )
+                    .with_shared_ttl(ttl),
+                )
+                .build()
+        };
+        let first = server(&store);
+        let second = server(&store);
+
+        first
+            .cache_with_tags("/posts/1".into(), "post", vec!["post:1".into()])
+            .await
+            .unwrap();
+        let cached = second.get("/posts/1").await.unwrap().unwrap();
+        assert_eq!(cached.response, b"post");
+
+        // The page is invalidated by the server that rendered it
+        assert_eq!(first.invalidate_tag("post:1").await.unwrap(), ["/posts/1"]);
+        assert!(store.0.lock().unwrap().is_empty());
+        assert!(first.get("/posts/1").await.unwrap().is_none());
+
+        // The other server keeps its copy in memory, but not for longer than the ttl
+        assert!(second.get("/posts/1").await.unwrap().is_some());
+        std::thread::sleep(ttl);
+        assert!(second.get("/posts/1").await.unwrap().is_none());
+
+        // A page written by one server and invalidated by the other is not served from memory past the ttl
+        second
+            .cache_with_tags("/posts/1".into(), "post", vec!["post:1".into()])
+            .await
+            .unwrap();
+        assert!(first.get("/posts/1").await.unwrap().is_some());
+        assert_eq!(first.invalidate_tag("post:1").await.unwrap(), ["/posts/1"]);
+        std::thread::sleep(ttl);
+        assert!(second.get("/posts/1").await.unwrap().is_none());
+        assert!(first.get("/posts/1").await.unwrap().is_none());
+    }
+}
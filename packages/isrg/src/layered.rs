@@ -0,0 +1,166 @@
+//! A cache backend that chains two backends
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{CacheEntry, IncrementalRendererError, RenderCacheBackend};
+
+/// A [`RenderCacheBackend`] that checks a fast backend before a slower one. Entries found in the second
+/// backend are copied into the first, and every write goes to both backends.
+///
+/// If the second backend is [shared](RenderCacheBackend::is_shared) between servers and the first is not,
+/// another server may invalidate the shared entry at any time. Copies in the first backend are only served
+/// for a short time after they were copied, one second by default, before the shared backend is checked
+/// again. Change the time with [`LayeredBackend::with_shared_ttl`].
+///
+/// ```rust
+/// # use dioxus_isrg::{FileSystemBackend, LayeredBackend, MemoryBackend};
+/// // Keep the 100 most recent routes in memory in front of the file system
+/// let backend = LayeredBackend::new(MemoryBackend::new(100), FileSystemBackend::new("./static"));
+/// ```
+pub struct LayeredBackend {
+    first: Box<dyn RenderCacheBackend>,
+    second: Box<dyn RenderCacheBackend>,
+    shared_ttl: Duration,
+    /// When entries of a shared second backend were copied into the first backend
+    copied_at: Mutex<HashMap<String, Instant>>,
+}
+
+impl LayeredBackend {
+    /// Create a layered backend that checks `first` before `second`
+    pub fn new(first: impl RenderCacheBackend, second: impl RenderCacheBackend) -> Self {
+        Self {
+            first: Box::new(first),
+            second: Box::new(second),
+            shared_ttl: Duration::from_secs(1),
+            copied_at: Default::default(),
+        }
+    }
+
+    /// Set how long copies of entries from a shared second backend are served from the first backend. This
+    /// is the longest time an invalidation on another server can take to reach this server.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{KeyValueBackend, KeyValueStore, LayeredBackend, MemoryBackend};
+    /// # use std::time::Duration;
+    /// # fn backend(store: impl KeyValueStore) -> LayeredBackend {
+    /// LayeredBackend::new(MemoryBackend::new(100), KeyValueBackend::new(store))
+    ///     .with_shared_ttl(Duration::from_millis(200))
+    /// # }
+    /// ```
+    pub fn with_shared_ttl(mut self, ttl: Duration) -> Self {
+        self.shared_ttl = ttl;
+        self
+    }
+
+    /// Whether copies in the first backend can go stale because the second backend is shared
+    fn copies_expire(&self) -> bool {
+        !self.first.is_shared() && self.second.is_shared()
+    }
+
+    fn copied_at(&self) -> std::sync::MutexGuard<'_, HashMap<String, Instant>> {
+        self.copied_at.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Remember when an entry was written into the first backend
+    fn track_copy(&self, route: &str) {
+        if self.copies_expire() {
+            let mut copied_at = self.copied_at();
+            copied_at.retain(|_, at| at.elapsed() < self.shared_ttl);
+            copied_at.insert(route.to_string(), Instant::now());
+        }
+    }
+
+    /// Whether the copy of a route in the first backend may still be served
+    fn copy_is_fresh(&self, route: &str) -> bool {
+        !self.copies_expire()
+            || self
+                .copied_at()
+                .get(route)
+                .is_some_and(|at| at.elapsed() < self.shared_ttl)
+    }
+
+    fn forget_copies(&self, routes: &[String]) {
+        if self.copies_expire() {
+            let mut copied_at = self.copied_at();
+            for route in routes {
+                copied_at.remove(route);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RenderCacheBackend for LayeredBackend {
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        if let Some(entry) = self.first.get(route).await? {
+            if self.copy_is_fresh(route) {
+                return Ok(Some(entry));
+            }
+            // The shared entry may have been invalidated by another server since it was copied
+            self.first.invalidate(route).await?;
+        }
+        let Some(entry) = self.second.get(route).await? else {
+            self.forget_copies(&[route.to_string()]);
+            return Ok(None);
+        };
+        match self.first.put(route, entry.clone()).await {
+            Ok(()) => self.track_copy(route),
+            Err(err) => {
+                tracing::error!(
+                    "Failed to copy route \"{route}\" into the first cache layer: {err}"
+                );
+            }
+        }
+        Ok(Some(entry))
+    }
+
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        self.second.put(route, entry.clone()).await?;
+        self.first.put(route, entry).await?;
+        self.track_copy(route);
+        Ok(())
+    }
+
+    fn is_shared(&self) -> bool {
+        self.first.is_shared() || self.second.is_shared()
+    }
+
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.second.invalidate(route).await?;
+        self.first.invalidate(route).await?;
+        self.forget_copies(&[route.to_string()]);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut routes = self.second.list().await?;
+        for route in self.first.list().await? {
+            if !routes.contains(&route) {
+                routes.push(route);
+            }
+        }
+        Ok(routes)
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut routes = self.second.invalidate_tag(tag).await?;
+        for route in self.first.invalidate_tag(tag).await? {
+            if !routes.contains(&route) {
+                routes.push(route);
+            }
+        }
+        self.forget_copies(&routes);
+        Ok(routes)
+    }
+
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
+        self.second.clear().await?;
+        self.first.clear().await?;
+        self.copied_at().clear();
+        Ok(())
+    }
+}
@@ -2,36 +2,47 @@
 
 #![allow(non_snake_case)]
 
+mod backend;
 mod config;
 mod freshness;
 #[cfg(not(target_arch = "wasm32"))]
 mod fs_cache;
+mod key_value;
+mod layered;
 mod memory_cache;
 
-use std::time::Duration;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
+pub use backend::*;
 use chrono::Utc;
 pub use config::*;
 pub use freshness::*;
-
-use self::memory_cache::InMemoryCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use fs_cache::FileSystemBackend;
+pub use key_value::{KeyValueBackend, KeyValueStore};
+pub use layered::LayeredBackend;
+pub use memory_cache::MemoryBackend;
 
 /// A render that was cached from a previous render.
-pub struct CachedRender<'a> {
+pub struct CachedRender {
     /// The route that was rendered
     pub route: String,
     /// The freshness information for the rendered response
     pub freshness: RenderFreshness,
     /// The rendered response
-    pub response: &'a [u8],
+    pub response: Vec<u8>,
 }
 
 /// An incremental renderer.
 pub struct IncrementalRenderer {
-    pub(crate) memory_cache: InMemoryCache,
-    #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) file_system_cache: fs_cache::FileSystemCache,
+    backend: std::sync::Arc<dyn RenderCacheBackend>,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    /// Clear the backend before it is used for the first time
+    clear_on_start: AtomicBool,
 }
 
 impl IncrementalRenderer {
@@ -41,39 +52,81 @@ impl IncrementalRenderer {
     }
 
     /// Remove a route from the cache.
-    pub fn invalidate(&mut self, route: &str) {
-        self.memory_cache.invalidate(route);
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.invalidate(route);
+    pub async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.clear_if_starting().await?;
+        self.backend.invalidate(route).await
+    }
+
+    /// Remove every route that was cached with a tag. Returns the routes that were removed.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
+    /// let response = b"<html><body>Hello world</body></html>";
+    /// renderer
+    ///     .cache_with_tags("/posts/42".to_string(), response, vec!["post:42".to_string()])
+    ///     .await
+    ///     .unwrap();
+    /// let invalidated = renderer.invalidate_tag("post:42").await.unwrap();
+    /// assert_eq!(invalidated, vec!["/posts/42".to_string()]);
+    /// assert!(renderer.get("/posts/42").await.unwrap().is_none());
+    /// # }
+    /// ```
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        self.clear_if_starting().await?;
+        self.backend.invalidate_tag(tag).await
     }
 
     /// Remove all routes from the cache.
-    pub fn invalidate_all(&mut self) {
-        self.memory_cache.clear();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.clear();
+    pub async fn invalidate_all(&self) -> Result<(), IncrementalRendererError> {
+        self.clear_on_start.store(false, Ordering::Relaxed);
+        self.backend.clear().await
+    }
+
+    /// List every cached route.
+    pub async fn routes(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        self.clear_if_starting().await?;
+        self.backend.list().await
     }
 
     /// Cache a rendered response.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
     /// let route = "/index".to_string();
     /// let response = b"<html><body>Hello world</body></html>";
-    /// renderer.cache(route, response).unwrap();
+    /// renderer.cache(route, response).await.unwrap();
+    /// # }
     /// ```
-    pub fn cache(
-        &mut self,
+    pub async fn cache(
+        &self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+    ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.cache_with_tags(route, html, Vec::new()).await
+    }
+
+    /// Cache a rendered response with tags that can be used to invalidate it later with [`Self::invalidate_tag`].
+    /// The tags replace any tags the route was cached with before.
+    pub async fn cache_with_tags(
+        &self,
         route: String,
         html: impl Into<Vec<u8>>,
+        tags: Vec<String>,
     ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.clear_if_starting().await?;
         let timestamp = Utc::now();
-        let html = html.into();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache
-            .put(route.clone(), timestamp, html.clone())?;
-        self.memory_cache.put(route, timestamp, html);
+        let entry = CacheEntry {
+            timestamp,
+            html: html.into(),
+            tags,
+        };
+        self.backend.put(&route, entry).await?;
         Ok(RenderFreshness::created_at(
             timestamp,
             self.invalidate_after,
@@ -84,65 +137,72 @@ impl IncrementalRenderer {
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
     /// # let route = "/index".to_string();
     /// # let response = b"<html><body>Hello world</body></html>";
-    /// # renderer.cache(route, response).unwrap();
+    /// # renderer.cache(route, response).await.unwrap();
     /// let route = "/index";
-    /// let response = renderer.get(route).unwrap();
+    /// let response = renderer.get(route).await.unwrap();
     /// assert_eq!(response.unwrap().response, b"<html><body>Hello world</body></html>");
+    /// # }
     /// ```
     ///
     /// If the route is not cached, `None` is returned.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
-    /// let route = "/index";
-    /// let response = renderer.get(route).unwrap();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
+    /// let route = "/not-cached";
+    /// let response = renderer.get(route).await.unwrap();
     /// assert!(response.is_none());
+    /// # }
     /// ```
-    pub fn get<'a>(
-        &'a mut self,
-        route: &str,
-    ) -> Result<Option<CachedRender<'a>>, IncrementalRendererError> {
-        let Self {
-            memory_cache,
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache,
-            ..
-        } = self;
-
-        #[allow(unused)]
-        enum FsGetError {
-            NotPresent,
-            Error(IncrementalRendererError),
-        }
+    ///
+    /// Routes older than [`IncrementalRendererConfig::invalidate_after`] are returned as stale while they are
+    /// within the [`IncrementalRendererConfig::stale_while_revalidate`] window. After that they are removed.
+    pub async fn get(&self, route: &str) -> Result<Option<CachedRender>, IncrementalRendererError> {
+        self.clear_if_starting().await?;
+        let Some(entry) = self.backend.get(route).await? else {
+            return Ok(None);
+        };
 
-        // The borrow checker prevents us from simply using a match/if and returning early. Instead we need to use the more complex closure API
-        // non lexical lifetimes will make this possible (it works with polonius)
-        let or_insert = || {
-            // check the file cache
-            #[cfg(not(target_arch = "wasm32"))]
-            return match file_system_cache.get(route) {
-                Ok(Some((freshness, bytes))) => Ok((freshness.timestamp(), bytes)),
-                Ok(None) => Err(FsGetError::NotPresent),
-                Err(e) => Err(FsGetError::Error(e)),
-            };
-
-            #[allow(unreachable_code)]
-            Err(FsGetError::NotPresent)
+        // If the timestamp is in the future, the entry was just rendered
+        let age = Utc::now()
+            .signed_duration_since(entry.timestamp)
+            .to_std()
+            .unwrap_or_default();
+        let freshness = match self.invalidate_after {
+            Some(invalidate_after) => {
+                // The cache entry is out of date, so we need to remove it
+                let expires_after =
+                    invalidate_after + self.stale_while_revalidate.unwrap_or_default();
+                if age > expires_after {
+                    tracing::trace!("cache entry for {route:?} out of date");
+                    self.backend.invalidate(route).await?;
+                    return Ok(None);
+                }
+                RenderFreshness::new(age.as_secs(), invalidate_after.as_secs(), entry.timestamp)
+            }
+            None => RenderFreshness::new_age(age.as_secs(), entry.timestamp),
         };
 
-        match memory_cache.try_get_or_insert(route, or_insert) {
-            Ok(Some((freshness, bytes))) => Ok(Some(CachedRender {
-                route: route.to_string(),
-                freshness,
-                response: bytes,
-            })),
-            Err(FsGetError::NotPresent) | Ok(None) => Ok(None),
-            Err(FsGetError::Error(e)) => Err(e),
+        Ok(Some(CachedRender {
+            route: route.to_string(),
+            freshness,
+            response: entry.html,
+        }))
+    }
+
+    /// Clear the cache the first time it is used if the renderer was configured to clear the cache on startup
+    async fn clear_if_starting(&self) -> Result<(), IncrementalRendererError> {
+        if self.clear_on_start.swap(false, Ordering::Relaxed) {
+            self.backend.clear().await?;
         }
+        Ok(())
     }
 }
 
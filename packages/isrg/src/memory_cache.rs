@@ -1,89 +1,82 @@
-//! Incremental file based incremental rendering
+//! An in memory cache for rendered routes
 
 #![allow(non_snake_case)]
 
-use chrono::offset::Utc;
-use chrono::DateTime;
 use rustc_hash::FxHasher;
-use std::{hash::BuildHasherDefault, num::NonZeroUsize};
+use std::{hash::BuildHasherDefault, num::NonZeroUsize, sync::Mutex};
 
-use super::freshness::RenderFreshness;
+use crate::{CacheEntry, IncrementalRendererError, RenderCacheBackend};
 
-pub(crate) struct InMemoryCache {
+/// A [`RenderCacheBackend`] that keeps the most recently used routes in memory
+pub struct MemoryBackend {
     #[allow(clippy::type_complexity)]
-    lru: Option<lru::LruCache<String, (DateTime<Utc>, Vec<u8>), BuildHasherDefault<FxHasher>>>,
-    invalidate_after: Option<std::time::Duration>,
+    lru: Option<Mutex<lru::LruCache<String, CacheEntry, BuildHasherDefault<FxHasher>>>>,
 }
 
-impl InMemoryCache {
-    pub fn new(memory_cache_limit: usize, invalidate_after: Option<std::time::Duration>) -> Self {
+impl MemoryBackend {
+    /// Create a memory backend that holds up to `memory_cache_limit` routes. If the limit is zero, nothing is cached.
+    pub fn new(memory_cache_limit: usize) -> Self {
         Self {
             lru: NonZeroUsize::new(memory_cache_limit)
-                .map(|limit| lru::LruCache::with_hasher(limit, Default::default())),
-            invalidate_after,
+                .map(|limit| Mutex::new(lru::LruCache::with_hasher(limit, Default::default()))),
         }
     }
 
-    pub fn clear(&mut self) {
-        if let Some(cache) = &mut self.lru {
-            cache.clear();
+    fn with_lru<T: Default>(
+        &self,
+        f: impl FnOnce(&mut lru::LruCache<String, CacheEntry, BuildHasherDefault<FxHasher>>) -> T,
+    ) -> T {
+        match &self.lru {
+            Some(lru) => f(&mut lru.lock().unwrap_or_else(|err| err.into_inner())),
+            None => T::default(),
         }
     }
+}
 
-    pub fn put(&mut self, route: String, timestamp: DateTime<Utc>, data: Vec<u8>) {
-        if let Some(cache) = &mut self.lru {
-            cache.put(route, (timestamp, data));
+#[async_trait::async_trait]
+impl RenderCacheBackend for MemoryBackend {
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        let entry = self.with_lru(|lru| lru.get(route).cloned());
+        if entry.is_some() {
+            tracing::trace!("memory cache hit {:?}", route);
         }
+        Ok(entry)
     }
 
-    pub fn invalidate(&mut self, route: &str) {
-        if let Some(cache) = &mut self.lru {
-            cache.pop(route);
-        }
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        self.with_lru(|lru| {
+            lru.put(route.to_string(), entry);
+        });
+        Ok(())
     }
 
-    pub fn try_get_or_insert<'a, F: FnOnce() -> Result<(DateTime<Utc>, Vec<u8>), E>, E>(
-        &'a mut self,
-        route: &str,
-        or_insert: F,
-    ) -> Result<Option<(RenderFreshness, &'a [u8])>, E> {
-        if let Some(memory_cache) = self.lru.as_mut() {
-            let (timestamp, _) = memory_cache.try_get_or_insert(route.to_string(), or_insert)?;
-
-            let now = Utc::now();
-            let elapsed = timestamp.signed_duration_since(now);
-            let age = elapsed.num_seconds();
-            // The cache entry is out of date, so we need to remove it.
-            if let Some(invalidate_after) = self.invalidate_after {
-                // If we can't convert to a std duration, the duration is negative and hasn't elapsed yet.
-                if let Ok(std_elapsed) = elapsed.to_std() {
-                    if std_elapsed > invalidate_after {
-                        tracing::trace!("memory cache out of date");
-                        memory_cache.pop(route);
-                        return Ok(None);
-                    }
-                }
-            }
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.with_lru(|lru| {
+            lru.pop(route);
+        });
+        Ok(())
+    }
 
-            // We need to reborrow because we may have invalidated the lifetime if the route was removed.
-            // We know it wasn't because we returned... but rust doesn't understand that.
-            let (timestamp, cache_hit) = memory_cache.get(route).unwrap();
+    async fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        Ok(self.with_lru(|lru| lru.iter().map(|(route, _)| route.clone()).collect()))
+    }
 
-            return match self.invalidate_after {
-                Some(invalidate_after) => {
-                    tracing::trace!("memory cache hit");
-                    let max_age = invalidate_after.as_secs();
-                    let freshness = RenderFreshness::new(age as u64, max_age, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-                None => {
-                    tracing::trace!("memory cache hit");
-                    let freshness = RenderFreshness::new_age(age as u64, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-            };
-        }
+    async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        Ok(self.with_lru(|lru| {
+            let routes: Vec<_> = lru
+                .iter()
+                .filter(|(_, entry)| entry.tags.iter().any(|entry_tag| entry_tag == tag))
+                .map(|(route, _)| route.clone())
+                .collect();
+            for route in &routes {
+                lru.pop(route);
+            }
+            routes
+        }))
+    }
 
-        Ok(None)
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
+        self.with_lru(|lru| lru.clear());
+        Ok(())
     }
 }
@@ -0,0 +1,35 @@
+// @ts-check
+const { test, expect } = require("@playwright/test");
+
+test("islands hydrate and respond to events", async ({ page }) => {
+  await page.goto("http://localhost:7070");
+
+  // The server renders every island with its props
+  const first = page.locator("#first-counter");
+  const second = page.locator("#second-counter");
+  await expect(first).toHaveText("Count 1");
+  await expect(second).toHaveText("Count 10");
+
+  // Each island is hydrated in its own virtual dom
+  await first.click();
+  await expect(first).toHaveText("Count 2");
+  await expect(second).toHaveText("Count 10");
+
+  await second.click();
+  await second.click();
+  await expect(second).toHaveText("Count 12");
+  await expect(first).toHaveText("Count 2");
+});
+
+test("the static part of the page is not hydrated", async ({ page }) => {
+  await page.goto("http://localhost:7070");
+
+  // Wait for the islands to hydrate before clicking the static button
+  const first = page.locator("#first-counter");
+  await first.click();
+  await expect(first).toHaveText("Count 2");
+
+  const button = page.locator("#static-button");
+  await button.click();
+  await expect(button).toHaveText("Static 0");
+});
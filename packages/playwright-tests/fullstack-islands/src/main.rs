@@ -0,0 +1,41 @@
+// This test is used by playwright configured in the root of the repo
+// Tests:
+// - Rendering a page with islands
+// - Hydrating each island on its own
+// - Events inside of islands
+
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus::LaunchBuilder::new()
+        .with_cfg(server_only! {
+            dioxus::fullstack::ServeConfig::builder().enable_islands()
+        })
+        .launch(app);
+}
+
+fn app() -> Element {
+    // The static part of the page is never hydrated, so this button does nothing on the client
+    let mut static_count = use_signal(|| 0);
+
+    rsx! {
+        h1 { "A mostly static page" }
+        button {
+            id: "static-button",
+            onclick: move |_| static_count += 1,
+            "Static {static_count}"
+        }
+        Counter { id: "first-counter".to_string(), initial: 1 }
+        Counter { id: "second-counter".to_string(), initial: 10 }
+    }
+}
+
+#[island]
+fn Counter(id: String, initial: i32) -> Element {
+    let mut count = use_signal(|| initial);
+
+    rsx! {
+        button { id, onclick: move |_| count += 1, "Count {count}" }
+    }
+}
@@ -0,0 +1,66 @@
+// @ts-check
+const { test, expect } = require("@playwright/test");
+
+test("idle boundaries hydrate without interaction", async ({ page }) => {
+  await page.goto("http://localhost:7171");
+
+  const idle = page.locator("#idle");
+  await expect(idle.locator(".status")).toHaveText("Hydrated");
+
+  const counter = idle.locator(".counter");
+  await counter.click();
+  await expect(counter).toHaveText("Count 1");
+});
+
+test("visible boundaries hydrate when they scroll into view", async ({
+  page,
+}) => {
+  await page.goto("http://localhost:7171");
+
+  // Wait for the rest of the page to hydrate
+  await expect(page.locator("#idle .status")).toHaveText("Hydrated");
+
+  const visible = page.locator("#visible");
+  await expect(visible.locator(".status")).toHaveText("Not hydrated");
+
+  await visible.scrollIntoViewIfNeeded();
+  await expect(visible.locator(".status")).toHaveText("Hydrated");
+
+  const counter = visible.locator(".counter");
+  await counter.click();
+  await expect(counter).toHaveText("Count 1");
+});
+
+test("interaction boundaries replay the event that hydrated them", async ({
+  page,
+}) => {
+  await page.goto("http://localhost:7171");
+
+  // Wait for the rest of the page to hydrate
+  await expect(page.locator("#idle .status")).toHaveText("Hydrated");
+
+  const interaction = page.locator("#interaction");
+  await expect(interaction.locator(".status")).toHaveText("Not hydrated");
+
+  // The first click hydrates the boundary and is handled once the counter is hydrated
+  const counter = interaction.locator(".counter");
+  await counter.click();
+  await expect(interaction.locator(".status")).toHaveText("Hydrated");
+  await expect(counter).toHaveText("Count 1");
+});
+
+test("interaction boundaries run the default action of the replayed click", async ({
+  page,
+}) => {
+  await page.goto("http://localhost:7171");
+
+  // Wait for the rest of the page to hydrate
+  await expect(page.locator("#idle .status")).toHaveText("Hydrated");
+
+  // The click is cancelled while the boundary hydrates. Replaying it has to toggle the checkbox
+  const interaction = page.locator("#interaction");
+  const checkbox = interaction.locator(".checkbox");
+  await checkbox.click();
+  await expect(checkbox).toBeChecked();
+  await expect(interaction.locator(".checked")).toHaveText("Checked: true");
+});
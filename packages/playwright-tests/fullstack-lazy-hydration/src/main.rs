@@ -0,0 +1,62 @@
+// This test is used by playwright configured in the root of the repo
+// Tests:
+// - Lazy hydration with the idle, visible and interaction triggers
+// - Replaying the event that triggered hydration with its default action
+
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus::launch(app);
+}
+
+fn app() -> Element {
+    rsx! {
+        div { id: "idle",
+            LazyHydrate { trigger: HydrationTrigger::Idle, Counter {} }
+        }
+        div { id: "interaction",
+            LazyHydrate { trigger: HydrationTrigger::Interaction,
+                Counter {}
+                Checkbox {}
+            }
+        }
+        // Push the visible boundary below the fold
+        div { style: "height: 3000px" }
+        div { id: "visible",
+            LazyHydrate { trigger: HydrationTrigger::Visible, Counter {} }
+        }
+    }
+}
+
+#[component]
+fn Counter() -> Element {
+    let mut hydrated = use_signal(|| false);
+    let mut count = use_signal(|| 0);
+    use_effect(move || hydrated.set(true));
+
+    rsx! {
+        p { class: "status",
+            if hydrated() {
+                "Hydrated"
+            } else {
+                "Not hydrated"
+            }
+        }
+        button { class: "counter", onclick: move |_| count += 1, "Count {count}" }
+    }
+}
+
+#[component]
+fn Checkbox() -> Element {
+    let mut checked = use_signal(|| false);
+
+    rsx! {
+        input {
+            class: "checkbox",
+            r#type: "checkbox",
+            oninput: move |event| checked.set(event.checked()),
+        }
+        p { class: "checked", "Checked: {checked}" }
+    }
+}
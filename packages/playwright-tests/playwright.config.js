@@ -105,12 +105,21 @@ module.exports = defineConfig({
     {
       cwd: path.join(process.cwd(), "web-hash-routing"),
       command:
-        'cargo run --package dioxus-cli --release -- run --verbose --force-sequential --platform web --addr "127.0.0.1" --port 2021',
+        'cargo run --package dioxus-cli --release -- run --verbose --bin web-hash-routing --force-sequential --platform web --addr "127.0.0.1" --port 2021',
       port: 2021,
       timeout: 50 * 60 * 1000,
       reuseExistingServer: !process.env.CI,
       stdout: "pipe",
     },
+    {
+      cwd: path.join(process.cwd(), "web-hash-routing"),
+      command:
+        'cargo run --package dioxus-cli --release -- run --verbose --bin web-hash-routing-prefixed --force-sequential --platform web --addr "127.0.0.1" --port 2022',
+      port: 2022,
+      timeout: 50 * 60 * 1000,
+      reuseExistingServer: !process.env.CI,
+      stdout: "pipe",
+    },
     {
       cwd: path.join(process.cwd(), "fullstack"),
       command:
@@ -138,6 +147,24 @@ module.exports = defineConfig({
       reuseExistingServer: !process.env.CI,
       stdout: "pipe",
     },
+    {
+      cwd: path.join(process.cwd(), "fullstack-islands"),
+      command:
+        'cargo run --package dioxus-cli --release -- run --verbose --force-sequential --platform web --addr "127.0.0.1" --port 7070',
+      port: 7070,
+      timeout: 50 * 60 * 1000,
+      reuseExistingServer: !process.env.CI,
+      stdout: "pipe",
+    },
+    {
+      cwd: path.join(process.cwd(), "fullstack-lazy-hydration"),
+      command:
+        'cargo run --package dioxus-cli --release -- run --verbose --force-sequential --platform web --addr "127.0.0.1" --port 7171',
+      port: 7171,
+      timeout: 50 * 60 * 1000,
+      reuseExistingServer: !process.env.CI,
+      stdout: "pipe",
+    },
     {
       cwd: path.join(process.cwd(), "suspense-carousel"),
       command:
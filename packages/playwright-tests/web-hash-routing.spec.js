@@ -29,3 +29,46 @@ test("fallback", async ({ page }) => {
   const main = page.locator("#not-found");
   await expect(main).toContainText("NotFound");
 });
+
+test("anchor without a route", async ({ page }) => {
+  await page.goto("http://localhost:2021/#/other");
+
+  // Expect clicking a link to #section to keep the route and only change the fragment
+  const link = page.locator("#section-link");
+  await link.click();
+  await expect(page).toHaveURL("http://localhost:2021/#/other#section");
+  await expect(page.locator("#other")).toContainText("Other");
+
+  // Reloading the page keeps the route
+  await page.reload();
+  await expect(page.locator("#other")).toContainText("Other");
+});
+
+test("prefix redirect", async ({ page }) => {
+  await page.goto("http://localhost:2022");
+
+  // Expect the route to be written after the prefix
+  const main = page.locator("#other");
+  await expect(main).toContainText("Other");
+  await expect(page).toHaveURL("http://localhost:2022/#!/other");
+});
+
+test("prefix links", async ({ page }) => {
+  await page.goto("http://localhost:2022/#!/other");
+
+  // Expect links to include the prefix
+  const link = page.locator("a[href='/#!/other/123']");
+  await link.click();
+  await expect(page).toHaveURL("http://localhost:2022/#!/other/123");
+  await expect(page.locator("#other-id")).toContainText("OtherId 123");
+});
+
+test("prefix anchor without a route", async ({ page }) => {
+  await page.goto("http://localhost:2022/#!/other");
+
+  // Expect the fragment to follow the route after the prefix
+  const link = page.locator("#section-link");
+  await link.click();
+  await expect(page).toHaveURL("http://localhost:2022/#!/other#section");
+  await expect(page.locator("#other")).toContainText("Other");
+});
@@ -0,0 +1,4 @@
+// Keep the route after `#!` in the url
+fn main() {
+    dioxus_playwright_web_hash_routing_test::launch(Some("!".to_string()));
+}
@@ -0,0 +1,82 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr};
+
+/// The locales of the route enum: `#[i18n(locales = ["en", "fr"], default = "en")]`
+#[derive(Debug)]
+pub struct I18n {
+    pub locales: Vec<LitStr>,
+    pub default: LitStr,
+}
+
+impl Parse for I18n {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut locales = None;
+        let mut default = None;
+
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+            if name == "locales" {
+                let content;
+                syn::bracketed!(content in input);
+                let list =
+                    content.parse_terminated(|input| input.parse::<LitStr>(), syn::Token![,])?;
+                locales = Some(list.into_iter().collect::<Vec<_>>());
+            } else if name == "default" {
+                default = Some(input.parse::<LitStr>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Unknown i18n option. Expected `locales` or `default`",
+                ));
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<syn::Token![,]>()?;
+        }
+
+        let Some(locales) = locales.filter(|locales| !locales.is_empty()) else {
+            return Err(
+                input.error("Expected a list of locales in the form `locales = [\"en\", \"fr\"]`")
+            );
+        };
+        for locale in &locales {
+            let value = locale.value();
+            if value.is_empty() || value.contains(['/', '?', '#']) {
+                return Err(syn::Error::new_spanned(
+                    locale,
+                    "Locales must be a single, non-empty route segment",
+                ));
+            }
+        }
+        // The first locale is the default if none is specified
+        let default = default.unwrap_or_else(|| locales[0].clone());
+        if !locales
+            .iter()
+            .any(|locale| locale.value() == default.value())
+        {
+            return Err(syn::Error::new_spanned(
+                default,
+                "The default locale must be one of the locales",
+            ));
+        }
+
+        Ok(Self { locales, default })
+    }
+}
+
+impl I18n {
+    pub fn locales_const(&self) -> TokenStream {
+        let locales = &self.locales;
+        let default = &self.default;
+        quote! {
+            const LOCALES: Option<dioxus_router::i18n::Locales> = Some(dioxus_router::i18n::Locales {
+                locales: &[#(#locales),*],
+                default: #default,
+            });
+        }
+    }
+}
@@ -10,6 +10,7 @@ pub struct LayoutId(pub usize);
 #[derive(Debug)]
 pub struct Layout {
     pub comp: Path,
+    pub loader: Option<Path>,
     pub active_nests: Vec<NestId>,
 }
 
@@ -27,6 +28,21 @@ impl Layout {
             }
         }
     }
+
+    /// Call the loader for this layout with the dynamic segments of the active nests. Returns the
+    /// loader path along with the future it returns.
+    pub fn loader(&self, nests: &[Nest]) -> Option<(&Path, TokenStream)> {
+        let loader = self.loader.as_ref()?;
+        let dynamic_segments = self
+            .active_nests
+            .iter()
+            .flat_map(|id| nests[id.0].dynamic_segments());
+
+        Some((
+            loader,
+            quote! { #loader(#(::std::clone::Clone::clone(#dynamic_segments),)*) },
+        ))
+    }
 }
 
 impl Layout {
@@ -35,6 +51,24 @@ impl Layout {
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
 
-        Ok(Self { comp, active_nests })
+        // Then parse the optional `loader = function` option
+        let mut loader = None;
+        if input.parse::<syn::Token![,]>().is_ok() && !input.is_empty() {
+            let name = input.parse::<syn::Ident>()?;
+            if name != "loader" {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Unknown layout option. Expected `loader`",
+                ));
+            }
+            input.parse::<syn::Token![=]>()?;
+            loader = Some(input.parse()?);
+        }
+
+        Ok(Self {
+            comp,
+            loader,
+            active_nests,
+        })
     }
 }
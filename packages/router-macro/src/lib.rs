@@ -3,13 +3,15 @@
 
 extern crate proc_macro;
 
+use i18n::I18n;
 use layout::Layout;
 use nest::{Nest, NestId};
 use proc_macro::TokenStream;
-use quote::{__private::Span, format_ident, quote, ToTokens};
+use quote::{__private::Span, quote, ToTokens};
 use redirect::Redirect;
-use route::{Route, RouteType};
+use route::{Route, RouteType, StatusRoute};
 use segment::RouteSegment;
+use sitemap::SitemapSection;
 use syn::{parse::ParseStream, parse_macro_input, Ident, Token, Type};
 
 use proc_macro2::TokenStream as TokenStream2;
@@ -17,6 +19,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use crate::{layout::LayoutId, route_tree::ParseRouteTree};
 
 mod hash;
+mod i18n;
 mod layout;
 mod nest;
 mod query;
@@ -24,6 +27,7 @@ mod redirect;
 mod route;
 mod route_tree;
 mod segment;
+mod sitemap;
 
 /// Derives the Routable trait for an enum of routes
 ///
@@ -36,7 +40,7 @@ mod segment;
 /// 1. Static Segments: "/static"
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
-/// 4. Query Segments: "/?:..query" (where query has a type that is FromQuery in all child Variants) or "/?:query&:other_query" (where query and other_query has a type that is FromQueryArgument in all child Variants)
+/// 4. Query Segments: "/?:..query" (where query has a type that is FromQuery or serde Deserialize in all child Variants) or "/?:query&:other_query" (where query and other_query has a type that is FromQueryArgument in all child Variants)
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -91,11 +95,42 @@ mod segment;
 /// # fn IndexComponent() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[route("path", component)]`
+/// # `#[i18n(locales = ["en", "fr"], default = "en")]`
 ///
-/// The `#[route]` attribute is used to define a route. It takes up to 2 parameters:
+/// The `#[i18n]` attribute on the enum lets every route be prefixed with a locale. It takes 2 parameters:
+/// - `locales`: The locales the app supports
+/// - (optional) `default`: The locale of routes without a locale prefix. If not specified, the first locale is used
+///
+/// Links and navigation keep the current locale. Read the active locale with `use_locale`.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// #[i18n(locales = ["en", "fr"], default = "en")]
+/// enum Route {
+///     // This is at /about in English and /fr/about in French
+///     #[route("/about")]
+///     About {},
+/// }
+/// # #[component]
+/// # fn About() -> Element { VNode::empty() }
+/// ```
+///
+/// # `#[route("path", component, lazy, loader = function, intercept = "outlet", changefreq = "daily", priority = 0.5, noindex, title = "Title", meta(key = "value"), not_found)]`
+///
+/// The `#[route]` attribute is used to define a route. It takes up to 11 parameters:
 /// - `path`: The path to the enum variant (relative to the parent nest)
 /// - (optional) `component`: The component to render when the route is matched. If not specified, the name of the variant is used
+/// - (optional) `lazy`: Split the component and everything only it uses into a separate wasm module when the `wasm-split` feature is enabled
+/// - (optional) `loader`: An async function that loads data for the route. It takes the same parameters as the component and its output can be read with `use_loader_data`
+/// - (optional) `intercept`: The named outlet the component renders into over the current page when the user navigates to the route. Direct loads render the route as a full page
+/// - (optional) `changefreq`: How often the page changes for the sitemap. One of `always`, `hourly`, `daily`, `weekly`, `monthly`, `yearly` or `never`
+/// - (optional) `priority`: The priority of the page in the sitemap between `0.0` and `1.0`
+/// - (optional) `noindex`: Leave the page out of the sitemap
+/// - (optional) `title`: The title of the page for the document and `use_breadcrumbs`. It can use the fields of the route like `format!`: `title = "Post {id}"`
+/// - (optional) `meta`: The `<meta>` tags of the page in the head of the document. Keys that aren't identifiers are strings: `meta(description = "About us", "og:type" = "website")`
+/// - (optional) `not_found` or `error`: Mark the page for paths that don't match any other route or the page the router renders when a routed component throws an error. During server side rendering, they respond with a `404 Not Found` or `500 Internal Server Error` status. The error route can't have any fields or be inside a layout
 ///
 /// Routes are the most basic attribute. They allow you to define a route and the component to render when the route is matched. The component must take all dynamic parameters of the route and all parent nests.
 /// The next variant will be tied to the component. If you link to that variant, the component will be rendered.
@@ -114,11 +149,109 @@ mod segment;
 /// # fn Index() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[redirect("path", function)]`
+/// Loaders for a route and all of its layouts start in parallel as soon as the router navigates to the route, so nested layouts don't need to wait for each other to fetch their data.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     // Load the post before rendering the Blog component
+///     #[route("/blog/:id", loader = load_post)]
+///     Blog { id: usize },
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Post {
+///     title: String,
+/// }
+///
+/// async fn load_post(id: usize) -> Post {
+///     Post { title: format!("Post {id}") }
+/// }
+///
+/// #[component]
+/// fn Blog(id: usize) -> Element {
+///     let post = use_loader_data::<Post>()?;
+///     rsx! { h1 { "{post.title}" } }
+/// }
+/// ```
+///
+/// Lazy routes are downloaded the first time the user navigates to them. The route suspends while the module loads, so the closest suspense boundary will show its fallback.
+/// During server side rendering, the module for the current route is preloaded in the head of the page.
+///
+/// Only routes marked `lazy` are split. Before `lazy` was added, every route was split into its own module when the `wasm-split` feature was enabled. Add `lazy` to the routes that should still be split when upgrading.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     // The admin panel is only downloaded when the user visits it
+///     #[route("/admin", lazy)]
+///     Admin {},
+/// }
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin() -> Element { VNode::empty() }
+/// ```
+///
+/// The not found route is usually a catch all route at the end of the enum. Components can read the errors the error route renders for with the `ErrorContext` of the router.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/500", error)]
+///     ServerError {},
+///     #[route("/:..segments", not_found)]
+///     NotFound { segments: Vec<String> },
+/// }
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn ServerError() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn NotFound(segments: Vec<String>) -> Element { VNode::empty() }
+/// ```
+///
+/// # `#[slot("name", component)]`
 ///
-/// The `#[redirect]` attribute is used to define a redirect. It takes 2 parameters:
+/// The `#[slot]` attribute fills a named outlet while the route is active. It takes 2 parameters:
+/// - `name`: The name of the outlet, as in `Outlet::<Route> { name: "name" }`
+/// - `component`: The component to render in the outlet. It takes the same parameters as the route component
+///
+/// A route can fill any number of named outlets.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     // The Dashboard component renders the DashboardSidebar in the outlet named "sidebar"
+///     #[route("/dashboard/:id")]
+///     #[slot("sidebar", DashboardSidebar)]
+///     Dashboard { id: usize },
+/// }
+/// # #[component]
+/// # fn Dashboard(id: usize) -> Element { VNode::empty() }
+/// # #[component]
+/// # fn DashboardSidebar(id: usize) -> Element { VNode::empty() }
+/// ```
+///
+/// # `#[redirect("path", function, permanent)]`
+///
+/// The `#[redirect]` attribute is used to define a redirect. It takes up to 3 parameters:
 /// - `path`: The path to the enum variant (relative to the parent nest)
 /// - `function`: A function that takes the parameters from the path and returns a new route
+/// - (optional) `permanent`: Marks the redirect as permanent. The server answers it with a `308 Permanent Redirect` instead of a `307 Temporary Redirect`
 ///
 /// ```rust
 /// use dioxus::prelude::*;
@@ -127,6 +260,8 @@ mod segment;
 /// enum Route {
 ///     // Redirects the /:id route to the Index route
 ///     #[redirect("/:id", |id: usize| Route::Index {})]
+///     // Redirects the /home route to the Index route for good
+///     #[redirect("/home", || Route::Index {}, permanent)]
 ///     #[route("/", Index)]
 ///     Index {},
 /// }
@@ -136,6 +271,37 @@ mod segment;
 ///
 /// Redirects allow you to redirect a route to another route. The function must take all dynamic parameters of the route and all parent nests.
 ///
+/// # `#[guard(function)]`
+///
+/// The `#[guard]` attribute runs a function before the route renders. It takes 1 parameter:
+/// - `function`: A function that takes the current route and returns a `GuardOutcome` that allows the route, redirects to another route or renders a fallback
+///
+/// A guard directly after a `#[nest]` or `#[layout]` attribute protects every route until the matching `#[end_nest]` or `#[end_layout]`. Otherwise, it only protects the route it is attached to. Guards run from the outermost block to the route itself.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::router::GuardOutcome;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[nest("/admin")]
+///     #[guard(require_login)]
+///         #[route("/")]
+///         Admin {},
+///     #[end_nest]
+///     #[route("/login")]
+///     Login {},
+/// }
+///
+/// fn require_login(_: &Route) -> GuardOutcome<Route> {
+///     GuardOutcome::Redirect(Route::Login {}.into())
+/// }
+/// # #[component]
+/// # fn Login() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin() -> Element { VNode::empty() }
+/// ```
+///
 /// # `#[nest("path")]`
 ///
 /// The `#[nest]` attribute is used to define a nest. It takes 1 parameter:
@@ -187,10 +353,11 @@ mod segment;
 /// # fn Home() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[layout(component)]`
+/// # `#[layout(component, loader = function)]`
 ///
-/// The `#[layout]` attribute is used to define a layout. It takes 1 parameter:
+/// The `#[layout]` attribute is used to define a layout. It takes up to 2 parameters:
 /// - `component`: The component to render when the route is matched. If not specified, the name of the variant is used
+/// - (optional) `loader`: An async function that loads data for the layout. It takes the dynamic parameters of the nests the layout is in
 ///
 /// The layout component allows you to wrap all children of the layout in a component. The child routes are rendered in the Outlet of the layout component. The layout component must take all dynamic parameters of the nests it is nested in.
 ///
@@ -241,7 +408,9 @@ mod segment;
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route, nest, end_nest, layout, end_layout, redirect, guard, child, slot, i18n
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -276,6 +445,7 @@ struct RouteEnum {
     nests: Vec<Nest>,
     layouts: Vec<Layout>,
     site_map: Vec<SiteMapSegment>,
+    i18n: Option<I18n>,
 }
 
 impl RouteEnum {
@@ -293,8 +463,14 @@ impl RouteEnum {
         let mut nests = Vec::new();
         let mut nest_stack = Vec::new();
 
+        // The guards of every open nest and layout block from the outermost to the innermost
+        let mut guard_stack: Vec<(GuardBlock, Vec<syn::Path>)> = Vec::new();
+
         for variant in &data.variants {
             let mut excluded = Vec::new();
+            let mut route_guards = Vec::new();
+            // Guards directly after a `#[nest]` or `#[layout]` attribute protect the whole block
+            let mut guards_block = false;
             // Apply the any nesting attributes in order
             for attr in &variant.attrs {
                 if attr.path().is_ident("nest") {
@@ -352,8 +528,12 @@ impl RouteEnum {
 
                     nests.push(nest);
                     nest_stack.push(NestId(nest_index));
+                    guard_stack.push((GuardBlock::Nest, Vec::new()));
+                    guards_block = true;
                 } else if attr.path().is_ident("end_nest") {
                     nest_stack.pop();
+                    pop_guard_block(&mut guard_stack, GuardBlock::Nest);
+                    guards_block = false;
                     // pop the current nest segment off the stack and add it to the parent or the site map
                     if let Some(segment) = site_map_stack.pop() {
                         let children = site_map_stack
@@ -388,19 +568,33 @@ impl RouteEnum {
                             ));
                         };
                         excluded.push(LayoutId(layout_index));
+                        guards_block = false;
                     } else {
                         let layout_index = layouts.len();
                         layouts.push(layout);
                         layout_stack.push(LayoutId(layout_index));
+                        guard_stack.push((GuardBlock::Layout, Vec::new()));
+                        guards_block = true;
                     }
                 } else if attr.path().is_ident("end_layout") {
                     layout_stack.pop();
+                    pop_guard_block(&mut guard_stack, GuardBlock::Layout);
+                    guards_block = false;
                 } else if attr.path().is_ident("redirect") {
                     let parser = |input: ParseStream| {
                         Redirect::parse(input, nest_stack.clone(), endpoints.len())
                     };
                     let redirect = attr.parse_args_with(parser)?;
                     endpoints.push(RouteEndpoint::Redirect(redirect));
+                    guards_block = false;
+                } else if attr.path().is_ident("guard") {
+                    let guard: syn::Path = attr.parse_args()?;
+                    match guard_stack.last_mut() {
+                        Some((_, guards)) if guards_block => guards.push(guard),
+                        _ => route_guards.push(guard),
+                    }
+                } else {
+                    guards_block = false;
                 }
             }
 
@@ -408,7 +602,14 @@ impl RouteEnum {
             let mut active_layouts = layout_stack.clone();
             active_layouts.retain(|&id| !excluded.contains(&id));
 
-            let route = Route::parse(active_nests, active_layouts, variant.clone())?;
+            // Layouts the route opts out of still protect it with their guards
+            let mut guards: Vec<_> = guard_stack
+                .iter()
+                .flat_map(|(_, guards)| guards.iter().cloned())
+                .collect();
+            guards.extend(route_guards);
+
+            let route = Route::parse(active_nests, active_layouts, guards, variant.clone())?;
 
             // add the route to the site map
             let mut segment = SiteMapSegment::new(&route.segments);
@@ -467,12 +668,37 @@ impl RouteEnum {
             children.push(current);
         }
 
+        // The router renders the error route when a routed component fails, so there can only be one
+        if let Some(route) = endpoints
+            .iter()
+            .filter_map(|endpoint| match endpoint {
+                RouteEndpoint::Route(route) if route.status == Some(StatusRoute::Error) => {
+                    Some(route)
+                }
+                _ => None,
+            })
+            .nth(1)
+        {
+            return Err(syn::Error::new_spanned(
+                &route.route_name,
+                "Only one route can be the `error` route",
+            ));
+        }
+
+        let i18n = data
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("i18n"))
+            .map(|attr| attr.parse_args::<I18n>())
+            .transpose()?;
+
         let myself = Self {
             name: name.clone(),
             endpoints,
             nests,
             layouts,
             site_map,
+            i18n,
         };
 
         Ok(myself)
@@ -503,14 +729,9 @@ impl RouteEnum {
     }
 
     fn parse_impl(&self) -> TokenStream2 {
-        let tree = ParseRouteTree::new(&self.endpoints, &self.nests);
         let name = &self.name;
-
-        let error_name = format_ident!("{}MatchError", self.name);
-        let tokens = tree.roots.iter().map(|&id| {
-            let route = tree.get(id).unwrap();
-            route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
-        });
+        let error_name = self.error_name();
+        let parse_body = self.parse_body(&self.endpoints);
 
         quote! {
             impl<'a> ::core::convert::TryFrom<&'a str> for #name {
@@ -525,42 +746,103 @@ impl RouteEnum {
                 type Err = dioxus_router::routable::RouteParseError<#error_name>;
 
                 fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
-                    let route = s;
-                    let (route, hash) = route.split_once('#').unwrap_or((route, ""));
-                    let (route, query) = route.split_once('?').unwrap_or((route, ""));
-                    // Remove any trailing slashes. We parse /route/ and /route in the same way
-                    // Note: we don't use trim because it includes more code
-                    let route = route.strip_suffix('/').unwrap_or(route);
-                    let query = dioxus_router::exports::percent_encoding::percent_decode_str(query)
-                        .decode_utf8()
-                        .unwrap_or(query.into());
-                    let hash = dioxus_router::exports::percent_encoding::percent_decode_str(hash)
-                        .decode_utf8()
-                        .unwrap_or(hash.into());
-                    let mut segments = route.split('/').map(|s| {
-                        dioxus_router::exports::percent_encoding::percent_decode_str(s)
-                            .decode_utf8()
-                            .unwrap_or(s.into())
-                    });
-                    // skip the first empty segment
-                    if s.starts_with('/') {
-                        let _ = segments.next();
-                    } else {
-                        // if this route does not start with a slash, it is not a valid route
-                        return Err(dioxus_router::routable::RouteParseError {
-                            attempted_routes: Vec::new(),
-                        });
-                    }
-                    let mut errors = Vec::new();
+                    #parse_body
+                }
+            }
+        }
+    }
 
-                    #(#tokens)*
+    /// The body of a function that parses the string `s` with some of the routes and redirects
+    fn parse_body<'a>(
+        &'a self,
+        endpoints: impl IntoIterator<Item = &'a RouteEndpoint>,
+    ) -> TokenStream2 {
+        let tree = ParseRouteTree::new(endpoints, &self.nests);
+        let error_name = self.error_name();
+        let tokens = tree.roots.iter().map(|&id| {
+            let route = tree.get(id).unwrap();
+            route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
+        });
 
-                    Err(dioxus_router::routable::RouteParseError {
-                        attempted_routes: errors,
-                    })
+        quote! {
+        let route = s;
+        let (route, hash) = route.split_once('#').unwrap_or((route, ""));
+        let (route, query) = route.split_once('?').unwrap_or((route, ""));
+        // Remove any trailing slashes. We parse /route/ and /route in the same way
+        // Note: we don't use trim because it includes more code
+        let route = route.strip_suffix('/').unwrap_or(route);
+        // Serde query structs decode the raw query themselves so encoded `&` and `=` are preserved
+        #[allow(unused)]
+        let raw_query = query;
+        let query = dioxus_router::exports::percent_encoding::percent_decode_str(query)
+            .decode_utf8()
+            .unwrap_or(query.into());
+        let hash = dioxus_router::exports::percent_encoding::percent_decode_str(hash)
+            .decode_utf8()
+            .unwrap_or(hash.into());
+        let mut segments = route.split('/').map(|s| {
+            dioxus_router::exports::percent_encoding::percent_decode_str(s)
+                .decode_utf8()
+                .unwrap_or(s.into())
+        });
+        // skip the first empty segment
+        if s.starts_with('/') {
+            let _ = segments.next();
+        } else {
+            // if this route does not start with a slash, it is not a valid route
+            return Err(dioxus_router::routable::RouteParseError {
+                attempted_routes: Vec::new(),
+            });
+        }
+        let mut errors = Vec::new();
+
+        #(#tokens)*
+
+        Err(dioxus_router::routable::RouteParseError {
+            attempted_routes: errors,
+        })
+        }
+    }
+
+    /// Find the kind of the redirect a path matches by parsing the path with only the temporary or
+    /// only the permanent redirects. The path is a redirect if the redirect parses into the same
+    /// route as the full enum
+    fn redirect_kind_impl(&self) -> Option<TokenStream2> {
+        let redirects = |permanent: bool| {
+            self.endpoints.iter().filter(move |endpoint| {
+                matches!(endpoint, RouteEndpoint::Redirect(redirect) if redirect.permanent == permanent)
+            })
+        };
+        if redirects(false).next().is_none() && redirects(true).next().is_none() {
+            return None;
+        }
+
+        let error_name = self.error_name();
+        let kinds = [
+            (true, quote! { dioxus_router::guard::RedirectKind::Permanent }),
+            (false, quote! { dioxus_router::guard::RedirectKind::Temporary }),
+        ]
+        .into_iter()
+        .filter(|(permanent, _)| redirects(*permanent).next().is_some())
+        .map(|(permanent, kind)| {
+            let parse_body = self.parse_body(redirects(permanent));
+            quote! {
+                let parse = |s: &str| -> ::std::result::Result<Self, dioxus_router::routable::RouteParseError<#error_name>> {
+                    #parse_body
+                };
+                if parse(path).is_ok_and(|redirect| redirect.to_string() == route) {
+                    return Some(#kind);
                 }
             }
-        }
+        });
+
+        Some(quote! {
+            fn redirect_kind(path: &str) -> Option<dioxus_router::guard::RedirectKind> {
+                let route = <Self as ::std::str::FromStr>::from_str(path).ok()?.to_string();
+                #(#kinds)*
+                None
+            }
+        })
     }
 
     fn error_name(&self) -> Ident {
@@ -668,27 +950,231 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut loader_matches = Vec::new();
+        let mut split_modules = Vec::new();
+        let mut preload_matches = Vec::new();
+        let mut split_module_matches = Vec::new();
+        let mut sitemap_options_matches = Vec::new();
+        let mut metadata_matches = Vec::new();
+        let mut guard_matches = Vec::new();
+        let mut is_guarded_matches = Vec::new();
+        let mut http_status_matches = Vec::new();
+        let mut error_route = None;
+        let mut slot_matches = Vec::new();
+        let mut intercept_matches = Vec::new();
+        let mut render_intercepted_matches = Vec::new();
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
                 matches.push(route.routable_match(&self.layouts, &self.nests, name));
+                loader_matches.extend(route.loaders_match(&self.layouts, &self.nests));
+                split_modules.extend(route.split_module_items(name));
+                preload_matches.extend(route.preload_match());
+                split_module_matches.extend(route.split_module_match());
+                sitemap_options_matches.extend(route.sitemap_options_match());
+                metadata_matches.extend(route.metadata_match());
+                guard_matches.extend(route.guard_match());
+                is_guarded_matches.extend(route.is_guarded_match());
+                http_status_matches.extend(route.http_status_match());
+                if route.status == Some(StatusRoute::Error) {
+                    let route_name = &route.route_name;
+                    error_route = Some(quote! {
+                        fn error_route() -> Option<Self> {
+                            Some(Self::#route_name {})
+                        }
+                    });
+                }
+                slot_matches.extend(route.slot_matches());
+                intercept_matches.extend(route.intercept_match());
+                render_intercepted_matches.extend(route.render_intercepted_match());
             }
         }
 
+        // Only generate the loaders if at least one route has a loader to keep the codegen small
+        let loaders = (!loader_matches.is_empty()).then(|| {
+            quote! {
+                fn loaders(&self) -> Vec<dioxus_router::RouteLoader> {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#loader_matches)*
+                        _ => Vec::new(),
+                    }
+                }
+            }
+        });
+
+        // Only generate the split module functions if at least one route is lazy or has a child router
+        let split_module = (!split_module_matches.is_empty()).then(|| {
+            quote! {
+                fn split_module(&self) -> Option<&'static str> {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#split_module_matches)*
+                        _ => None,
+                    }
+                }
+
+                dioxus::config_macros::maybe_wasm_split_items! {
+                    if wasm_split {
+                        fn preload(&self) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ()>>> {
+                            #[allow(unused, unreachable_patterns)]
+                            match self {
+                                #(#preload_matches)*
+                                _ => Box::pin(::std::future::ready(())),
+                            }
+                        }
+                    } else {}
+                }
+            }
+        });
+
+        // Only generate the sitemap options if at least one route sets them or has a child router
+        let sitemap_options = (!sitemap_options_matches.is_empty()).then(|| {
+            quote! {
+                fn sitemap_options(&self) -> dioxus_router::sitemap::SitemapOptions {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#sitemap_options_matches)*
+                        _ => Default::default(),
+                    }
+                }
+            }
+        });
+
+        // Only generate the metadata if at least one route has a title, head metadata or a child router
+        let metadata = (!metadata_matches.is_empty()).then(|| {
+            quote! {
+                fn metadata(&self) -> dioxus_router::metadata::RouteMetadata {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#metadata_matches)*
+                        _ => Default::default(),
+                    }
+                }
+            }
+        });
+
+        // Only generate the guards if at least one route is guarded or has a child router
+        let guard = (!guard_matches.is_empty()).then(|| {
+            quote! {
+                fn guard(&self) -> dioxus_router::guard::GuardOutcome<Self> {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#guard_matches)*
+                        _ => dioxus_router::guard::GuardOutcome::Allow,
+                    }
+                }
+
+                fn is_guarded(&self) -> bool {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#is_guarded_matches)*
+                        _ => false,
+                    }
+                }
+            }
+        });
+
+        let redirect_kind = self.redirect_kind_impl();
+
+        // Only generate the http status if at least one route is a not found or error route or has
+        // a child router
+        let http_status = (!http_status_matches.is_empty()).then(|| {
+            quote! {
+                fn http_status(&self) -> Option<u16> {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#http_status_matches)*
+                        _ => None,
+                    }
+                }
+            }
+        });
+
+        // Only generate the named outlets if at least one route fills a slot
+        let render_slot = (!slot_matches.is_empty()).then(|| {
+            quote! {
+                fn render_slot(&self, name: &str) -> dioxus_core::Element {
+                    let myself = self.clone();
+                    #[allow(unused, unreachable_patterns)]
+                    match (name, myself) {
+                        #(#slot_matches)*
+                        _ => VNode::empty()
+                    }
+                }
+            }
+        });
+
+        // Only generate the intercepting routes if at least one route intercepts navigation
+        let intercept = (!intercept_matches.is_empty()).then(|| {
+            quote! {
+                fn intercept_outlet(&self) -> Option<&'static str> {
+                    #[allow(unused, unreachable_patterns)]
+                    match self {
+                        #(#intercept_matches)*
+                        _ => None,
+                    }
+                }
+
+                fn render_intercepted(&self) -> dioxus_core::Element {
+                    let myself = self.clone();
+                    #[allow(unused, unreachable_patterns)]
+                    match myself {
+                        #(#render_intercepted_matches)*
+                        _ => VNode::empty()
+                    }
+                }
+            }
+        });
+
+        let locales = self.i18n.as_ref().map(I18n::locales_const);
+
+        let sitemap_section = SitemapSection::new(name, &self.endpoints, &self.nests);
+        let sitemap_id = sitemap_section.id();
+        let sitemap_link_section = sitemap_section.link_section();
+
         quote! {
+            #(#split_modules)*
+
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
                     #(#site_map,)*
                 ];
 
+                #locales
+
+                const SITEMAP_ID: &'static str = #sitemap_id;
+
                 fn render(&self, level: usize) -> dioxus_core::Element {
+                    #sitemap_link_section
+
                     let myself = self.clone();
                     match (level, myself) {
                         #(#matches)*
                         _ => VNode::empty()
                     }
                 }
+
+                #loaders
+
+                #split_module
+
+                #sitemap_options
+
+                #metadata
+
+                #guard
+
+                #redirect_kind
+
+                #http_status
+
+                #error_route
+
+                #render_slot
+
+                #intercept
             }
         }
     }
@@ -699,6 +1185,20 @@ enum RouteEndpoint {
     Redirect(Redirect),
 }
 
+/// The kind of block a `#[guard]` attribute can protect
+#[derive(Clone, Copy, PartialEq)]
+enum GuardBlock {
+    Nest,
+    Layout,
+}
+
+/// Remove the guards of the innermost block of a kind when the block ends
+fn pop_guard_block(guard_stack: &mut Vec<(GuardBlock, Vec<syn::Path>)>, block: GuardBlock) {
+    if let Some(index) = guard_stack.iter().rposition(|(kind, _)| *kind == block) {
+        guard_stack.remove(index);
+    }
+}
+
 struct SiteMapSegment {
     pub segment_type: SegmentType,
     pub children: Vec<SiteMapSegment>,
@@ -119,15 +119,20 @@ impl FullQuerySegment {
         let ident = &self.ident;
         let ty = &self.ty;
         quote! {
-            let #ident = <#ty as dioxus_router::routable::FromQuery>::from_query(&*query);
+            let #ident = {
+                use dioxus_router::routable::{SpreadQueryFromSerde, SpreadQueryFromStr};
+                (&&dioxus_router::routable::SpreadQuery::<#ty>::new()).parse_query(&*query, raw_query)
+            };
         }
     }
 
     pub fn write(&self) -> TokenStream2 {
         let ident = &self.ident;
+        let ty = &self.ty;
         quote! {
             {
-                let as_string = #ident.to_string();
+                use dioxus_router::routable::{SpreadQueryFromSerde, SpreadQueryFromStr};
+                let as_string = (&&dioxus_router::routable::SpreadQuery::<#ty>::new()).display_query(#ident);
                 write!(f, "?{}", dioxus_router::exports::percent_encoding::utf8_percent_encode(&as_string, dioxus_router::exports::QUERY_ASCII_SET))?;
             }
         }
@@ -17,6 +17,7 @@ pub(crate) struct Redirect {
     pub query: Option<QuerySegment>,
     pub hash: Option<HashFragment>,
     pub function: syn::ExprClosure,
+    pub permanent: bool,
     pub index: usize,
 }
 
@@ -59,6 +60,19 @@ impl Redirect {
         let _ = input.parse::<syn::Token![,]>();
         let function = input.parse::<syn::ExprClosure>()?;
 
+        // Then parse the optional `permanent` flag
+        let mut permanent = false;
+        if input.parse::<syn::Token![,]>().is_ok() && !input.is_empty() {
+            let flag = input.parse::<Ident>()?;
+            if flag != "permanent" {
+                return Err(syn::Error::new_spanned(
+                    flag,
+                    "Unknown redirect option. Expected `permanent`",
+                ));
+            }
+            permanent = true;
+        }
+
         let mut closure_arguments = Vec::new();
         for arg in function.inputs.iter() {
             match arg {
@@ -96,6 +110,7 @@ impl Redirect {
             query,
             hash,
             function,
+            permanent,
             index,
         })
     }
@@ -2,10 +2,11 @@ use quote::{format_ident, quote, quote_spanned};
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse_quote;
+use syn::spanned::Spanned;
 use syn::Field;
 use syn::Path;
 use syn::Type;
-use syn::{Ident, LitStr};
+use syn::{Ident, Lit, LitStr};
 
 use proc_macro2::TokenStream as TokenStream2;
 
@@ -18,26 +19,225 @@ use crate::query::QuerySegment;
 use crate::segment::create_error_type;
 use crate::segment::parse_route_segments;
 use crate::segment::RouteSegment;
+use crate::sitemap::{json_string, static_path, SitemapItem};
 
 struct RouteArgs {
     route: LitStr,
     comp_name: Option<Path>,
+    loader: Option<Path>,
+    lazy: bool,
+    intercept: Option<LitStr>,
+    sitemap: RouteSitemap,
+    metadata: RouteMetadata,
+    status: Option<StatusRoute>,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
+        let mut comp_name = None;
+        let mut loader = None;
+        let mut lazy = false;
+        let mut intercept = None;
+        let mut sitemap = RouteSitemap::default();
+        let mut metadata = RouteMetadata::default();
+        let mut status = None;
+
+        while input.parse::<syn::Token![,]>().is_ok() {
+            if input.is_empty() {
+                break;
+            }
+            // The head metadata is in the form `meta(key = "value", "og:key" = "value")`
+            if input.peek(Ident) && input.peek2(syn::token::Paren) {
+                let name = input.parse::<Ident>()?;
+                if name != "meta" {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "Unknown route option. Expected `meta(..)`",
+                    ));
+                }
+                let content;
+                syn::parenthesized!(content in input);
+                metadata.meta.extend(
+                    content
+                        .parse_terminated(MetaEntry::parse, syn::Token![,])?
+                        .into_iter()
+                        .map(|entry| (entry.key, entry.value)),
+                );
+            }
+            // Named options are in the form `name = value`
+            else if input.peek(Ident) && input.peek2(syn::Token![=]) {
+                let name = input.parse::<Ident>()?;
+                input.parse::<syn::Token![=]>()?;
+                if name == "loader" {
+                    loader = Some(input.parse()?);
+                } else if name == "intercept" {
+                    intercept = Some(input.parse()?);
+                } else if name == "changefreq" {
+                    sitemap.changefreq = Some(parse_changefreq(input.parse()?)?);
+                } else if name == "priority" {
+                    sitemap.priority = Some(parse_priority(input.parse()?)?);
+                } else if name == "title" {
+                    metadata.title = Some(input.parse()?);
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "Unknown route option. Expected `loader`, `intercept`, `changefreq`, `priority` or `title`",
+                    ));
+                }
+            }
+            // Flags are a single identifier. `lazy` splits the route into a separate wasm module,
+            // `noindex` leaves the route out of the sitemap and `not_found` and `error` mark the
+            // pages for the http status of the response
+            else if input.peek(Ident)
+                && !input.peek2(syn::Token![::])
+                && matches!(
+                    input.fork().parse::<Ident>()?.to_string().as_str(),
+                    "lazy" | "noindex" | "not_found" | "error"
+                )
+            {
+                let flag = input.parse::<Ident>()?;
+                if flag == "lazy" {
+                    lazy = true;
+                } else if flag == "noindex" {
+                    sitemap.noindex = true;
+                } else if status.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        flag,
+                        "A route can't be both the `not_found` and the `error` route",
+                    ));
+                } else if flag == "not_found" {
+                    status = Some(StatusRoute::NotFound);
+                } else {
+                    status = Some(StatusRoute::Error);
+                }
+            } else if comp_name.is_none() {
+                comp_name = Some(input.parse()?);
+            } else {
+                return Err(input.error(
+                    "Expected `lazy`, `noindex`, `not_found`, `error` or a route option in the form `name = value`",
+                ));
+            }
+        }
 
         Ok(RouteArgs {
             route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
+            comp_name,
+            loader,
+            lazy,
+            intercept,
+            sitemap,
+            metadata,
+            status,
         })
     }
 }
 
+/// A route that renders an error page and sets the http status of the response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StatusRoute {
+    /// The page for paths that don't match any other route. Responds with `404 Not Found`
+    NotFound,
+    /// The page the router renders when a routed component throws an error. Responds with
+    /// `500 Internal Server Error`
+    Error,
+}
+
+/// The title and head metadata of a route. The strings are format strings that can use the fields
+/// of the route
+#[derive(Debug, Default)]
+pub(crate) struct RouteMetadata {
+    title: Option<LitStr>,
+    meta: Vec<(String, LitStr)>,
+}
+
+impl RouteMetadata {
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.meta.is_empty()
+    }
+}
+
+/// A single entry of `meta(..)`: `description = "value"` or `"og:title" = "value"`
+struct MetaEntry {
+    key: String,
+    value: LitStr,
+}
+
+impl Parse for MetaEntry {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = match input.peek(LitStr) {
+            true => input.parse::<LitStr>()?.value(),
+            false => input.parse::<Ident>()?.to_string(),
+        };
+        input.parse::<syn::Token![=]>()?;
+        let value = input.parse()?;
+        Ok(MetaEntry { key, value })
+    }
+}
+
+/// A component that fills a named outlet: `#[slot("name", Component)]`
+#[derive(Debug)]
+pub(crate) struct Slot {
+    name: LitStr,
+    component: Path,
+}
+
+impl Parse for Slot {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let component = input.parse()?;
+        let _ = input.parse::<syn::Token![,]>();
+        Ok(Slot { name, component })
+    }
+}
+
+/// The sitemap options of a route
+#[derive(Debug, Default)]
+pub(crate) struct RouteSitemap {
+    changefreq: Option<Ident>,
+    priority: Option<f32>,
+    noindex: bool,
+}
+
+impl RouteSitemap {
+    fn is_default(&self) -> bool {
+        self.changefreq.is_none() && self.priority.is_none() && !self.noindex
+    }
+}
+
+fn parse_changefreq(lit: LitStr) -> syn::Result<Ident> {
+    let variant = match lit.value().as_str() {
+        "always" => "Always",
+        "hourly" => "Hourly",
+        "daily" => "Daily",
+        "weekly" => "Weekly",
+        "monthly" => "Monthly",
+        "yearly" => "Yearly",
+        "never" => "Never",
+        _ => return Err(syn::Error::new_spanned(
+            lit,
+            "Expected one of `always`, `hourly`, `daily`, `weekly`, `monthly`, `yearly` or `never`",
+        )),
+    };
+    Ok(Ident::new(variant, lit.span()))
+}
+
+fn parse_priority(lit: Lit) -> syn::Result<f32> {
+    let priority = match &lit {
+        Lit::Float(float) => float.base10_parse::<f32>()?,
+        Lit::Int(int) => int.base10_parse::<f32>()?,
+        _ => return Err(syn::Error::new_spanned(lit, "Expected a number")),
+    };
+    if !(0.0..=1.0).contains(&priority) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "The priority must be between 0.0 and 1.0",
+        ));
+    }
+    Ok(priority)
+}
+
 struct ChildArgs {
     route: LitStr,
 }
@@ -60,6 +260,14 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub loader: Option<Path>,
+    pub lazy: bool,
+    pub slots: Vec<Slot>,
+    pub intercept: Option<LitStr>,
+    pub sitemap: RouteSitemap,
+    pub metadata: RouteMetadata,
+    pub guards: Vec<Path>,
+    pub status: Option<StatusRoute>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -67,6 +275,7 @@ impl Route {
     pub(crate) fn parse(
         nests: Vec<NestId>,
         layouts: Vec<LayoutId>,
+        guards: Vec<Path>,
         variant: syn::Variant,
     ) -> syn::Result<Self> {
         let route_attr = variant
@@ -75,6 +284,12 @@ impl Route {
             .find(|attr| attr.path().is_ident("route"));
         let route;
         let ty;
+        let mut loader = None;
+        let mut lazy = false;
+        let mut intercept = None;
+        let mut sitemap = RouteSitemap::default();
+        let mut metadata = RouteMetadata::default();
+        let mut status = None;
         let route_name = variant.ident.clone();
         match route_attr {
             Some(attr) => {
@@ -84,6 +299,12 @@ impl Route {
                     component: comp_name,
                 };
                 route = args.route.value();
+                loader = args.loader;
+                lazy = args.lazy;
+                intercept = args.intercept;
+                sitemap = args.sitemap;
+                metadata = args.metadata;
+                status = args.status;
             }
             None => {
                 if let Some(route_attr) = variant
@@ -146,6 +367,40 @@ impl Route {
             _ => Vec::new(),
         };
 
+        if status == Some(StatusRoute::Error) && !layouts.is_empty() {
+            return Err(syn::Error::new_spanned(
+                variant.clone(),
+                "The error route can't be inside a layout because the router renders it in place of the layouts of the route that failed",
+            ));
+        }
+
+        if status == Some(StatusRoute::Error) && !fields.is_empty() {
+            return Err(syn::Error::new_spanned(
+                variant.clone(),
+                "The error route can't have any fields because the router creates it when a routed component fails",
+            ));
+        }
+
+        if lazy && intercept.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant.clone(),
+                "Lazy routes cannot intercept navigation",
+            ));
+        }
+
+        let slots = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("slot"))
+            .map(|attr| attr.parse_args::<Slot>())
+            .collect::<syn::Result<Vec<_>>>()?;
+        if let (Some(slot), RouteType::Child(_)) = (slots.first(), &ty) {
+            return Err(syn::Error::new_spanned(
+                &slot.name,
+                "Child routes cannot fill named outlets. Add the slot to the routes of the child router instead",
+            ));
+        }
+
         let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -163,6 +418,14 @@ impl Route {
             hash,
             nests,
             layouts,
+            loader,
+            lazy,
+            slots,
+            intercept,
+            sitemap,
+            metadata,
+            guards,
+            status,
             fields,
         })
     }
@@ -267,10 +530,29 @@ impl Route {
                     }
                 }
             }
-            RouteType::Leaf { component } => {
+            // Routes that are not lazy are rendered directly
+            RouteType::Leaf { component } if !self.lazy => {
                 let dynamic_segments = self.dynamic_segments();
                 let dynamic_segments_from_route = self.dynamic_segments();
 
+                let component = quote_spanned! { name.span() =>
+                    #component
+                };
+
+                quote! {
+                    #[allow(unused)]
+                    (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
+                        rsx! {
+                            #component {
+                                #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                            }
+                        }
+                    }
+                }
+            }
+            RouteType::Leaf { component } => {
+                let dynamic_segments = self.dynamic_segments();
+                let dynamic_segments_from_route = self.dynamic_segments();
 
                 let component = quote_spanned! { name.span() =>
                     #component
@@ -285,16 +567,12 @@ impl Route {
                 the complexity towards the "leaf" of the codegen rather to its core. In the future though,
                 we should think about restructuring the router macro completely since its codegen
                 makes up nearly 30-40% of the binary size in the dioxus docsite.
+
+                The lazy loader itself is defined next to the Routable implementation in
+                `split_module_items` so that the router can also preload the module.
                 */
-                use sha2::Digest;
                 let dynamic_segments_receiver = self.dynamic_segments();
-                let dynamic_segments_from_route_ = self.dynamic_segments();
-                let dynamic_segments_from_route__ = self.dynamic_segments();
-                    let unique_identifier = base16::encode_lower(
-                    &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
-                );
-                let module_name = format_ident!("module{}{unique_identifier}", name).to_string();
-                let comp_name = format_ident!("route{}{unique_identifier}", name);
+                let module_static = self.split_module_static();
 
                 quote! {
                     #[allow(unused)]
@@ -302,28 +580,10 @@ impl Route {
                         dioxus::config_macros::maybe_wasm_split! {
                             if wasm_split {
                                 {
-                                    fn #comp_name(args: #router_name) -> Element {
-                                        match args {
-                                            #router_name::#name { #(#dynamic_segments_from_route_,)* } => {
-                                                rsx! {
-                                                    #component {
-                                                        #(#dynamic_segments_from_route__: #dynamic_segments_from_route__,)*
-                                                    }
-                                                }
-                                            }
-                                            _ => unreachable!()
-                                        }
-                                    }
-
-
-
                                     #[component]
                                     fn LoaderInner(args: NoPartialEq<#router_name>) -> Element {
-                                        static MODULE: wasm_split::LazyLoader<#router_name, Element> =
-                                            wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
-
-                                        use_resource(|| async move { MODULE.load().await }).suspend()?;
-                                        MODULE.call(args.0).unwrap()
+                                        use_resource(|| async move { #module_static.load().await }).suspend()?;
+                                        #module_static.call(args.0).unwrap()
                                     }
 
                                     struct NoPartialEq<T>(T);
@@ -370,6 +630,421 @@ impl Route {
         tokens
     }
 
+    /// Create the match arm that starts the loaders for this route and all of its layouts. Returns
+    /// `None` if the route cannot have any loaders.
+    pub(crate) fn loaders_match(&self, layouts: &[Layout], nests: &[Nest]) -> Option<TokenStream2> {
+        let name = &self.route_name;
+
+        let mut loaders = Vec::new();
+        for layout_id in &self.layouts {
+            loaders.extend(layouts[layout_id.0].loader(nests));
+        }
+        if let Some(loader) = &self.loader {
+            let args = self.dynamic_segments();
+            loaders.push((
+                loader,
+                quote! { #loader(#(::std::clone::Clone::clone(#args),)*) },
+            ));
+        }
+
+        // The data of a loader is looked up by its type, so the loaders of a route and its layouts
+        // must return different types. The check fails to compile at the later loader if they don't
+        let futures: Vec<_> = (0..loaders.len())
+            .map(|i| format_ident!("__loader_{}", i))
+            .collect();
+        let mut checks = Vec::new();
+        for (later, (loader, _)) in loaders.iter().enumerate() {
+            for earlier in &futures[..later] {
+                let future = &futures[later];
+                checks.push(quote_spanned! { loader.span() =>
+                    dioxus_router::exports::assert_distinct_loader_outputs(&#earlier, &#future);
+                });
+            }
+        }
+        let calls = loaders.iter().map(|(_, call)| call);
+        let start_loaders = quote! {
+            #(let #futures = #calls;)*
+            #(#checks)*
+            let loaders = vec![#(dioxus_router::RouteLoader::new(#futures),)*];
+        };
+
+        let dynamic_segments = self.dynamic_segments();
+        match &self.ty {
+            // Child routes may have loaders of their own
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #(#dynamic_segments,)* #child } => {
+                        #start_loaders
+                        let mut loaders = loaders;
+                        loaders.extend(dioxus_router::routable::Routable::loaders(#child));
+                        loaders
+                    }
+                })
+            }
+            RouteType::Leaf { .. } => {
+                if loaders.is_empty() {
+                    return None;
+                }
+                Some(quote! {
+                    Self::#name { #(#dynamic_segments,)* } => {
+                        #start_loaders
+                        loaders
+                    }
+                })
+            }
+        }
+    }
+
+    /// A unique identifier for the module the route is split into
+    fn split_module_identifier(&self) -> String {
+        use sha2::Digest;
+        let name = &self.route_name;
+        base16::encode_lower(
+            &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
+        )
+    }
+
+    fn split_module_static(&self) -> Ident {
+        format_ident!(
+            "__MODULE_{}_{}",
+            self.route_name,
+            self.split_module_identifier()
+        )
+    }
+
+    /// The name of the wasm module this route is split into
+    fn split_module_name(&self) -> String {
+        format!(
+            "module{}{}",
+            self.route_name,
+            self.split_module_identifier()
+        )
+    }
+
+    /// Create the function and lazy loader for the module this route is split into when the wasm
+    /// bundle is split. Returns `None` for routes that are not lazy and child routes which are split
+    /// by the child router.
+    pub(crate) fn split_module_items(&self, router_name: &Ident) -> Option<TokenStream2> {
+        let RouteType::Leaf { component } = &self.ty else {
+            return None;
+        };
+        if !self.lazy {
+            return None;
+        }
+        let name = &self.route_name;
+        let component = quote_spanned! { name.span() =>
+            #component
+        };
+        let dynamic_segments = self.dynamic_segments();
+        let dynamic_segments_from_route = self.dynamic_segments();
+
+        let unique_identifier = self.split_module_identifier();
+        let module_name = self.split_module_name();
+        let comp_name = format_ident!("route{}{unique_identifier}", name);
+        let module_static = self.split_module_static();
+
+        Some(quote! {
+            dioxus::config_macros::maybe_wasm_split_items! {
+                if wasm_split {
+                    #[allow(non_snake_case)]
+                    fn #comp_name(args: #router_name) -> Element {
+                        match args {
+                            #router_name::#name { #(#dynamic_segments,)* } => {
+                                rsx! {
+                                    #component {
+                                        #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                                    }
+                                }
+                            }
+                            _ => unreachable!()
+                        }
+                    }
+
+                    #[allow(non_upper_case_globals)]
+                    static #module_static: wasm_split::LazyLoader<#router_name, Element> =
+                        wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
+                } else {}
+            }
+        })
+    }
+
+    /// Create the match arm that preloads the module this route is split into. Returns `None` if
+    /// the route is not split.
+    pub(crate) fn preload_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::preload(#child),
+                })
+            }
+            RouteType::Leaf { .. } if self.lazy => {
+                let module_static = self.split_module_static();
+                Some(quote! {
+                    Self::#name { .. } => Box::pin(async {
+                        #module_static.load().await;
+                    }),
+                })
+            }
+            RouteType::Leaf { .. } => None,
+        }
+    }
+
+    /// Create the match arm that returns the name of the module this route is split into. Returns
+    /// `None` if the route is not split.
+    pub(crate) fn split_module_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::split_module(#child),
+                })
+            }
+            RouteType::Leaf { .. } if self.lazy => {
+                let module_name = self.split_module_name();
+                Some(quote! {
+                    Self::#name { .. } => Some(#module_name),
+                })
+            }
+            RouteType::Leaf { .. } => None,
+        }
+    }
+
+    /// Create the match arms that render the components this route fills named outlets with
+    pub(crate) fn slot_matches(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let name = &self.route_name;
+        self.slots.iter().map(move |slot| {
+            let slot_name = &slot.name;
+            let component = &slot.component;
+            let dynamic_segments = self.dynamic_segments();
+            let dynamic_segments_from_route = self.dynamic_segments();
+            quote! {
+                (#slot_name, Self::#name { #(#dynamic_segments,)* }) => {
+                    rsx! {
+                        #component {
+                            #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Create the match arm that returns the named outlet this route renders into when it is
+    /// intercepted. Returns `None` if the route is not intercepting.
+    pub(crate) fn intercept_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        let outlet = self.intercept.as_ref()?;
+        Some(quote! {
+            Self::#name { .. } => Some(#outlet),
+        })
+    }
+
+    /// Create the match arm that renders only the component of this route when it is intercepted
+    pub(crate) fn render_intercepted_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        self.intercept.as_ref()?;
+        let RouteType::Leaf { component } = &self.ty else {
+            return None;
+        };
+        let dynamic_segments = self.dynamic_segments();
+        let dynamic_segments_from_route = self.dynamic_segments();
+        Some(quote! {
+            Self::#name { #(#dynamic_segments,)* } => {
+                rsx! {
+                    #component {
+                        #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                    }
+                }
+            }
+        })
+    }
+
+    /// Create the match arm that returns the sitemap options of this route. Returns `None` if the
+    /// route uses the default options.
+    pub(crate) fn http_status_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match (&self.ty, self.status) {
+            (RouteType::Child(field), _) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::http_status(#child),
+                })
+            }
+            (RouteType::Leaf { .. }, Some(StatusRoute::NotFound)) => Some(quote! {
+                Self::#name { .. } => Some(404),
+            }),
+            (RouteType::Leaf { .. }, Some(StatusRoute::Error)) => Some(quote! {
+                Self::#name { .. } => Some(500),
+            }),
+            (RouteType::Leaf { .. }, None) => None,
+        }
+    }
+
+    pub(crate) fn sitemap_options_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::sitemap_options(#child),
+                })
+            }
+            RouteType::Leaf { .. } if !self.sitemap.is_default() => {
+                let changefreq = match &self.sitemap.changefreq {
+                    Some(variant) => {
+                        quote! { Some(dioxus_router::sitemap::ChangeFrequency::#variant) }
+                    }
+                    None => quote! { None },
+                };
+                let priority = match self.sitemap.priority {
+                    Some(priority) => quote! { Some(#priority) },
+                    None => quote! { None },
+                };
+                let noindex = self.sitemap.noindex;
+                Some(quote! {
+                    Self::#name { .. } => dioxus_router::sitemap::SitemapOptions {
+                        changefreq: #changefreq,
+                        priority: #priority,
+                        noindex: #noindex,
+                    },
+                })
+            }
+            RouteType::Leaf { .. } => None,
+        }
+    }
+
+    /// Get the route for the sitemap section of the binary. Returns `None` if the route has dynamic
+    /// segments, is left out of the sitemap or is a status route.
+    pub(crate) fn sitemap_item(&self, nests: &[Nest]) -> Option<SitemapItem<'_>> {
+        let mut segments = Vec::new();
+        for segment in self
+            .nests
+            .iter()
+            .flat_map(|id| &nests[id.0].segments)
+            .chain(&self.segments)
+        {
+            match segment {
+                RouteSegment::Static(segment) => segments.push(segment.as_str()),
+                _ => return None,
+            }
+        }
+        let path = static_path(segments);
+
+        match &self.ty {
+            RouteType::Child(field) => Some(SitemapItem::Child {
+                prefix: path,
+                ty: &field.ty,
+            }),
+            RouteType::Leaf { .. } if self.sitemap.noindex || self.status.is_some() => None,
+            RouteType::Leaf { .. } => {
+                let mut json = format!("{{\"loc\":{}", json_string(&path));
+                if let Some(changefreq) = &self.sitemap.changefreq {
+                    let changefreq = changefreq.to_string().to_lowercase();
+                    json.push_str(&format!(",\"changefreq\":\"{changefreq}\""));
+                }
+                if let Some(priority) = self.sitemap.priority {
+                    json.push_str(&format!(",\"priority\":{priority}"));
+                }
+                json.push('}');
+                Some(SitemapItem::Route(json))
+            }
+        }
+    }
+
+    pub(crate) fn metadata_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::metadata(#child),
+                })
+            }
+            RouteType::Leaf { .. } if !self.metadata.is_empty() => {
+                let dynamic_segments = self.dynamic_segments();
+                let title = match &self.metadata.title {
+                    Some(title) => quote! { Some(format!(#title)) },
+                    None => quote! { None },
+                };
+                let meta = self
+                    .metadata
+                    .meta
+                    .iter()
+                    .map(|(key, value)| quote! { (#key, format!(#value)) });
+                Some(quote! {
+                    Self::#name { #(#dynamic_segments,)* } => dioxus_router::metadata::RouteMetadata {
+                        title: #title,
+                        meta: vec![#(#meta),*],
+                    },
+                })
+            }
+            RouteType::Leaf { .. } => None,
+        }
+    }
+
+    /// Run the guards of the route's nests, layouts and the route itself in order. Child routes run
+    /// the guards of the child router after their own
+    pub(crate) fn guard_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        let guards = self.guards.iter().map(|guard| {
+            quote! {
+                match #guard(self) {
+                    dioxus_router::guard::GuardOutcome::Allow => {}
+                    outcome => return outcome,
+                }
+            }
+        });
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                let dynamic_segments = self.dynamic_segments();
+                let clone_segments = self.dynamic_segments();
+                Some(quote! {
+                    Self::#name { #(#dynamic_segments,)* #child } => {
+                        #(#guards)*
+                        dioxus_router::routable::Routable::guard(#child).map_route(|#child| Self::#name {
+                            #(#clone_segments: ::std::clone::Clone::clone(#clone_segments),)*
+                            #child
+                        })
+                    }
+                })
+            }
+            RouteType::Leaf { .. } => {
+                if self.guards.is_empty() {
+                    return None;
+                }
+                Some(quote! {
+                    Self::#name { .. } => {
+                        #(#guards)*
+                        dioxus_router::guard::GuardOutcome::Allow
+                    }
+                })
+            }
+        }
+    }
+
+    /// Check if any guard protects the route. Child routes are also guarded if the child router guards
+    /// the child route
+    pub(crate) fn is_guarded_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) if self.guards.is_empty() => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::is_guarded(#child),
+                })
+            }
+            _ if self.guards.is_empty() => None,
+            _ => Some(quote! {
+                Self::#name { .. } => true,
+            }),
+        }
+    }
+
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         self.fields.iter().map(|(name, _)| {
             quote! {#name}
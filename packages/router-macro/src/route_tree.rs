@@ -98,9 +98,12 @@ impl<'a> ParseRouteTree<'a> {
             .expect("Cannot get children of non static or nest segment")
     }
 
-    pub(crate) fn new(endpoints: &'a [RouteEndpoint], nests: &'a [Nest]) -> Self {
+    pub(crate) fn new(
+        endpoints: impl IntoIterator<Item = &'a RouteEndpoint>,
+        nests: &'a [Nest],
+    ) -> Self {
         let routes = endpoints
-            .iter()
+            .into_iter()
             .map(|endpoint| match endpoint {
                 RouteEndpoint::Route(route) => PathIter::new_route(route, nests),
                 RouteEndpoint::Redirect(redirect) => PathIter::new_redirect(redirect, nests),
@@ -0,0 +1,124 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Ident, Type};
+
+use crate::{nest::Nest, RouteEndpoint};
+
+/// A route of the enum that can be embedded in the sitemap section
+pub(crate) enum SitemapItem<'a> {
+    /// A static route with the json of its sitemap entry
+    Route(String),
+    /// A child router at a static prefix
+    Child { prefix: String, ty: &'a Type },
+}
+
+/// The sitemap section embeds the static routes of the enum in the binary so `dx build` can write
+/// them to `sitemap.xml` without running the app. The section is an exported static with the
+/// `__DIOXUS_SITEMAP__` prefix that contains json like this:
+///
+/// ```json
+/// {"id":"...","routes":[{"loc":"/","priority":1}],"children":[{"prefix":"/blog","id":"..."}]}
+/// ```
+///
+/// The ids of child routers are only known to the child's own macro, so the json is built in a const
+/// context from the `SITEMAP_ID` of the child routers.
+pub(crate) struct SitemapSection {
+    id: String,
+    parts: Vec<TokenStream2>,
+}
+
+impl SitemapSection {
+    pub(crate) fn new(name: &Ident, endpoints: &[RouteEndpoint], nests: &[Nest]) -> Self {
+        let mut routes = Vec::new();
+        let mut children = Vec::new();
+        for endpoint in endpoints {
+            let RouteEndpoint::Route(route) = endpoint else {
+                continue;
+            };
+            match route.sitemap_item(nests) {
+                Some(SitemapItem::Route(json)) => routes.push(json),
+                Some(SitemapItem::Child { prefix, ty }) => children.push((prefix, ty)),
+                None => {}
+            }
+        }
+
+        // The span of the enum is unique for every enum in the crate, and the package name and the
+        // routes make collisions with enums in other crates unlikely
+        let mut hash = DefaultHasher::new();
+        format!("{:?}", name.span()).hash(&mut hash);
+        std::env::var("CARGO_PKG_NAME").ok().hash(&mut hash);
+        name.to_string().hash(&mut hash);
+        routes.hash(&mut hash);
+        let id = format!("{:016x}", hash.finish());
+
+        let mut parts = Vec::new();
+        let mut json = format!(
+            "{{\"id\":\"{id}\",\"routes\":[{}],\"children\":[",
+            routes.join(",")
+        );
+        for (index, (prefix, ty)) in children.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!("{{\"prefix\":{},\"id\":\"", json_string(prefix)));
+            parts.push(quote! { #json });
+            parts.push(quote! { <#ty as dioxus_router::routable::Routable>::SITEMAP_ID });
+            json = "\"}".to_string();
+        }
+        json.push_str("]}");
+        parts.push(quote! { #json });
+
+        Self { id, parts }
+    }
+
+    /// The id parent routers use to reference the routes of this enum
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Create the exported static with the sitemap section. The static is read in `Routable::render`
+    /// to keep the linker from removing it.
+    pub(crate) fn link_section(&self) -> TokenStream2 {
+        let export_name = format!("__DIOXUS_SITEMAP__{}", self.id);
+        let parts = &self.parts;
+        quote! {
+            const __SITEMAP_PARTS: &[&str] = &[#(#parts,)*];
+            const __SITEMAP_LEN: usize = dioxus_router::sitemap::macro_helpers::section_len(__SITEMAP_PARTS);
+            #[unsafe(export_name = #export_name)]
+            static __SITEMAP_SECTION: [u8; __SITEMAP_LEN] = dioxus_router::sitemap::macro_helpers::section(__SITEMAP_PARTS);
+            static __REFERENCE_TO_SITEMAP_SECTION: &'static [u8] = &__SITEMAP_SECTION;
+            _ = unsafe { std::ptr::read_volatile(&__REFERENCE_TO_SITEMAP_SECTION) };
+        }
+    }
+}
+
+/// Join static route segments into a path. Empty segments are skipped so the root of a nest or child
+/// router doesn't end with a slash.
+pub(crate) fn static_path<'a>(segments: impl IntoIterator<Item = &'a str>) -> String {
+    let mut path = String::new();
+    for segment in segments.into_iter().filter(|segment| !segment.is_empty()) {
+        path.push('/');
+        path.push_str(segment);
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    path
+}
+
+/// Quote a string for json
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -2,18 +2,35 @@
 
 use std::fmt::Debug;
 
-use dioxus_core::{Attribute, Element, EventHandler, VNode};
+use dioxus_core::{try_consume_context, use_hook, Attribute, Element, EventHandler, VNode};
 use dioxus_core_macro::{rsx, Props};
 use dioxus_html::{
     self as dioxus_elements, ModifiersInteraction, MountedEvent, MouseEvent, PointerInteraction,
+    VisibleEvent,
 };
 use dioxus_signals::{GlobalSignal, Owner, Readable};
 
 use tracing::error;
 
-use crate::navigation::NavigationTarget;
+use crate::contexts::LoaderContext;
+use crate::navigation::{HistoryState, NavigationTarget};
 use crate::utils::use_router_internal::use_router_internal;
 
+/// When a [`Link`] should start loading the code and data for its target route.
+///
+/// Prefetching starts the loaders of the target route and loads its wasm-split module, so the route
+/// can render right away when the user navigates to it. Without a split bundle, the code for every
+/// route is already loaded and only the loaders are started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkPrefetch {
+    /// Prefetch the target when the user hovers over or focuses the link.
+    Hover,
+    /// Prefetch the target when the link scrolls into view.
+    Visible,
+    /// Prefetch the target as soon as the link is mounted.
+    Eager,
+}
+
 /// The properties for a [`Link`].
 #[derive(Props, Clone, PartialEq)]
 pub struct LinkProps {
@@ -48,12 +65,39 @@ pub struct LinkProps {
     /// 3. If `onclick_only` is [`true`], only the provided `onclick` handler will be executed.
     pub onclick_only: bool,
 
+    /// When [`true`], the scroll position is kept after navigating instead of scrolling to the top
+    /// of the page or to the element the `#hash` of the `target` points to.
+    #[props(default)]
+    pub preserve_scroll: bool,
+
+    /// When to prefetch the target route. Defaults to not prefetching.
+    ///
+    /// This has no effect for external targets.
+    pub prefetch: Option<LinkPrefetch>,
+
     /// The rel attribute for the generated HTML anchor tag.
     ///
     /// For external `a`s, this defaults to `noopener noreferrer`.
     pub rel: Option<String>,
 
+    /// The type of the [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API)
+    /// that animates the navigation. Styles can select it with the `:active-view-transition-type()`
+    /// pseudo-class.
+    ///
+    /// Setting this animates the navigation even if view transitions are disabled in the
+    /// [`crate::RouterConfig`].
+    pub view_transition: Option<String>,
+
+    /// A state to attach to the history entry of the `target`, like the page the link was opened
+    /// from. It isn't part of the URL and can be read with [`crate::hooks::use_history_state`].
+    ///
+    /// Create the state from any serializable value with [`HistoryState::new`].
+    pub state: Option<HistoryState>,
+
     /// The navigation target. Roughly equivalent to the href attribute of an HTML anchor tag.
+    ///
+    /// Relative targets like `..`, `./edit`, `?page=2` or `#section` are resolved against the
+    /// current route.
     #[props(into)]
     pub to: NavigationTarget,
 
@@ -70,7 +114,11 @@ impl Debug for LinkProps {
             .field("new_tab", &self.new_tab)
             .field("onclick", &self.onclick.as_ref().map(|_| "onclick is set"))
             .field("onclick_only", &self.onclick_only)
+            .field("preserve_scroll", &self.preserve_scroll)
+            .field("prefetch", &self.prefetch)
             .field("rel", &self.rel)
+            .field("state", &self.state)
+            .field("view_transition", &self.view_transition)
             .finish()
     }
 }
@@ -139,12 +187,16 @@ pub fn Link(props: LinkProps) -> Element {
     let LinkProps {
         active_class,
         children,
-        attributes,
+        mut attributes,
         new_tab,
         onclick,
         onclick_only,
+        preserve_scroll,
+        prefetch,
         rel,
+        state,
         to,
+        view_transition,
         class,
         ..
     } = props;
@@ -169,7 +221,9 @@ pub fn Link(props: LinkProps) -> Element {
     };
     // Add the history's prefix to internal hrefs for use in the rsx
     let full_href = match &to {
-        NavigationTarget::Internal(url) => router.prefix().unwrap_or_default() + url,
+        NavigationTarget::Internal(url) => {
+            router.prefix().unwrap_or_default() + &router.localize_route(url)
+        }
         NavigationTarget::External(route) => route.clone(),
     };
 
@@ -202,6 +256,41 @@ pub fn Link(props: LinkProps) -> Element {
 
     let do_default = onclick.is_none() || !onclick_only;
 
+    // Only internal routes have loaders and split modules to prefetch
+    let loaders = use_hook(try_consume_context::<LoaderContext>);
+    let prefetch_target = match (&to, loaders) {
+        (NavigationTarget::Internal(url), Some(loaders)) => {
+            prefetch.map(|mode| (mode, url.clone(), loaders))
+        }
+        _ => None,
+    };
+    let prefetch_eagerly = prefetch_target
+        .clone()
+        .and_then(|(mode, url, loaders)| (mode == LinkPrefetch::Eager).then_some((url, loaders)));
+
+    // Only listen for the events the prefetch mode needs
+    match prefetch_target {
+        Some((LinkPrefetch::Hover, url, loaders)) => {
+            let focus_url = url.clone();
+            attributes.push(dioxus_elements::events::onmouseenter(move |_| {
+                loaders.prefetch_str(&url)
+            }));
+            attributes.push(dioxus_elements::events::onfocus(move |_| {
+                loaders.prefetch_str(&focus_url)
+            }));
+        }
+        Some((LinkPrefetch::Visible, url, loaders)) => {
+            attributes.push(dioxus_elements::events::onvisible(
+                move |event: VisibleEvent| {
+                    if event.is_intersecting().unwrap_or_default() {
+                        loaders.prefetch_str(&url)
+                    }
+                },
+            ));
+        }
+        _ => {}
+    }
+
     let action = move |event: MouseEvent| {
         // Only handle events without modifiers
         if !event.modifiers().is_empty() {
@@ -220,7 +309,12 @@ pub fn Link(props: LinkProps) -> Element {
         event.prevent_default();
 
         if do_default && is_router_nav {
-            router.push_any(to.clone());
+            router.push_any(
+                to.clone(),
+                !preserve_scroll,
+                view_transition.clone(),
+                state.clone(),
+            );
         }
 
         if let Some(handler) = onclick {
@@ -229,6 +323,9 @@ pub fn Link(props: LinkProps) -> Element {
     };
 
     let onmounted = move |event| {
+        if let Some((url, loaders)) = &prefetch_eagerly {
+            loaders.prefetch_str(url);
+        }
         if let Some(handler) = props.onmounted {
             handler.call(event);
         }
@@ -1,5 +1,15 @@
 use crate::{outlet::OutletContext, *};
 use dioxus_core::Element;
+use dioxus_core_macro::Props;
+
+/// The props for [`Outlet`].
+#[derive(Props, Clone, Debug, Default, PartialEq)]
+pub struct OutletProps {
+    /// The name of the outlet. Named outlets render the component the current route fills the slot
+    /// with instead of the next nested route.
+    #[props(into)]
+    pub name: Option<String>,
+}
 
 /// An outlet for the current content.
 ///
@@ -68,6 +78,89 @@ use dioxus_core::Element;
 /// # vdom.rebuild_in_place();
 /// # assert_eq!(dioxus_ssr::render(&vdom), "<h1>App</h1><p>Child</p>");
 /// ```
-pub fn Outlet<R: Routable + Clone>() -> Element {
-    OutletContext::<R>::render()
+///
+/// # Named outlets
+///
+/// A layout can contain any number of named outlets next to its default outlet. Routes fill a named
+/// outlet with the `#[slot("name", Component)]` attribute. The slot component takes the same
+/// parameters as the route component. Routes that don't fill the slot render nothing in it.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable)]
+/// #[rustfmt::skip]
+/// enum Route {
+///     #[layout(Dashboard)]
+///         #[route("/")]
+///         #[slot("sidebar", StatsSidebar)]
+///         Stats {},
+///         #[route("/users")]
+///         Users {},
+/// }
+///
+/// #[component]
+/// fn Dashboard() -> Element {
+///     rsx! {
+///         aside { Outlet::<Route> { name: "sidebar" } }
+///         main { Outlet::<Route> {} }
+///     }
+/// }
+///
+/// #[component]
+/// fn StatsSidebar() -> Element {
+///     rsx! { "Filters" }
+/// }
+/// # #[component]
+/// # fn Stats() -> Element { rsx! { "Stats" } }
+/// # #[component]
+/// # fn Users() -> Element { rsx! { "Users" } }
+/// #
+/// # #[component]
+/// # fn App() -> Element {
+/// #     rsx! {
+/// #         dioxus_router::components::HistoryProvider {
+/// #             history:  move |_| std::rc::Rc::new(dioxus_history::MemoryHistory::with_initial_path(Route::Stats {}.to_string())) as std::rc::Rc<dyn dioxus_history::History>,
+/// #             Router::<Route> {}
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # vdom.rebuild_in_place();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<aside>Filters</aside><main>Stats</main>");
+/// ```
+///
+/// # Intercepting routes
+///
+/// Routes with the `intercept = "name"` option render only their component in the named outlet
+/// when the user navigates to them from another page. The previous page stays visible in the default
+/// outlet behind it, which is useful for modals. When the page is loaded directly or rendered on the
+/// server, the route renders as a full page instead.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable)]
+/// #[rustfmt::skip]
+/// enum Route {
+///     #[layout(Frame)]
+///         #[route("/")]
+///         Gallery {},
+///         #[route("/photo/:id", intercept = "modal")]
+///         Photo { id: usize },
+/// }
+///
+/// #[component]
+/// fn Frame() -> Element {
+///     rsx! {
+///         Outlet::<Route> {}
+///         Outlet::<Route> { name: "modal" }
+///     }
+/// }
+/// # #[component]
+/// # fn Gallery() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Photo(id: usize) -> Element { VNode::empty() }
+/// ```
+pub fn Outlet<R: Routable + Clone>(props: OutletProps) -> Element {
+    OutletContext::<R>::render(props.name.as_deref())
 }
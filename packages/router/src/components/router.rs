@@ -1,7 +1,12 @@
-use crate::{provide_router_context, routable::Routable, router_cfg::RouterConfig, Outlet};
-use dioxus_core::{provide_context, use_hook, Callback, Element};
+use crate::{
+    guard::GuardOutcome, provide_router_context, routable::Routable, router_cfg::RouterConfig,
+    LoaderContext, Outlet, RouterContext,
+};
+use dioxus_core::{provide_context, provide_error_boundary, use_hook, Callback, Element, VNode};
 use dioxus_core_macro::{rsx, Props};
+use dioxus_hooks::use_effect;
 use dioxus_signals::{GlobalSignal, Owner, Readable};
+use std::{cell::RefCell, rc::Rc};
 
 /// The props for [`Router`].
 #[derive(Props)]
@@ -34,12 +39,28 @@ impl<R: Clone> PartialEq for RouterProps<R> {
 }
 
 /// A component that renders the current route.
+///
+/// # Scroll management
+/// After navigating to a new route, the router scrolls to the top of the page or to the element the
+/// `#hash` of the route points to. When going back or forward, it restores the scroll positions of
+/// the page and of every element with a `data-dioxus-scroll` attribute. The value of the attribute,
+/// or the id of the element if it is empty, identifies the container across renders:
+///
+/// ```rust, ignore
+/// rsx! {
+///     nav { "data-dioxus-scroll": "sidebar", overflow_y: "auto", /* ... */ }
+/// }
+/// ```
+///
+/// Use the `preserve_scroll` prop of [`crate::components::Link`] to keep the scroll position for a single link.
 pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
-    use crate::{outlet::OutletContext, RouterContext};
+    use crate::outlet::OutletContext;
 
-    use_hook(|| {
-        provide_router_context(RouterContext::new(props.config.call(())));
-    });
+    let router = use_router_context::<R>(props.config);
+    let guard_fallback = use_route_guard::<R>(router);
+    let error_route = use_error_route::<R>(router);
+    let fallback = guard_fallback.or(error_route);
+    use_route_loaders::<R>(router, fallback.is_none());
 
     #[cfg(feature = "streaming")]
     dioxus_hooks::use_after_suspense_resolved(|| {
@@ -50,5 +71,217 @@ pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
         provide_context(OutletContext::<R>::new());
     });
 
-    rsx! { Outlet::<R> {} }
+    #[cfg(feature = "html")]
+    let is_root = use_scroll_management::<R>(router);
+
+    // Keep the head of the document in sync with the metadata of the route and link to the current
+    // page in the other locales for search engines. The metadata of child routers is read through
+    // the root route
+    #[cfg(feature = "html")]
+    return rsx! {
+        if is_root {
+            RouteHead::<R> {}
+        }
+        if R::LOCALES.is_some() {
+            LocaleAlternates {}
+        }
+        if let Some(fallback) = fallback {
+            {fallback}
+        } else {
+            Outlet::<R> {}
+        }
+    };
+
+    #[cfg(not(feature = "html"))]
+    rsx! {
+        if let Some(fallback) = fallback {
+            {fallback}
+        } else {
+            Outlet::<R> {}
+        }
+    }
+}
+
+/// Create the router context. The router runs the guards of the initial route and describes the
+/// response of the route to the server before anything renders
+fn use_router_context<R: Routable + Clone>(config: Callback<(), RouterConfig<R>>) -> RouterContext {
+    let negotiated_locale = use_negotiated_locale::<R>();
+    use_hook(|| {
+        let router = RouterContext::new(config.call(()), negotiated_locale);
+        provide_router_context(router);
+        router
+    })
+}
+
+/// Pick the locale of routes without a locale prefix from the Accept-Language header on the server
+/// and reuse the same locale while hydrating on the client
+fn use_negotiated_locale<R: Routable>() -> Option<&'static str> {
+    #[cfg(feature = "streaming")]
+    let negotiated_locale = dioxus_fullstack_hooks::use_server_cached(|| {
+        R::LOCALES
+            .and_then(|locales| {
+                let languages = dioxus_fullstack_hooks::accepted_languages();
+                locales.negotiate(languages.iter().map(String::as_str))
+            })
+            .map(str::to_string)
+    });
+    #[cfg(not(feature = "streaming"))]
+    let negotiated_locale: Option<String> = None;
+
+    R::LOCALES
+        .zip(negotiated_locale)
+        .and_then(|(locales, locale)| locales.find(&locale))
+}
+
+/// Get the element a guard of the current route renders in place of the route. A guard that
+/// redirects renders nothing until the router navigates to its target.
+///
+/// The guards of the initial route run when the router is created. After that, the guards run again
+/// after every navigation and when a signal they read changes.
+fn use_route_guard<R: Routable + Clone>(router: RouterContext) -> Option<Element> {
+    use_effect(move || router.follow_guard_redirect::<R>());
+
+    match router
+        .full_route_string()
+        .parse::<R>()
+        .map(|route| route.guard())
+    {
+        Ok(GuardOutcome::Allow) | Err(_) => None,
+        Ok(GuardOutcome::Redirect(_)) => Some(VNode::empty()),
+        Ok(GuardOutcome::Fallback(fallback)) => Some(fallback),
+    }
+}
+
+/// Catch the errors of routed components and render the error route in place of the route if the
+/// enum has an error route. The errors are cleared when the router navigates to another route
+fn use_error_route<R: Routable + Clone>(router: RouterContext) -> Option<Element> {
+    let (errors, errored_route) = use_hook(|| {
+        let errors = R::error_route().map(|_| provide_error_boundary());
+        (errors, Rc::new(RefCell::new(None::<String>)))
+    });
+    let errors = errors?;
+    let current_route = router.full_route_string();
+    let mut errored_route = errored_route.borrow_mut();
+    if errors.errors().is_empty() {
+        *errored_route = None;
+        return None;
+    }
+    match &*errored_route {
+        Some(route) if *route != current_route => {
+            *errored_route = None;
+            errors.clear_errors();
+            return None;
+        }
+        Some(_) => {}
+        None => {
+            *errored_route = Some(current_route);
+            // Respond with the status of the error once when the route fails. Routes that failed
+            // to parse are not found instead of server errors
+            #[cfg(feature = "streaming")]
+            dioxus_fullstack_hooks::set_status(
+                match errors
+                    .errors()
+                    .iter()
+                    .any(|error| error.downcast::<crate::ParseRouteError>().is_some())
+                {
+                    true => 404,
+                    false => 500,
+                },
+            );
+        }
+    }
+    R::error_route().map(|route| route.render(0))
+}
+
+/// Start the loaders for the current route before any of the routed components render
+fn use_route_loaders<R: Routable + Clone>(router: RouterContext, load: bool) {
+    let loaders = use_hook(|| provide_context(LoaderContext::new::<R>()));
+    if let (Ok(route), true) = (router.full_route_string().parse::<R>(), load) {
+        loaders.load(&route);
+    }
+}
+
+/// Scroll to the top of the page, the `#hash` target or the saved position of the history entry
+/// after each navigation renders. Child routers leave scrolling to the root router. Returns whether
+/// this is the root router
+#[cfg(feature = "html")]
+fn use_scroll_management<R: Routable>(router: RouterContext) -> bool {
+    let (history, document, is_root) = use_hook(|| {
+        let is_root = crate::components::child_router::consume_child_route_mapping::<R>().is_none();
+        let document = dioxus_core::try_consume_context::<Rc<dyn dioxus_document::Document>>()
+            .filter(|_| is_root);
+        let history = dioxus_history::history();
+        // The router takes over scrolling from the history
+        if document.is_some() {
+            history.disable_scroll_restoration();
+        }
+        (history, document, is_root)
+    });
+    if let Some(document) = document {
+        dioxus_core::queue_effect(move || {
+            let route = history.current_route();
+            if let Some((key, action)) = router.update_scroll(&route) {
+                document.eval(crate::contexts::scroll::scroll_script(key, action, &route));
+            }
+        });
+    }
+    is_root
+}
+
+/// Render the title and `<meta>` tags of the current route into the head of the document
+#[cfg(feature = "html")]
+fn RouteHead<R: Routable + Clone>() -> Element {
+    let metadata = crate::router()
+        .full_route_string()
+        .parse::<R>()
+        .map(|route| route.metadata())
+        .unwrap_or_default();
+
+    // The `<meta>` tags of the first route are created with the head components so they are also
+    // rendered on the server. Later navigations update them in place
+    let (initial, previous, document) = use_hook(|| {
+        (
+            metadata.meta.clone(),
+            Rc::new(RefCell::new(metadata.meta.clone())),
+            dioxus_document::document(),
+        )
+    });
+    let mut previous = previous.borrow_mut();
+    if *previous != metadata.meta {
+        let script = crate::metadata::update_meta_script(&previous, &metadata.meta);
+        dioxus_core::queue_effect(move || _ = document.eval(script));
+        *previous = metadata.meta;
+    }
+
+    rsx! {
+        if let Some(title) = metadata.title {
+            dioxus_document::Title { "{title}" }
+        }
+        for (key, content) in initial {
+            dioxus_document::Meta {
+                key: "{key}",
+                name: (!key.contains(':')).then(|| key.to_string()),
+                property: key.contains(':').then(|| key.to_string()),
+                content,
+            }
+        }
+    }
+}
+
+/// Render the `hreflang` alternate links for the current page
+#[cfg(feature = "html")]
+fn LocaleAlternates() -> Element {
+    let router = crate::router();
+    // Search engines expect absolute links, so the links start with the origin of the site if the server
+    // configures one. The client reuses the same origin while hydrating
+    #[cfg(feature = "streaming")]
+    let origin = dioxus_fullstack_hooks::use_server_cached(dioxus_fullstack_hooks::site_origin)
+        .unwrap_or_default();
+    #[cfg(not(feature = "streaming"))]
+    let origin = String::new();
+    rsx! {
+        for (hreflang, href) in router.locale_alternates(&origin) {
+            dioxus_document::Link { key: "{hreflang}", rel: "alternate", hreflang, href }
+        }
+    }
 }
@@ -0,0 +1,195 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+use dioxus_core::{current_scope_id, spawn, ScopeId, Task};
+use dioxus_signals::{Readable, Signal, Writable};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::routable::Routable;
+
+type LoaderFuture = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// A type erased data loader for a route. These are generated by the `#[route("/path", loader = my_loader)]`
+/// and `#[layout(MyLayout, loader = my_loader)]` attributes of the [`Routable`] derive macro.
+///
+/// The result of the loader can be read with [`crate::hooks::use_loader_data`].
+pub struct RouteLoader {
+    type_id: TypeId,
+    future: LoaderFuture,
+}
+
+impl RouteLoader {
+    /// Create a new loader from the future returned by a loader function.
+    ///
+    /// In fullstack apps, the output of the loader is serialized into the page on the server and
+    /// hydrated on the client without running the loader again.
+    #[track_caller]
+    pub fn new<T, F>(future: F) -> Self
+    where
+        T: Serialize + DeserializeOwned + 'static,
+        F: Future<Output = T> + 'static,
+    {
+        #[cfg(feature = "streaming")]
+        let future = dioxus_fullstack_hooks::server_future(future);
+
+        Self {
+            type_id: TypeId::of::<T>(),
+            future: Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> }),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct DistinctOutputs;
+#[doc(hidden)]
+pub struct SameOutputs;
+
+/// Implemented for every pair of types, and a second time for pairs of the same type. Requiring this
+/// trait is ambiguous (and fails to compile) if both types are the same.
+#[doc(hidden)]
+pub trait DistinctLoaderOutputs<Marker> {}
+
+impl<A, B> DistinctLoaderOutputs<DistinctOutputs> for (A, B) {}
+
+impl<A> DistinctLoaderOutputs<SameOutputs> for (A, A) {}
+
+/// Fail to compile if two loaders of the same route return the same type. This is called by the code
+/// the [`Routable`] derive macro generates.
+#[doc(hidden)]
+pub fn assert_distinct_loader_outputs<A, B, Marker>(_: &A, _: &B)
+where
+    A: Future,
+    B: Future,
+    (A::Output, B::Output): DistinctLoaderOutputs<Marker>,
+{
+}
+
+pub(crate) struct LoaderSlot {
+    pub(crate) value: Option<Rc<dyn Any>>,
+    pub(crate) task: Task,
+}
+
+type RouteSlots = HashMap<TypeId, LoaderSlot>;
+
+/// The loaders that are running or have finished for the current route and any routes that were
+/// prefetched. This is provided by the [`crate::components::Router`] component.
+#[derive(Clone, Copy)]
+pub(crate) struct LoaderContext {
+    scope: ScopeId,
+    current_route: Signal<Option<String>>,
+    routes: Signal<HashMap<String, RouteSlots>>,
+    prefetch_route: fn(LoaderContext, &str),
+}
+
+impl LoaderContext {
+    pub(crate) fn new<R: Routable>() -> Self {
+        Self {
+            scope: current_scope_id().expect("to be in a dioxus runtime"),
+            current_route: Signal::new(None),
+            routes: Signal::new(HashMap::new()),
+            prefetch_route: |myself, route| {
+                if let Ok(route) = R::from_str(route) {
+                    myself.prefetch(&route);
+                }
+            },
+        }
+    }
+
+    /// Get the loader slot for the type in the current route. This subscribes to changes in the
+    /// current route's loaders.
+    pub(crate) fn with_slot<O>(
+        &self,
+        type_id: TypeId,
+        f: impl FnOnce(Option<&LoaderSlot>) -> O,
+    ) -> O {
+        let current_route = self.current_route.read();
+        let routes = self.routes.read();
+        let slot = current_route
+            .as_ref()
+            .and_then(|route| routes.get(route))
+            .and_then(|slots| slots.get(&type_id));
+        f(slot)
+    }
+
+    /// Start all loaders for the route if the route changed since the last time the loaders were started.
+    pub(crate) fn load<R: Routable>(mut self, route: &R) {
+        let route_string = route.to_string();
+        if self.current_route.peek().as_deref() == Some(route_string.as_str()) {
+            return;
+        }
+        self.current_route.set(Some(route_string.clone()));
+
+        // Cancel any loaders that are still running for the old route or routes that were prefetched
+        // but never visited. If this route was prefetched, keep the data we already have
+        self.routes.write().retain(|route, slots| {
+            let keep = *route == route_string;
+            if !keep {
+                for slot in slots.values() {
+                    slot.task.cancel();
+                }
+            }
+            keep
+        });
+
+        self.start(route_string, route);
+    }
+
+    /// Start the loaders for a route the user is likely to navigate to next.
+    pub(crate) fn prefetch<R: Routable>(self, route: &R) {
+        let route_string = route.to_string();
+        if self.current_route.peek().as_deref() == Some(route_string.as_str())
+            || self.routes.peek().contains_key(&route_string)
+        {
+            return;
+        }
+
+        self.scope.in_runtime(|| {
+            spawn(route.preload());
+            self.start(route_string, route);
+        });
+    }
+
+    /// Prefetch a route from the string representation of the root route
+    pub(crate) fn prefetch_str(self, route: &str) {
+        (self.prefetch_route)(self, route)
+    }
+
+    fn start<R: Routable>(mut self, route_string: String, route: &R) {
+        if self.routes.peek().contains_key(&route_string) {
+            return;
+        }
+
+        let mut slots = HashMap::new();
+        let mut tasks = Vec::new();
+        for loader in route.loaders() {
+            let RouteLoader { type_id, future } = loader;
+            let mut routes = self.routes;
+            let key = route_string.clone();
+            let task = self.scope.in_runtime(|| {
+                spawn(async move {
+                    let value = future.await;
+                    if let Some(slot) = routes
+                        .write()
+                        .get_mut(&key)
+                        .and_then(|slots| slots.get_mut(&type_id))
+                    {
+                        slot.value = Some(value);
+                    }
+                })
+            });
+            slots.insert(type_id, LoaderSlot { value: None, task });
+            tasks.push(task);
+        }
+        self.routes.write().insert(route_string, slots);
+
+        // Poll every loader right away in case the data is already available
+        for task in tasks {
+            let _ = task.poll_now();
+        }
+    }
+}
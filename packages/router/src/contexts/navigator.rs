@@ -1,4 +1,7 @@
-use crate::{ExternalNavigationFailure, NavigationTarget, RouterContext};
+use crate::{
+    ExternalNavigationFailure, HistoryState, NavigationTarget, NavigationTargetResolveError,
+    RouterContext,
+};
 
 /// Acquire the navigator without subscribing to updates.
 ///
@@ -47,11 +50,81 @@ impl Navigator {
 
     /// Push a new location.
     ///
-    /// The previous location will be available to go back to.
+    /// The previous location will be available to go back to. Relative targets like `..`,
+    /// `./edit`, `?page=2` or `#section` are resolved against the current route.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
         self.0.push(target)
     }
 
+    /// Push a new location and return an error if the target doesn't match any route.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_router::navigation::NavigationTargetResolveError;
+    /// # #[component]
+    /// # fn Post(id: usize) -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable, Debug, PartialEq)]
+    /// enum Route {
+    ///     #[route("/post/:id")]
+    ///     Post { id: usize },
+    /// }
+    ///
+    /// #[component]
+    /// fn NextPost() -> Element {
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| {
+    ///                 if let Err(err) = navigator().try_push("../next") {
+    ///                     tracing::error!("There is no next post: {err}");
+    ///                 }
+    ///             },
+    ///             "Next"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn try_push(
+        &self,
+        target: &str,
+    ) -> Result<Option<ExternalNavigationFailure>, NavigationTargetResolveError> {
+        self.0.try_push(target)
+    }
+
+    /// Push a new location and attach a state to its history entry.
+    ///
+    /// The state is serialized as JSON and isn't part of the URL. Read it with
+    /// [`RouterContext::current_state`] or [`crate::hooks::use_history_state`].
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Post(id: usize) -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable, Debug, PartialEq)]
+    /// enum Route {
+    ///     #[route("/post/:id")]
+    ///     Post { id: usize },
+    /// }
+    ///
+    /// #[component]
+    /// fn SearchResult(id: usize) -> Element {
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| {
+    ///                 navigator().push_with_state(Route::Post { id }, "search");
+    ///             },
+    ///             "Open"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.0.push_with_state(target, state)
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -61,4 +134,23 @@ impl Navigator {
     ) -> Option<ExternalNavigationFailure> {
         self.0.replace(target)
     }
+
+    /// Replace the current location and return an error if the target doesn't match any route.
+    pub fn try_replace(
+        &self,
+        target: &str,
+    ) -> Result<Option<ExternalNavigationFailure>, NavigationTargetResolveError> {
+        self.0.try_replace(target)
+    }
+
+    /// Replace the current location and the state attached to its history entry.
+    ///
+    /// The previous location will **not** be available to go back to.
+    pub fn replace_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.0.replace_with_state(target, state)
+    }
 }
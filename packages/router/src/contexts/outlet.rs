@@ -1,6 +1,6 @@
 use dioxus_core::{provide_context, try_consume_context, use_hook, Element, VNode};
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{routable::Routable, utils::use_router_internal::use_router_internal, RouterContext};
 
 /// A context that manages nested routing levels for outlet components.
 ///
@@ -38,13 +38,24 @@ impl<R> OutletContext<R> {
         self.current_level
     }
 
-    pub(crate) fn render() -> Element
+    pub(crate) fn render(name: Option<&str>) -> Element
     where
         R: Routable + Clone,
     {
         let router = use_router_internal().expect("Outlet must be inside of a router");
         let outlet: OutletContext<R> = use_outlet_context();
         let current_level = outlet.level();
+
+        // Named outlets render the slots of the current route and don't add a nesting level
+        if let Some(name) = name {
+            let current = router.current::<R>();
+            return match intercepted_background::<R>(&router) {
+                Some(_) if current.intercept_outlet() == Some(name) => current.render_intercepted(),
+                Some(background) => background.render_slot(name),
+                None => current.render_slot(name),
+            };
+        }
+
         provide_context(outlet.next());
 
         if let Some(error) = router.render_error() {
@@ -55,10 +66,19 @@ impl<R> OutletContext<R> {
             };
         }
 
-        router.current::<R>().render(current_level)
+        // If the current route intercepted the navigation, keep rendering the previous page behind it
+        match intercepted_background::<R>(&router) {
+            Some(background) => background.render(current_level),
+            None => router.current::<R>().render(current_level),
+        }
     }
 }
 
+/// Parse the page behind the current route if the current route intercepted the navigation
+fn intercepted_background<R: Routable>(router: &RouterContext) -> Option<R> {
+    router.intercepted_background()?.parse().ok()
+}
+
 /// Returns the current outlet context from the component hierarchy.
 ///
 /// This hook retrieves the outlet context from the current component scope. If no context is found,
@@ -10,8 +10,16 @@ use dioxus_history::history;
 use dioxus_signals::{CopyValue, Readable, Signal, Writable};
 
 use crate::{
-    components::child_router::consume_child_route_mapping, navigation::NavigationTarget,
-    routable::Routable, router_cfg::RouterConfig, SiteMapSegment,
+    components::child_router::consume_child_route_mapping,
+    contexts::scroll::{ScrollAction, ScrollEntries},
+    guard::GuardOutcome,
+    i18n::Locales,
+    navigation::{
+        is_relative, resolve_relative, HistoryState, NavigationTarget, NavigationTargetResolveError,
+    },
+    routable::Routable,
+    router_cfg::RouterConfig,
+    SiteMapSegment,
 };
 
 /// An error that is thrown when the router fails to parse a route
@@ -72,9 +80,28 @@ struct RouterContextInner {
 
     internal_route: fn(&str) -> bool,
 
+    intercepts_route: fn(&str) -> bool,
+
+    intercepted: Option<InterceptedRoute>,
+
+    locales: Option<Locales>,
+
+    /// The locale of routes without a locale prefix if it was negotiated from the request
+    negotiated_locale: Option<&'static str>,
+
+    scroll: ScrollEntries,
+
+    view_transitions: bool,
+
     site_map: &'static [SiteMapSegment],
 }
 
+/// A route that intercepted a client side navigation along with the page that stays visible behind it
+struct InterceptedRoute {
+    route: String,
+    background: String,
+}
+
 impl RouterContextInner {
     fn update_subscribers(&self) {
         for &id in self.subscribers.lock().unwrap().iter() {
@@ -88,6 +115,123 @@ impl RouterContextInner {
         }
     }
 
+    /// Push or replace an internal route and remember the page behind it if the route intercepts
+    /// client side navigation. Routes without a locale prefix keep the current locale.
+    ///
+    /// If `scroll` is `false`, the scroll position is kept after the navigation renders. The
+    /// `view_transition` type animates the navigation even if view transitions are disabled. The
+    /// `state` is attached to the new history entry.
+    fn navigate(
+        &mut self,
+        route: String,
+        replace: bool,
+        scroll: bool,
+        view_transition: Option<String>,
+        state: Option<HistoryState>,
+    ) {
+        let route = self.localize_target(route);
+        self.navigate_localized(route, replace, scroll, view_transition, state);
+    }
+
+    /// Add the locale of the current route to a route that navigation targets if it doesn't have one
+    fn localize_target(&self, route: String) -> String {
+        match self.locales {
+            Some(locales) if locales.split_route(&route).0.is_none() => {
+                let locale = self.locale(&history().current_route());
+                self.localize(&route, locale)
+            }
+            _ => route,
+        }
+    }
+
+    fn navigate_localized(
+        &mut self,
+        route: String,
+        replace: bool,
+        scroll: bool,
+        view_transition: Option<String>,
+        state: Option<HistoryState>,
+    ) {
+        let history = history();
+        let previous = self.strip_locale(&history.current_route());
+        match (replace, state) {
+            (true, Some(state)) => history.replace_with_state(route, state.0),
+            (true, None) => history.replace(route),
+            (false, Some(state)) => history.push_with_state(route, state.0),
+            (false, None) => history.push(route),
+        }
+        let current = self.strip_locale(&history.current_route());
+        if previous == current {
+            return;
+        }
+        self.scroll.navigated(replace, scroll);
+        self.start_view_transition(view_transition);
+
+        self.intercepted = match (self.intercepts_route)(&current) {
+            true => {
+                // Keep the original page visible when navigating from one intercepting route to another
+                let background = match self.intercepted.take() {
+                    Some(intercepted) if intercepted.route == previous => intercepted.background,
+                    _ => previous,
+                };
+                Some(InterceptedRoute {
+                    route: current,
+                    background,
+                })
+            }
+            false => None,
+        };
+    }
+
+    /// Render the next update of the page in a view transition if view transitions are enabled or
+    /// the navigation asked for one
+    fn start_view_transition(&self, view_transition: Option<String>) {
+        let types = match view_transition {
+            Some(view_transition) => vec![view_transition],
+            None if self.view_transitions => Vec::new(),
+            None => return,
+        };
+        #[cfg(feature = "html")]
+        if let Some(document) =
+            dioxus_core::try_consume_context::<std::rc::Rc<dyn dioxus_document::Document>>()
+        {
+            document.start_view_transition(types);
+        }
+    }
+
+    /// The locale of routes without a locale prefix
+    fn unprefixed_locale(&self) -> Option<&'static str> {
+        let locales = self.locales?;
+        Some(self.negotiated_locale.unwrap_or(locales.default))
+    }
+
+    /// The locale of a route in the history
+    fn locale(&self, route: &str) -> Option<&'static str> {
+        let locales = self.locales?;
+        locales
+            .split_route(route)
+            .0
+            .or_else(|| self.unprefixed_locale())
+    }
+
+    /// Remove the locale prefix from a route in the history
+    fn strip_locale(&self, route: &str) -> String {
+        match self.locales {
+            Some(locales) => locales.split_route(route).1,
+            None => route.to_string(),
+        }
+    }
+
+    /// Add the prefix for a locale to a route without a locale prefix
+    fn localize(&self, route: &str, locale: Option<&str>) -> String {
+        match locale {
+            Some(locale) if Some(locale) != self.unprefixed_locale() => {
+                prefix_locale(locale, route)
+            }
+            _ => route.to_string(),
+        }
+    }
+
     fn external(&mut self, external: String) -> Option<ExternalNavigationFailure> {
         match history().external(external.clone()) {
             true => None,
@@ -103,6 +247,15 @@ impl RouterContextInner {
     }
 }
 
+/// Add the prefix of a locale to a route. The root route of a locale is `/fr` instead of `/fr/`
+fn prefix_locale(locale: &str, route: &str) -> String {
+    let rest = route.trim_start_matches('/');
+    match rest.starts_with(['?', '#']) || rest.is_empty() {
+        true => format!("/{locale}{rest}"),
+        false => format!("/{locale}/{rest}"),
+    }
+}
+
 /// A collection of router data that manages all routing functionality.
 #[derive(Clone, Copy)]
 pub struct RouterContext {
@@ -110,7 +263,10 @@ pub struct RouterContext {
 }
 
 impl RouterContext {
-    pub(crate) fn new<R: Routable + 'static>(cfg: RouterConfig<R>) -> Self {
+    pub(crate) fn new<R: Routable + 'static>(
+        cfg: RouterConfig<R>,
+        negotiated_locale: Option<&'static str>,
+    ) -> Self {
         let subscribers = Arc::new(Mutex::new(HashSet::new()));
         let mapping = consume_child_route_mapping();
 
@@ -139,6 +295,20 @@ impl RouterContext {
 
             internal_route: |route| R::from_str(route).is_ok(),
 
+            intercepts_route: |route| {
+                R::from_str(route).is_ok_and(|route| route.intercept_outlet().is_some())
+            },
+
+            intercepted: None,
+
+            locales: R::LOCALES,
+
+            negotiated_locale,
+
+            scroll: ScrollEntries::default(),
+
+            view_transitions: cfg.view_transitions,
+
             site_map: R::SITE_MAP,
         };
 
@@ -158,13 +328,78 @@ impl RouterContext {
         // If the current route is different from the one in the browser, replace the current route
         let current_route: R = myself.current();
 
-        if current_route.to_string() != history.current_route() {
+        let requested_route = myself.inner.read().strip_locale(&history.current_route());
+        if current_route.to_string() != requested_route {
+            #[cfg(feature = "streaming")]
+            let location = myself
+                .inner
+                .read()
+                .localize_target(current_route.to_string());
             myself.replace(current_route);
+            // The initial route is always rendered as a full page, even if it intercepts navigation
+            myself.inner.write_unchecked().intercepted = None;
+
+            // Answer requests for the path of a `#[redirect]` with a redirect on the server
+            #[cfg(feature = "streaming")]
+            if let Some(kind) = R::redirect_kind(&requested_route) {
+                myself.redirect_response(
+                    location,
+                    match kind {
+                        crate::guard::RedirectKind::Temporary => {
+                            dioxus_fullstack_hooks::RedirectStatus::Temporary
+                        }
+                        crate::guard::RedirectKind::Permanent => {
+                            dioxus_fullstack_hooks::RedirectStatus::Permanent
+                        }
+                    },
+                );
+            }
         }
 
+        // The guards of the initial route run before anything renders. Later navigations are guarded
+        // by the router component
+        #[cfg(feature = "streaming")]
+        myself.describe_response::<R>();
+        myself.follow_guard_redirect::<R>();
+
         myself
     }
 
+    /// Replace the current route with the target of its guard if the guard redirects
+    pub(crate) fn follow_guard_redirect<R: Routable>(&self) {
+        let route = self.full_route_string().parse::<R>();
+        if let Ok(GuardOutcome::Redirect(target)) = route.as_ref().map(Routable::guard) {
+            self.guard_redirect(target.into());
+        }
+    }
+
+    /// Tell the server how to answer the request for the initial route
+    #[cfg(feature = "streaming")]
+    fn describe_response<R: Routable>(&self) {
+        // The locale of routes without a locale prefix is picked from the Accept-Language header
+        if self.is_unprefixed_locale_route() {
+            dioxus_fullstack_hooks::vary_response("Accept-Language");
+        }
+        let Ok(route) = self.full_route_string().parse::<R>() else {
+            return;
+        };
+        // What a guarded route renders depends on the visitor, so it must never be shared between visitors
+        if route.is_guarded() {
+            dioxus_fullstack_hooks::mark_response_private();
+        }
+        if !matches!(route.guard(), GuardOutcome::Allow) {
+            return;
+        }
+        // Respond with the status of not found and error routes
+        if let Some(status) = route.http_status() {
+            dioxus_fullstack_hooks::set_status(status);
+        }
+        // Let the server preload the wasm module for the route if it is split
+        if let Some(module) = route.split_module() {
+            dioxus_fullstack_hooks::preload_split_module(module);
+        }
+    }
+
     /// Check if the router is running in a liveview context
     /// We do some slightly weird things for liveview because of the network boundary
     pub(crate) fn include_prevent_default(&self) -> bool {
@@ -199,11 +434,21 @@ impl RouterContext {
         self.change_route();
     }
 
-    pub(crate) fn push_any(&self, target: NavigationTarget) -> Option<ExternalNavigationFailure> {
+    /// Push a new location from a [`crate::components::Link`]. If `scroll` is `false`, the scroll
+    /// position is kept instead of scrolling to the top of the page or the `#hash` target.
+    pub(crate) fn push_any(
+        &self,
+        target: NavigationTarget,
+        scroll: bool,
+        view_transition: Option<String>,
+        state: Option<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
         {
             let mut write = self.inner.write_unchecked();
             match target {
-                NavigationTarget::Internal(p) => history().push(p),
+                NavigationTarget::Internal(p) => {
+                    write.navigate(p, false, scroll, view_transition, state)
+                }
                 NavigationTarget::External(e) => return write.external(e),
             }
         }
@@ -215,19 +460,20 @@ impl RouterContext {
     ///
     /// The previous location will be available to go back to.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.push(p)
-                }
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
+        self.push_any(target.into(), true, None, None)
+    }
 
-        self.change_route()
+    /// Push a new location and attach a [`HistoryState`] to its history entry.
+    ///
+    /// The state isn't part of the URL. It can be read with [`RouterContext::current_state`] while
+    /// the entry is active, including after going back or forward to it. External targets ignore
+    /// the state.
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.push_any(target.into(), true, None, Some(state.into()))
     }
 
     /// Replace the current location.
@@ -237,21 +483,115 @@ impl RouterContext {
         &self,
         target: impl Into<NavigationTarget>,
     ) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
+        self.replace_any(target.into(), None)
+    }
+
+    /// Replace the current location and the [`HistoryState`] of its history entry.
+    ///
+    /// The previous location will **not** be available to go back to.
+    pub fn replace_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.replace_any(target.into(), Some(state.into()))
+    }
+
+    fn replace_any(
+        &self,
+        target: NavigationTarget,
+        state: Option<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
         {
-            let mut state = self.inner.write_unchecked();
+            let mut write = self.inner.write_unchecked();
             match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.replace(p)
-                }
-                NavigationTarget::External(e) => return state.external(e),
+                NavigationTarget::Internal(p) => write.navigate(p, true, true, None, state),
+                NavigationTarget::External(e) => return write.external(e),
             }
         }
 
         self.change_route()
     }
 
+    /// Replace the current route with the target a route guard redirected to. On the server, the
+    /// request is answered with a temporary redirect to the target.
+    pub(crate) fn guard_redirect(&self, target: NavigationTarget) {
+        // The history keeps reporting the requested route while the server renders, so the location
+        // is taken from the target instead of the history
+        #[cfg(feature = "streaming")]
+        match &target {
+            NavigationTarget::Internal(route) => {
+                let location = self.inner.read().localize_target(route.clone());
+                self.redirect_response(location, dioxus_fullstack_hooks::RedirectStatus::Temporary);
+            }
+            NavigationTarget::External(url) => dioxus_fullstack_hooks::redirect(
+                url.clone(),
+                dioxus_fullstack_hooks::RedirectStatus::Temporary,
+            ),
+        }
+        self.replace_any(target, None);
+    }
+
+    /// Tell the server to answer the request with a redirect to a route of the router
+    #[cfg(feature = "streaming")]
+    fn redirect_response(&self, route: String, status: dioxus_fullstack_hooks::RedirectStatus) {
+        let location = format!("{}{route}", history().current_prefix().unwrap_or_default());
+        dioxus_fullstack_hooks::redirect(location, status);
+    }
+
+    /// Resolve a navigation target against the current route.
+    ///
+    /// Relative targets like `..`, `./edit`, `?page=2` or `#section` are resolved with
+    /// [`crate::navigation::resolve_relative`]. Absolute URLs are external targets. Targets that
+    /// point to a page of the router that doesn't match any route return an error instead of
+    /// falling back to an external navigation.
+    pub fn resolve(&self, target: &str) -> Result<NavigationTarget, NavigationTargetResolveError> {
+        let route = match is_relative(target) {
+            true => resolve_relative(&self.full_route_string(), target)
+                .map_err(NavigationTargetResolveError::InvalidUrl)?,
+            false if target.starts_with('/') => target.to_string(),
+            false => {
+                return url::Url::parse(target)
+                    .map(|_| NavigationTarget::External(target.to_string()))
+                    .map_err(NavigationTargetResolveError::InvalidUrl)
+            }
+        };
+        match self.internal_route(&route) {
+            true => Ok(NavigationTarget::Internal(route)),
+            false => Err(NavigationTargetResolveError::UnknownRoute(route)),
+        }
+    }
+
+    /// Resolve a navigation target with [`RouterContext::resolve`] and push it.
+    pub fn try_push(
+        &self,
+        target: &str,
+    ) -> Result<Option<ExternalNavigationFailure>, NavigationTargetResolveError> {
+        Ok(self.push(self.resolve(target)?))
+    }
+
+    /// Resolve a navigation target with [`RouterContext::resolve`] and replace the current location
+    /// with it.
+    pub fn try_replace(
+        &self,
+        target: &str,
+    ) -> Result<Option<ExternalNavigationFailure>, NavigationTargetResolveError> {
+        Ok(self.replace(self.resolve(target)?))
+    }
+
+    /// The state attached to the current history entry, deserialized as `T`.
+    ///
+    /// Returns [`None`] if the entry doesn't have a state or the state isn't a `T`.
+    pub fn current_state<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self.history_state()?.deserialize()
+    }
+
+    /// The [`HistoryState`] attached to the current history entry.
+    pub fn history_state(&self) -> Option<HistoryState> {
+        self.inner.read().subscribe_to_current_context();
+        history().current_state().map(HistoryState::from_json)
+    }
+
     /// The route that is currently active.
     pub fn current<R: Routable>(&self) -> R {
         let absolute_route = self.full_route_string();
@@ -280,7 +620,56 @@ impl RouterContext {
         let inner = self.inner.read();
         inner.subscribe_to_current_context();
         let history = history();
-        history.current_route()
+        inner.strip_locale(&history.current_route())
+    }
+
+    /// The active locale if the router has locales.
+    ///
+    /// This is the locale the current route is prefixed with. Routes without a locale prefix use the
+    /// default locale, or the locale picked from the `Accept-Language` header during server side rendering.
+    pub fn locale(&self) -> Option<&'static str> {
+        let inner = self.inner.read();
+        inner.subscribe_to_current_context();
+        inner.locale(&history().current_route())
+    }
+
+    /// Navigate to the current route in a different locale.
+    ///
+    /// Does nothing if the router doesn't have the locale.
+    pub fn set_locale(&self, locale: &str) {
+        {
+            let mut write = self.inner.write_unchecked();
+            let Some(locale) = write.locales.and_then(|locales| locales.find(locale)) else {
+                tracing::error!("Unable to switch to the locale {locale} because it isn't one of the locales of the router");
+                return;
+            };
+            let route = write.strip_locale(&history().current_route());
+            let route = write.localize(&route, Some(locale));
+            write.navigate_localized(route, false, true, None, None);
+        }
+
+        self.change_route();
+    }
+
+    /// The page that stays visible behind the current route if it intercepted a client side navigation.
+    pub(crate) fn intercepted_background(&self) -> Option<String> {
+        let current = self.full_route_string();
+        self.inner
+            .read()
+            .intercepted
+            .as_ref()
+            .filter(|intercepted| intercepted.route == current)
+            .map(|intercepted| intercepted.background.clone())
+    }
+
+    /// Move the scroll tracking to the history entry of the current route. Returns the key of the
+    /// entry and what should happen to the scroll position, or `None` if the entry didn't change.
+    pub(crate) fn update_scroll(&self, route: &str) -> Option<(u64, ScrollAction)> {
+        let mut write = self.inner.write_unchecked();
+        match write.scroll.is_empty() {
+            true => Some(write.scroll.initial(route)),
+            false => write.scroll.update(route),
+        }
     }
 
     /// The prefix that is currently active.
@@ -335,7 +724,45 @@ impl RouterContext {
     }
 
     pub(crate) fn internal_route(&self, route: &str) -> bool {
-        (self.inner.read().internal_route)(route)
+        let inner = self.inner.read();
+        (inner.internal_route)(&inner.strip_locale(route))
+    }
+
+    /// Add the locale prefix of the current locale to a route
+    pub(crate) fn localize_route(&self, route: &str) -> String {
+        self.inner.read().localize_target(route.to_string())
+    }
+
+    /// Check if the current route in the history has no locale prefix. The locale of these routes
+    /// depends on the languages the client accepts.
+    #[cfg(feature = "streaming")]
+    pub(crate) fn is_unprefixed_locale_route(&self) -> bool {
+        self.inner
+            .read()
+            .locales
+            .is_some_and(|locales| locales.split_route(&history().current_route()).0.is_none())
+    }
+
+    /// The links to the current page in every locale along with the `x-default` link to the page
+    /// without a locale prefix. The links start with `origin`, which is the configured origin of the
+    /// site or empty for relative links. Returns an empty list if the router doesn't have locales.
+    pub(crate) fn locale_alternates(&self, origin: &str) -> Vec<(&'static str, String)> {
+        let route = self.full_route_string();
+        let inner = self.inner.read();
+        let Some(locales) = inner.locales else {
+            return Vec::new();
+        };
+        let prefix = format!("{origin}{}", history().current_prefix().unwrap_or_default());
+        let mut alternates: Vec<_> = locales
+            .locales
+            .iter()
+            .map(|&locale| {
+                // Always link to the prefixed route so the link doesn't depend on the negotiated locale
+                (locale, format!("{prefix}{}", prefix_locale(locale, &route)))
+            })
+            .collect();
+        alternates.push(("x-default", format!("{prefix}{route}")));
+        alternates
     }
 }
 
@@ -385,6 +812,15 @@ where
         self.inner.push(target.into())
     }
 
+    /// Push a new location and attach a [`HistoryState`] to its history entry.
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget<R>>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.inner.push_with_state(target.into(), state)
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -395,6 +831,20 @@ where
         self.inner.replace(target.into())
     }
 
+    /// Replace the current location and the [`HistoryState`] of its history entry.
+    pub fn replace_with_state(
+        &self,
+        target: impl Into<NavigationTarget<R>>,
+        state: impl Into<HistoryState>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.inner.replace_with_state(target.into(), state)
+    }
+
+    /// The state attached to the current history entry, deserialized as `T`.
+    pub fn current_state<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self.inner.current_state()
+    }
+
     /// The route that is currently active.
     pub fn current(&self) -> R
     where
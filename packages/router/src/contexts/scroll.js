@@ -0,0 +1,65 @@
+// Scroll management for the router. The positions of the window and every element with a
+// `data-dioxus-scroll` attribute are saved for each history entry while the user scrolls.
+(function () {
+  if (window.__dioxusRouterScroll) return;
+
+  const state = { key: null, positions: {} };
+  window.__dioxusRouterScroll = state;
+
+  // The router restores scroll positions itself
+  if ("scrollRestoration" in history) history.scrollRestoration = "manual";
+
+  const containerKey = (element) =>
+    element.getAttribute("data-dioxus-scroll") || element.id;
+
+  const containers = () =>
+    document.querySelectorAll("[data-dioxus-scroll]");
+
+  const saved = () =>
+    (state.positions[state.key] ||= { window: null, containers: {} });
+
+  const save = (target) => {
+    if (state.key === null) return;
+    if (target === document || target === document.documentElement) {
+      saved().window = [window.scrollX, window.scrollY];
+    } else if (target instanceof Element && target.hasAttribute("data-dioxus-scroll")) {
+      const key = containerKey(target);
+      if (key) saved().containers[key] = [target.scrollLeft, target.scrollTop];
+    }
+  };
+
+  // Scroll events don't bubble, but they can be captured from the document
+  document.addEventListener("scroll", (event) => save(event.target), {
+    capture: true,
+    passive: true,
+  });
+
+  state.navigate = (key, action, hash) => {
+    state.key = key;
+
+    if (action === "restore") {
+      const positions = state.positions[key];
+      const [x, y] = (positions && positions.window) || [0, 0];
+      window.scrollTo(x, y);
+      for (const element of containers()) {
+        const position = positions && positions.containers[containerKey(element)];
+        if (position) element.scrollTo(position[0], position[1]);
+      }
+    } else if (action === "reset") {
+      let target = null;
+      if (hash) {
+        try {
+          target = document.getElementById(decodeURIComponent(hash));
+        } catch (_) {
+          target = document.getElementById(hash);
+        }
+      }
+      if (target) target.scrollIntoView();
+      else window.scrollTo(0, 0);
+    }
+
+    // Remember where the entry starts even if the user never scrolls
+    save(document);
+    for (const element of containers()) save(element);
+  };
+})();
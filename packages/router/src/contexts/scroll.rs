@@ -0,0 +1,129 @@
+//! Scroll management that works with every [`dioxus_history::History`].
+//!
+//! The router keeps track of which history entry is active. After a navigation renders, it tells the
+//! renderer through `dioxus_document::eval` to scroll to the top of the page or to the `#hash`
+//! target for new entries, and to restore the saved positions when going back or forward.
+
+/// What should happen to the scroll position after a navigation renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScrollAction {
+    /// Scroll to the element the `#hash` of the route points to, or to the top of the page
+    Reset,
+    /// Restore the scroll positions that were saved for the history entry
+    Restore,
+    /// Keep the current scroll positions
+    Preserve,
+}
+
+/// A navigation the router started that hasn't been rendered yet
+struct PendingNavigation {
+    replace: bool,
+    scroll: bool,
+}
+
+struct ScrollEntry {
+    key: u64,
+    route: String,
+}
+
+/// The history entries the router has seen. Each entry has a unique key the scroll positions are
+/// saved under.
+#[derive(Default)]
+pub(crate) struct ScrollEntries {
+    entries: Vec<ScrollEntry>,
+    index: usize,
+    next_key: u64,
+    pending: Option<PendingNavigation>,
+}
+
+impl ScrollEntries {
+    /// Remember that the router pushed or replaced an entry
+    pub(crate) fn navigated(&mut self, replace: bool, scroll: bool) {
+        self.pending = Some(PendingNavigation { replace, scroll });
+    }
+
+    /// Move to the entry of the route that was rendered. Returns the key of the entry and what to do
+    /// with the scroll position, or `None` if the entry didn't change.
+    pub(crate) fn update(&mut self, route: &str) -> Option<(u64, ScrollAction)> {
+        let pending = self.pending.take();
+        let current = self.entries.get(self.index)?;
+        if pending.is_none() && current.route == route {
+            return None;
+        }
+
+        let Some(pending) = pending else {
+            // The history moved without the router, like the back button of a browser. Find the
+            // entry next to the current one that it moved to
+            if self.index > 0 && self.entries[self.index - 1].route == route {
+                self.index -= 1;
+            } else if self
+                .entries
+                .get(self.index + 1)
+                .is_some_and(|entry| entry.route == route)
+            {
+                self.index += 1;
+            } else {
+                return Some((self.insert(route, false), ScrollAction::Reset));
+            }
+            return Some((self.entries[self.index].key, ScrollAction::Restore));
+        };
+
+        let action = match pending.scroll {
+            true => ScrollAction::Reset,
+            false => ScrollAction::Preserve,
+        };
+        Some((self.insert(route, pending.replace), action))
+    }
+
+    /// Start tracking the first entry of the history
+    pub(crate) fn initial(&mut self, route: &str) -> (u64, ScrollAction) {
+        self.pending = None;
+        (self.insert(route, false), ScrollAction::Reset)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn insert(&mut self, route: &str, replace: bool) -> u64 {
+        let key = self.next_key;
+        self.next_key += 1;
+        let entry = ScrollEntry {
+            key,
+            route: route.to_string(),
+        };
+
+        if replace && !self.entries.is_empty() {
+            self.entries[self.index] = entry;
+        } else {
+            if !self.entries.is_empty() {
+                self.index += 1;
+            }
+            // Pushing an entry drops every entry after the current one
+            self.entries.truncate(self.index);
+            self.entries.push(entry);
+        }
+
+        key
+    }
+}
+
+/// The script that moves the renderer to a history entry
+#[cfg(feature = "html")]
+pub(crate) fn scroll_script(key: u64, action: ScrollAction, route: &str) -> String {
+    let helpers = include_str!("./scroll.js");
+    let hash = route
+        .split_once('#')
+        .map(|(_, hash)| hash)
+        .unwrap_or_default()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    let action = match action {
+        ScrollAction::Reset => "reset",
+        ScrollAction::Restore => "restore",
+        ScrollAction::Preserve => "preserve",
+    };
+    format!(r#"{helpers};window.__dioxusRouterScroll.navigate({key}, "{action}", "{hash}");"#)
+}
@@ -0,0 +1,105 @@
+//! Guards that run before a route renders and redirects with http status codes.
+//!
+//! Add a guard to a route with the `#[guard(function)]` attribute. When the attribute follows a
+//! `#[nest]` or `#[layout]` attribute, the guard protects every route until the matching
+//! `#[end_nest]` or `#[end_layout]`. The guard is called with the current route before the route
+//! or any of its loaders run:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! # use dioxus_router::guard::GuardOutcome;
+//! # #[component]
+//! # fn Login() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Dashboard() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Settings() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn AccountFrame() -> Element { VNode::empty() }
+//! # fn is_logged_in() -> bool { false }
+//! #[derive(Clone, Debug, PartialEq, Routable)]
+//! enum Route {
+//!     #[layout(AccountFrame)]
+//!     #[guard(require_login)]
+//!         #[route("/dashboard")]
+//!         Dashboard {},
+//!         #[route("/settings")]
+//!         Settings {},
+//!     #[end_layout]
+//!     #[route("/login")]
+//!     Login {},
+//! }
+//!
+//! fn require_login(_: &Route) -> GuardOutcome<Route> {
+//!     match is_logged_in() {
+//!         true => GuardOutcome::Allow,
+//!         false => GuardOutcome::Redirect(Route::Login {}.into()),
+//!     }
+//! }
+//!
+//! assert!(matches!(Route::Dashboard {}.guard(), GuardOutcome::Redirect(_)));
+//! assert!(matches!(Route::Login {}.guard(), GuardOutcome::Allow));
+//! ```
+//!
+//! Guards of the initial route run when the [`crate::components::Router`] is created, before anything
+//! renders. After that, they run after every navigation. They can read contexts and signals, and the
+//! guard runs again when a signal it read changes, but they can't call hooks.
+//!
+//! During server side rendering, a guard that redirects answers the request with a
+//! `307 Temporary Redirect` and a `Location` header. Routes that match a `#[redirect]` answer with a
+//! `307 Temporary Redirect`, or a `308 Permanent Redirect` if the redirect is marked `permanent`.
+//! Guarded routes are sent with `Cache-Control: private, no-store` and are never stored in the
+//! incremental cache, since what they render depends on the visitor.
+
+use dioxus_core::Element;
+
+use crate::navigation::NavigationTarget;
+
+/// The result of a route guard.
+pub enum GuardOutcome<R> {
+    /// Render the route.
+    Allow,
+    /// Replace the current route with another one instead of rendering it.
+    Redirect(NavigationTarget<R>),
+    /// Render an element in place of the route.
+    Fallback(Element),
+}
+
+impl<R> GuardOutcome<R> {
+    /// Convert the route of a redirect to another route type. This is used to run the guards of
+    /// child routers from the parent router.
+    pub fn map_route<R2>(self, f: impl FnOnce(R) -> R2) -> GuardOutcome<R2> {
+        match self {
+            GuardOutcome::Allow => GuardOutcome::Allow,
+            GuardOutcome::Redirect(NavigationTarget::Internal(route)) => {
+                GuardOutcome::Redirect(NavigationTarget::Internal(f(route)))
+            }
+            GuardOutcome::Redirect(NavigationTarget::External(url)) => {
+                GuardOutcome::Redirect(NavigationTarget::External(url))
+            }
+            GuardOutcome::Fallback(element) => GuardOutcome::Fallback(element),
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for GuardOutcome<R>
+where
+    NavigationTarget<R>: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardOutcome::Allow => write!(f, "Allow"),
+            GuardOutcome::Redirect(target) => f.debug_tuple("Redirect").field(target).finish(),
+            GuardOutcome::Fallback(_) => write!(f, "Fallback"),
+        }
+    }
+}
+
+/// The kind of a `#[redirect]` route.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedirectKind {
+    /// A temporary redirect. Servers answer it with a `307 Temporary Redirect`.
+    Temporary,
+    /// A redirect marked with `permanent`. Servers answer it with a `308 Permanent Redirect`.
+    Permanent,
+}
@@ -0,0 +1,70 @@
+use crate::metadata::{breadcrumbs, Breadcrumb};
+use crate::utils::use_router_internal::use_router_internal;
+use crate::Routable;
+
+/// A hook that returns the breadcrumbs of the current route.
+///
+/// The breadcrumbs walk the parent chain of the current route from the outermost parent to the route
+/// itself. Each entry is a route with a `title` in its `#[route]` attribute. Routes without a title
+/// are left out.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_history::{History, MemoryHistory};
+/// # use dioxus_router::components::HistoryProvider;
+/// # use std::rc::Rc;
+/// #[derive(Clone, Routable, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/", title = "Home")]
+///     Home {},
+///     #[route("/blog", title = "Blog")]
+///     Blog {},
+///     #[route("/blog/:id", title = "Post {id}")]
+///     Post { id: usize },
+/// }
+///
+/// #[component]
+/// fn Post(id: usize) -> Element {
+///     rsx! {
+///         nav {
+///             for crumb in use_breadcrumbs::<Route>() {
+///                 Link { to: crumb.route, "{crumb.title}" }
+///             }
+///         }
+///     }
+/// }
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Blog() -> Element { VNode::empty() }
+/// #
+/// # #[component]
+/// # fn App() -> Element {
+/// #     rsx! {
+/// #         HistoryProvider {
+/// #             history: move |_| Rc::new(MemoryHistory::with_initial_path("/blog/1")) as Rc<dyn History>,
+/// #             Router::<Route> {}
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # vdom.rebuild_in_place();
+/// # assert_eq!(
+/// #     dioxus_ssr::render(&vdom),
+/// #     r#"<nav><a href="/">Home</a><a href="/blog">Blog</a><a href="/blog/1" aria-current="page">Post 1</a></nav>"#
+/// # );
+/// ```
+#[must_use]
+pub fn use_breadcrumbs<R: Routable + Clone>() -> Vec<Breadcrumb<R>> {
+    match use_router_internal() {
+        Some(r) => breadcrumbs(&r.current::<R>()),
+        None => {
+            panic!("`use_breadcrumbs` must be called in a descendant of a Router component")
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that returns the state attached to the current history entry, deserialized as `T`.
+///
+/// The state is passed to [`crate::Navigator::push_with_state`] or the `state` prop of a
+/// [`crate::components::Link`]. It stays with the history entry when the user goes back or forward
+/// to it, but isn't part of the URL. Returns `None` if the entry doesn't have a state or the state
+/// isn't a `T`.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_history::{History, MemoryHistory};
+/// # use dioxus_router::components::HistoryProvider;
+/// # use std::rc::Rc;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/post/:id")]
+///     Post { id: usize },
+/// }
+///
+/// #[component]
+/// fn Post(id: usize) -> Element {
+///     let back = match use_history_state::<String>().as_deref() {
+///         Some("search") => "Back to the search results",
+///         _ => "Back",
+///     };
+///     rsx! { p { "{back}" } }
+/// }
+/// #
+/// # #[component]
+/// # fn App() -> Element {
+/// #     rsx! {
+/// #         HistoryProvider {
+/// #             history: move |_| {
+/// #                 let history = MemoryHistory::default();
+/// #                 history.push_with_state("/post/1".to_string(), r#""search""#.to_string());
+/// #                 Rc::new(history) as Rc<dyn History>
+/// #             },
+/// #             Router::<Route> {}
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # vdom.rebuild_in_place();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<p>Back to the search results</p>");
+/// ```
+#[must_use]
+pub fn use_history_state<T: serde::de::DeserializeOwned>() -> Option<T> {
+    match use_router_internal() {
+        Some(r) => r.current_state(),
+        None => {
+            panic!("`use_history_state` must be called in a descendant of a Router component")
+        }
+    }
+}
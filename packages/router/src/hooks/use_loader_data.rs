@@ -0,0 +1,91 @@
+use std::any::TypeId;
+
+use dioxus_core::{suspend, try_consume_context, use_hook, CapturedError, RenderError};
+
+use crate::contexts::LoaderContext;
+
+/// A hook that reads the data returned by a loader of the current route or one of its layouts.
+///
+/// All loaders for a route and its layouts start in parallel as soon as the router navigates to the
+/// route (or during server side rendering). This hook looks up the loader by its output type and
+/// suspends the component until the data is ready. The loaders of a route and its layouts must return
+/// different types; the [`crate::Routable`] derive fails to compile if two of them return the same type.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/blog/:id", loader = load_post)]
+///     Blog { id: usize },
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Post {
+///     title: String,
+/// }
+///
+/// // Loaders take the same parameters as the component they load data for
+/// async fn load_post(id: usize) -> Post {
+///     Post {
+///         title: format!("Post {id}"),
+///     }
+/// }
+///
+/// #[component]
+/// fn Blog(id: usize) -> Element {
+///     let post = use_loader_data::<Post>()?;
+///     rsx! {
+///         h1 { "{post.title}" }
+///     }
+/// }
+/// ```
+///
+/// Loaders of a route and its layouts that return the same type are rejected, because the hook could
+/// not tell their data apart. Wrap the data in a new type instead:
+/// ```rust, compile_fail
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[nest("/:section")]
+///         #[layout(Section, loader = load_section_title)]
+///             #[route("/:page", loader = load_page_title)]
+///             Page { section: String, page: String },
+/// }
+///
+/// async fn load_section_title(section: String) -> String {
+///     section
+/// }
+///
+/// async fn load_page_title(section: String, page: String) -> String {
+///     page
+/// }
+/// # #[component]
+/// # fn Section(section: String) -> Element { todo!() }
+/// # #[component]
+/// # fn Page(section: String, page: String) -> Element { todo!() }
+/// ```
+pub fn use_loader_data<T: Clone + 'static>() -> Result<T, RenderError> {
+    let loaders = use_hook(|| {
+        try_consume_context::<LoaderContext>()
+            .expect("`use_loader_data` must be called in a descendant of a Router component")
+    });
+
+    loaders.with_slot(TypeId::of::<T>(), |slot| match slot {
+        Some(slot) => match &slot.value {
+            Some(value) => Ok(value
+                .downcast_ref::<T>()
+                .expect("loader data is keyed by its type")
+                .clone()),
+            None => Err(suspend(slot.task).unwrap_err()),
+        },
+        None => Err(RenderError::Aborted(CapturedError::from_display(format!(
+            "No loader for the current route returns `{}`",
+            std::any::type_name::<T>()
+        )))),
+    })
+}
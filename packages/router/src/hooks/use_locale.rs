@@ -0,0 +1,58 @@
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that returns the active locale of a router with locales.
+///
+/// The locale comes from the locale prefix of the current route. Routes without a prefix use the
+/// default locale of the `#[i18n]` attribute, or the locale picked from the `Accept-Language` header
+/// during server side rendering. Returns `None` if the router doesn't have locales.
+///
+/// Switch to another locale with [`crate::RouterContext::set_locale`].
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_history::{History, MemoryHistory};
+/// # use dioxus_router::components::HistoryProvider;
+/// # use std::rc::Rc;
+/// #[derive(Clone, Routable)]
+/// #[i18n(locales = ["en", "fr"], default = "en")]
+/// enum Route {
+///     #[route("/about")]
+///     About {},
+/// }
+///
+/// #[component]
+/// fn About() -> Element {
+///     let greeting = match use_locale() {
+///         Some("fr") => "Bonjour",
+///         _ => "Hello",
+///     };
+///     rsx! { p { "{greeting}" } }
+/// }
+/// #
+/// # #[component]
+/// # fn App() -> Element {
+/// #     rsx! {
+/// #         HistoryProvider {
+/// #             history: move |_| Rc::new(MemoryHistory::with_initial_path("/fr/about")) as Rc<dyn History>,
+/// #             Router::<Route> {}
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # vdom.rebuild_in_place();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<p>Bonjour</p>");
+/// ```
+#[must_use]
+pub fn use_locale() -> Option<&'static str> {
+    match use_router_internal() {
+        Some(r) => r.locale(),
+        None => {
+            panic!("`use_locale` must be called in a descendant of a Router component")
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use dioxus_core::{try_consume_context, use_hook};
+use dioxus_history::history;
+use dioxus_hooks::use_effect;
+use dioxus_signals::{Readable, Signal, Writable};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    query::{deserialize_query, serialize_query},
+    NavigationTarget, RouterContext,
+};
+
+/// A hook that keeps a serde struct in sync with the query string of the current URL.
+///
+/// The state is deserialized from the query string when the component is created and whenever the
+/// user navigates. Writing to the returned signal replaces the query string in the URL. The URL is
+/// updated with `replace`, so changing the state does not add entries to the history.
+///
+/// The router removes any part of the URL the current route does not capture, so the route should
+/// spread the query into the same struct with `#[route("/path?:..query")]`.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/search?:..query")]
+///     Search { query: SearchQuery },
+/// }
+///
+/// #[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+/// struct SearchQuery {
+///     #[serde(default)]
+///     text: String,
+///     #[serde(default)]
+///     tag: Vec<String>,
+/// }
+///
+/// #[component]
+/// fn Search(query: SearchQuery) -> Element {
+///     // Reads /search?text=dioxus&tag=rust&tag=web
+///     let mut query = use_query_state::<SearchQuery>();
+///     rsx! {
+///         input {
+///             value: "{query.read().text}",
+///             oninput: move |event| query.write().text = event.value(),
+///         }
+///         for tag in query.read().tag.iter() {
+///             span { "{tag}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_query_state<Q>() -> Signal<Q>
+where
+    Q: Serialize + DeserializeOwned + Default + PartialEq + 'static,
+{
+    let router = use_hook(|| {
+        try_consume_context::<RouterContext>()
+            .expect("`use_query_state` must be called in a descendant of a Router component")
+    });
+    let mut state = use_hook(|| {
+        let route = history().current_route();
+        Signal::new(deserialize_query::<Q>(split_route(&route).1))
+    });
+
+    // Update the state when the user navigates to a different query
+    use_effect(move || {
+        let route = router.full_route_string();
+        let query = deserialize_query::<Q>(split_route(&route).1);
+        if *state.peek() != query {
+            state.set(query);
+        }
+    });
+
+    // Replace the query in the URL when the state changes
+    use_effect(move || {
+        let state = state.read();
+        let route = history().current_route();
+        let (path, query, hash) = split_route(&route);
+        if deserialize_query::<Q>(query) == *state {
+            return;
+        }
+        let query = serialize_query(&*state);
+        let route = match query.is_empty() {
+            true => format!("{path}{hash}"),
+            false => format!("{path}?{query}{hash}"),
+        };
+        router.replace(NavigationTarget::Internal(route));
+    });
+
+    state
+}
+
+/// Split a route into the path, the query without the `?` and the hash with the `#`
+fn split_route(route: &str) -> (&str, &str, &str) {
+    let (route, hash) = match route.find('#') {
+        Some(index) => route.split_at(index),
+        None => (route, ""),
+    };
+    let (path, query) = route.split_once('?').unwrap_or((route, ""));
+    (path, query, hash)
+}
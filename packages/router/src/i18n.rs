@@ -0,0 +1,90 @@
+//! Locale prefixed routing for internationalized apps.
+//!
+//! Add the `#[i18n(locales = ["en", "fr"], default = "en")]` attribute to a [`Routable`](crate::routable::Routable)
+//! enum to let every route be prefixed with a locale. `/about` renders the about page in the default
+//! locale and `/fr/about` renders it in French. Read the active locale with [`crate::hooks::use_locale`].
+//!
+//! During server side rendering, routes without a locale prefix use the locale that best matches the
+//! `Accept-Language` header of the request. Since the rendered page depends on the header, avoid caching
+//! routes without a locale prefix with incremental rendering.
+
+/// The locales a [`Routable`](crate::routable::Routable) enum can be prefixed with.
+///
+/// This is generated from the `#[i18n]` attribute of the enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locales {
+    /// All locales the app supports.
+    pub locales: &'static [&'static str],
+    /// The locale of routes without a locale prefix.
+    pub default: &'static str,
+}
+
+impl Locales {
+    /// Find the locale with the given name.
+    pub fn find(&self, locale: &str) -> Option<&'static str> {
+        self.locales.iter().copied().find(|l| *l == locale)
+    }
+
+    /// Split the locale prefix off a route. Returns `None` as the locale if the route doesn't start
+    /// with one of the locales.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_router::i18n::Locales;
+    ///
+    /// let locales = Locales { locales: &["en", "fr"], default: "en" };
+    /// assert_eq!(locales.split_route("/fr/about?q=1"), (Some("fr"), "/about?q=1".to_string()));
+    /// assert_eq!(locales.split_route("/fr"), (Some("fr"), "/".to_string()));
+    /// assert_eq!(locales.split_route("/about"), (None, "/about".to_string()));
+    /// ```
+    pub fn split_route(&self, route: &str) -> (Option<&'static str>, String) {
+        let Some(path) = route.strip_prefix('/') else {
+            return (None, route.to_string());
+        };
+        let end = path.find(['/', '?', '#']).unwrap_or(path.len());
+        match self.find(&path[..end]) {
+            Some(locale) => {
+                let rest = &path[end..];
+                let rest = match rest.starts_with('/') {
+                    true => rest.to_string(),
+                    false => format!("/{rest}"),
+                };
+                (Some(locale), rest)
+            }
+            None => (None, route.to_string()),
+        }
+    }
+
+    /// Pick the locale that best matches a list of languages the user prefers, like the languages
+    /// from the `Accept-Language` header. Languages are matched exactly first, and then by their
+    /// primary language, so `fr-CA` matches the `fr` locale.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_router::i18n::Locales;
+    ///
+    /// let locales = Locales { locales: &["en", "fr"], default: "en" };
+    /// assert_eq!(locales.negotiate(["de", "fr-CA", "en"]), Some("fr"));
+    /// assert_eq!(locales.negotiate(["de"]), None);
+    /// ```
+    pub fn negotiate<'a>(
+        &self,
+        preferred: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&'static str> {
+        fn primary(language: &str) -> &str {
+            language.split(['-', '_']).next().unwrap_or(language)
+        }
+
+        preferred.into_iter().find_map(|language| {
+            self.locales
+                .iter()
+                .find(|locale| locale.eq_ignore_ascii_case(language))
+                .or_else(|| {
+                    self.locales
+                        .iter()
+                        .find(|locale| primary(locale).eq_ignore_ascii_case(primary(language)))
+                })
+                .copied()
+        })
+    }
+}
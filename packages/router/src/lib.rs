@@ -5,8 +5,13 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod guard;
+pub mod i18n;
+pub mod metadata;
 pub mod navigation;
+mod query;
 pub mod routable;
+pub mod sitemap;
 
 /// Components interacting with the router.
 pub mod components {
@@ -39,10 +44,14 @@ pub mod components {
 }
 
 mod contexts {
+    pub(crate) mod loader;
     pub(crate) mod navigator;
     pub(crate) mod outlet;
+    pub(crate) use loader::LoaderContext;
+    pub use loader::RouteLoader;
     pub use outlet::{use_outlet_context, OutletContext};
     pub(crate) mod router;
+    pub(crate) mod scroll;
     pub use navigator::*;
     pub(crate) use router::*;
     pub use router::{root_router, GenericRouterContext, ParseRouteError, RouterContext};
@@ -61,18 +70,38 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_loader_data;
+    pub use use_loader_data::*;
+
+    mod use_query_state;
+    pub use use_query_state::*;
+
+    mod use_locale;
+    pub use use_locale::*;
+
+    mod use_history_state;
+    pub use use_history_state::*;
+
+    mod use_breadcrumbs;
+    pub use use_breadcrumbs::*;
 }
 
 pub use hooks::router;
 
 #[cfg(feature = "html")]
-pub use crate::components::{GoBackButton, GoForwardButton, HistoryButtonProps, Link, LinkProps};
-pub use crate::components::{Outlet, Router, RouterProps};
+pub use crate::components::{
+    GoBackButton, GoForwardButton, HistoryButtonProps, Link, LinkPrefetch, LinkProps,
+};
+pub use crate::components::{Outlet, OutletProps, Router, RouterProps};
 pub use crate::contexts::*;
+pub use crate::guard::{GuardOutcome, RedirectKind};
 pub use crate::hooks::*;
+pub use crate::metadata::{Breadcrumb, RouteMetadata};
 pub use crate::navigation::*;
 pub use crate::routable::*;
 pub use crate::router_cfg::RouterConfig;
+pub use crate::sitemap::*;
 pub use dioxus_router_macro::Routable;
 
 #[doc(hidden)]
@@ -92,6 +121,7 @@ mod utils {
 
 #[doc(hidden)]
 pub mod exports {
+    pub use crate::contexts::loader::assert_distinct_loader_outputs;
     pub use crate::query_sets::*;
     pub use percent_encoding;
 }
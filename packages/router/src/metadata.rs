@@ -0,0 +1,160 @@
+//! Titles and head metadata attached to routes.
+//!
+//! Set them with the `title` and `meta` options of the `#[route]` attribute. The strings can use
+//! the fields of the route like `format!`:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! # #[component]
+//! # fn Blog() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Post(id: usize) -> Element { VNode::empty() }
+//! #[derive(Clone, Debug, PartialEq, Routable)]
+//! enum Route {
+//!     #[route("/blog", title = "Blog", meta(description = "Everything we wrote"))]
+//!     Blog {},
+//!     #[route("/blog/:id", title = "Post {id}", meta("og:type" = "article"))]
+//!     Post { id: usize },
+//! }
+//!
+//! let metadata = Route::Post { id: 1 }.metadata();
+//! assert_eq!(metadata.title.as_deref(), Some("Post 1"));
+//! assert_eq!(metadata.get("og:type"), Some("article"));
+//! ```
+//!
+//! The [`crate::components::Router`] keeps the title of the document and the `<meta>` tags in the
+//! head in sync with the current route. [`crate::hooks::use_breadcrumbs`] lists the titles of the
+//! current route and its parents.
+
+use crate::routable::Routable;
+
+/// The title and head metadata of a single route.
+///
+/// This is generated from the `title` and `meta` options of the `#[route]` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteMetadata {
+    /// The title of the page.
+    pub title: Option<String>,
+    /// The `<meta>` tags of the page as pairs of the key and the content. Keys that contain a `:`,
+    /// like `og:title`, are written to the `property` attribute and other keys to the `name`
+    /// attribute.
+    pub meta: Vec<(&'static str, String)>,
+}
+
+impl RouteMetadata {
+    /// Get the content of the `<meta>` tag with the key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.meta
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, content)| content.as_str())
+    }
+
+    /// Check if the route doesn't have a title or any metadata.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.meta.is_empty()
+    }
+}
+
+/// A route with a title in the parent chain of the current route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breadcrumb<R> {
+    /// The route to link to.
+    pub route: R,
+    /// The title of the route.
+    pub title: String,
+}
+
+/// Get the breadcrumbs of a route, starting from the outermost parent and ending with the route
+/// itself.
+///
+/// Every parent path of the route that parses is checked like [`Routable::parent`]. Routes without
+/// a title are left out.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::metadata::breadcrumbs;
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Post(id: usize) -> Element { VNode::empty() }
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/", title = "Home")]
+///     Home {},
+///     #[route("/blog/:id", title = "Post {id}")]
+///     Post { id: usize },
+/// }
+///
+/// let titles: Vec<_> = breadcrumbs(&Route::Post { id: 7 })
+///     .into_iter()
+///     .map(|crumb| crumb.title)
+///     .collect();
+/// assert_eq!(titles, ["Home", "Post 7"]);
+/// ```
+pub fn breadcrumbs<R: Routable>(route: &R) -> Vec<Breadcrumb<R>> {
+    let as_str = route.to_string();
+    let (route_and_query, _) = as_str.split_once('#').unwrap_or((&as_str, ""));
+    let (path, _) = route_and_query
+        .split_once('?')
+        .unwrap_or((route_and_query, ""));
+    let path = path.trim_end_matches('/');
+
+    // Every parent path from the root to the route itself. The route is parsed from its full
+    // string so the query and hash stay the same
+    let mut routes = vec![R::from_str("/").ok()];
+    routes.extend(
+        path.match_indices('/')
+            .skip(1)
+            .map(|(index, _)| R::from_str(&path[..index]).ok()),
+    );
+    if !path.is_empty() {
+        routes.push(Some(route.clone()));
+    }
+
+    let mut crumbs: Vec<Breadcrumb<R>> = Vec::new();
+    for route in routes.into_iter().flatten() {
+        let Some(title) = route.metadata().title else {
+            continue;
+        };
+        // Parent paths can parse into the same route, like a nest without an index route
+        if crumbs
+            .last()
+            .is_some_and(|last| last.route.to_string() == route.to_string())
+        {
+            continue;
+        }
+        crumbs.push(Breadcrumb { route, title });
+    }
+    crumbs
+}
+
+/// The script that replaces the `<meta>` tags the router created for the previous route
+#[cfg(feature = "html")]
+pub(crate) fn update_meta_script(
+    previous: &[(&'static str, String)],
+    current: &[(&'static str, String)],
+) -> String {
+    let previous: Vec<_> = previous.iter().map(|(key, _)| *key).collect();
+    let previous = serde_json::to_string(&previous).unwrap_or_default();
+    let current = serde_json::to_string(current).unwrap_or_default();
+    format!(
+        r#"(function () {{
+            const attribute = (key) => (key.includes(":") ? "property" : "name");
+            const find = (key) => document.head.querySelector(`meta[${{attribute(key)}}="${{CSS.escape(key)}}"]`);
+            const current = new Map({current});
+            for (const key of {previous}) {{
+                if (!current.has(key)) find(key)?.remove();
+            }}
+            for (const [key, content] of current) {{
+                let meta = find(key);
+                if (!meta) {{
+                    meta = document.createElement("meta");
+                    meta.setAttribute(attribute(key), key);
+                    document.head.appendChild(meta);
+                }}
+                meta.setAttribute("content", content);
+            }}
+        }})();"#
+    )
+}
@@ -24,6 +24,10 @@ impl<R: Routable> From<R> for NavigationTarget {
 }
 
 /// A target for the router to navigate to.
+///
+/// Strings that are relative to the current route, like `..`, `./edit`, `?page=2` or `#section`,
+/// are resolved against the current route when they are converted into a target inside of a
+/// router. This lets reusable components link to pages without knowing where they are mounted.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum NavigationTarget<R = String> {
     /// An internal path that the router can navigate to by itself.
@@ -95,6 +99,21 @@ impl<R: Routable> From<R> for NavigationTarget<R> {
 impl From<&str> for NavigationTarget {
     fn from(value: &str) -> Self {
         match try_router() {
+            // Relative targets always point to a page of the router
+            Some(router) if is_relative(value) => match router.resolve(value) {
+                Ok(target) => target,
+                Err(err) => {
+                    tracing::error!("Failed to resolve the navigation target {value:?}: {err}");
+                    match err {
+                        NavigationTargetResolveError::UnknownRoute(route) => {
+                            NavigationTarget::Internal(route)
+                        }
+                        NavigationTargetResolveError::InvalidUrl(_) => {
+                            NavigationTarget::Internal(value.to_string())
+                        }
+                    }
+                }
+            },
             Some(router) => match router.internal_route(value) {
                 true => NavigationTarget::Internal(value.to_string()),
                 false => NavigationTarget::External(value.to_string()),
@@ -106,13 +125,7 @@ impl From<&str> for NavigationTarget {
 
 impl From<String> for NavigationTarget {
     fn from(value: String) -> Self {
-        match try_router() {
-            Some(router) => match router.internal_route(&value) {
-                true => NavigationTarget::Internal(value),
-                false => NavigationTarget::External(value),
-            },
-            None => NavigationTarget::External(value.to_string()),
-        }
+        value.as_str().into()
     }
 }
 
@@ -134,6 +147,83 @@ impl<R: Routable> Display for NavigationTarget<R> {
     }
 }
 
+/// Check if a navigation target is relative to the current route, like `..`, `./edit`, `?page=2`
+/// or `#section`.
+///
+/// ```rust
+/// # use dioxus_router::navigation::is_relative;
+/// assert!(is_relative("../edit"));
+/// assert!(is_relative("?page=2"));
+/// assert!(!is_relative("/blog"));
+/// assert!(!is_relative("https://dioxuslabs.com"));
+/// ```
+pub fn is_relative(target: &str) -> bool {
+    !target.starts_with('/') && Url::parse(target) == Err(ParseError::RelativeUrlWithoutBase)
+}
+
+/// Resolve a relative navigation target against a route.
+///
+/// The route is treated like a directory, so `./edit` and `edit` point to a child of the route and
+/// `..` points to its parent. A target that only has a query replaces the query and the hash of the
+/// route and a target that only has a hash replaces the hash.
+///
+/// ```rust
+/// # use dioxus_router::navigation::resolve_relative;
+/// assert_eq!(resolve_relative("/blog/5", "./edit").unwrap(), "/blog/5/edit");
+/// assert_eq!(resolve_relative("/blog/5", "..").unwrap(), "/blog");
+/// assert_eq!(resolve_relative("/blog/5", "../6").unwrap(), "/blog/6");
+/// assert_eq!(resolve_relative("/blog?page=1", "?page=2").unwrap(), "/blog?page=2");
+/// assert_eq!(resolve_relative("/blog?page=1", "#comments").unwrap(), "/blog?page=1#comments");
+/// assert_eq!(resolve_relative("/blog", "../..").unwrap(), "/");
+/// ```
+pub fn resolve_relative(route: &str, target: &str) -> Result<String, ParseError> {
+    // Resolve the route against a placeholder origin with the url crate
+    let origin = Url::parse("http://router.invalid")?;
+    let split = route.find(['?', '#']).unwrap_or(route.len());
+    let (path, rest) = route.split_at(split);
+    let base = origin.join(&format!("{}/{rest}", path.trim_end_matches('/')))?;
+    let resolved = base.join(target)?;
+
+    let mut resolved_route = match resolved.path().trim_end_matches('/') {
+        "" => "/".to_string(),
+        path => path.to_string(),
+    };
+    if let Some(query) = resolved.query() {
+        resolved_route.push('?');
+        resolved_route.push_str(query);
+    }
+    if let Some(fragment) = resolved.fragment() {
+        resolved_route.push('#');
+        resolved_route.push_str(fragment);
+    }
+    Ok(resolved_route)
+}
+
+/// An error that can occur when resolving a navigation target against the current route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavigationTargetResolveError {
+    /// The target is not a valid URL.
+    InvalidUrl(ParseError),
+    /// The target points to a page of the router that doesn't match any route.
+    UnknownRoute(String),
+}
+
+impl Display for NavigationTargetResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavigationTargetResolveError::InvalidUrl(e) => write!(f, "Invalid URL: {e}"),
+            NavigationTargetResolveError::UnknownRoute(route) => {
+                write!(
+                    f,
+                    "The route {route:?} doesn't match any route of the router"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NavigationTargetResolveError {}
+
 /// An error that can occur when parsing a [`NavigationTarget`].
 pub enum NavigationTargetParseError<R: Routable> {
     /// A URL that is not valid.
@@ -156,7 +246,19 @@ impl<R: Routable> Debug for NavigationTargetParseError<R> {
 impl<R: Routable> FromStr for NavigationTarget<R> {
     type Err = NavigationTargetParseError<R>;
 
+    /// Parse a navigation target. Relative targets are resolved against the current route if they
+    /// are parsed inside of a router.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_relative(s) {
+            if let Some(router) = try_router() {
+                let route = resolve_relative(&router.full_route_string(), s)
+                    .map_err(NavigationTargetParseError::InvalidUrl)?;
+                return R::from_str(&route)
+                    .map(Self::Internal)
+                    .map_err(NavigationTargetParseError::InvalidInternalURL);
+            }
+        }
+
         match Url::parse(s) {
             Ok(_) => Ok(Self::External(s.to_string())),
             Err(ParseError::RelativeUrlWithoutBase) => {
@@ -168,3 +270,50 @@ impl<R: Routable> FromStr for NavigationTarget<R> {
         }
     }
 }
+
+/// A value attached to a history entry instead of the URL.
+///
+/// The state is serialized as JSON and stays with the entry when the user goes back or forward to
+/// it. Use it for data that shouldn't be shared through the URL, like the page a link was opened
+/// from.
+///
+/// ```rust
+/// # use dioxus_router::navigation::HistoryState;
+/// let state = HistoryState::from(&["search", "rust"]);
+/// assert_eq!(state.json(), r#"["search","rust"]"#);
+/// assert_eq!(state.deserialize::<Vec<String>>(), Some(vec!["search".to_string(), "rust".to_string()]));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HistoryState(pub(crate) String);
+
+impl HistoryState {
+    /// Serialize a value into a [`HistoryState`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can't be serialized as JSON.
+    pub fn new<T: serde::Serialize + ?Sized>(value: &T) -> Self {
+        Self(serde_json::to_string(value).expect("history state can be serialized as JSON"))
+    }
+
+    /// Create a [`HistoryState`] from the JSON a [`dioxus_history::History`] stored.
+    pub fn from_json(json: String) -> Self {
+        Self(json)
+    }
+
+    /// The JSON representation of the state.
+    pub fn json(&self) -> &str {
+        &self.0
+    }
+
+    /// Deserialize the state. Returns [`None`] if the state doesn't match the type.
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_str(&self.0).ok()
+    }
+}
+
+impl<T: serde::Serialize + ?Sized> From<&T> for HistoryState {
+    fn from(value: &T) -> Self {
+        Self::new(value)
+    }
+}
@@ -0,0 +1,421 @@
+//! Deserialize query strings into serde structs.
+//!
+//! Values are parsed the way browsers submit forms: a key can appear once or many times, so a
+//! sequence field accepts both `?tag=a` and `?tag=a&tag=b`. Nested values use brackets like
+//! `?filter[min]=1&filter[max]=5`, and the indexed keys `serde_qs` serializes sequences into, like
+//! `?tag[0]=a&tag[1]=b`, are read back into sequences.
+
+use serde::{
+    de::{self, value::StringDeserializer, DeserializeOwned, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Serialize,
+};
+use std::fmt::Display;
+
+/// Deserialize an entire query string into a serde struct.
+///
+/// If a value can't be deserialized, the error is logged and the value is left out so the rest of the
+/// query is still used. If the query can't be deserialized without it, the default value is used.
+pub(crate) fn deserialize_query<T: DeserializeOwned + Default>(query: &str) -> T {
+    let mut node = Node::parse(query);
+    loop {
+        match T::deserialize(node.clone()) {
+            Ok(value) => return value,
+            Err(err) => {
+                tracing::error!("Failed to deserialize query: {}", err);
+                // Retry without the value that failed
+                if err.path.is_empty() || !node.remove(&err.path) {
+                    return T::default();
+                }
+            }
+        }
+    }
+}
+
+/// Serialize a serde struct into a query string.
+pub(crate) fn serialize_query<T: Serialize>(value: &T) -> String {
+    serde_qs::to_string(value).unwrap_or_else(|err| {
+        tracing::error!("Failed to serialize query: {}", err);
+        String::new()
+    })
+}
+
+/// The values of a query string grouped by their keys
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    /// Every value of a key without nested keys in the order they appear
+    Values(Vec<String>),
+    /// Nested keys in the order they first appear
+    Map(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn value(value: String) -> Node {
+        Node::Values(vec![value])
+    }
+
+    fn parse(query: &str) -> Self {
+        let mut root = Node::Map(Vec::new());
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            let (first, rest) = match key.find('[') {
+                Some(index) => key.split_at(index),
+                None => (&*key, ""),
+            };
+            let mut path = vec![first.to_string()];
+            path.extend(
+                rest.split_terminator(']')
+                    .map(|segment| segment.trim_start_matches('[').to_string()),
+            );
+            // `tag[]` and `tag[0]` are values of `tag`. Indices of nested values like
+            // `items[0][name]` stay keys so each item is kept together
+            if path.len() > 1 && is_index(path.last().unwrap()) {
+                path.pop();
+            }
+            root.insert(&path, value.into_owned());
+        }
+        root
+    }
+
+    fn insert(&mut self, path: &[String], value: String) {
+        let Some((key, rest)) = path.split_first() else {
+            match self {
+                Node::Values(values) => values.push(value),
+                // A key can't have both a value and nested keys, keep the nested keys
+                Node::Map(_) => {}
+            }
+            return;
+        };
+        if let Node::Values(values) = self {
+            if !values.is_empty() {
+                return;
+            }
+            *self = Node::Map(Vec::new());
+        }
+        let Node::Map(entries) = self else {
+            unreachable!()
+        };
+        let index = match entries.iter().position(|(other, _)| other == key) {
+            Some(index) => index,
+            None => {
+                entries.push((key.clone(), Node::Values(Vec::new())));
+                entries.len() - 1
+            }
+        };
+        entries[index].1.insert(rest, value);
+    }
+
+    /// Remove the value at a path. Returns false if the path doesn't exist
+    fn remove(&mut self, path: &[String]) -> bool {
+        let (Some((key, rest)), Node::Map(entries)) = (path.split_first(), self) else {
+            return false;
+        };
+        let Some(index) = entries.iter().position(|(other, _)| other == key) else {
+            return false;
+        };
+        if rest.is_empty() {
+            entries.remove(index);
+            true
+        } else {
+            entries[index].1.remove(rest)
+        }
+    }
+
+    /// The last value of the node, used for fields that only take one value
+    fn into_value(self) -> Result<String, QueryError> {
+        match self {
+            Node::Values(mut values) => Ok(values.pop().unwrap_or_default()),
+            Node::Map(_) => Err(de::Error::custom("expected a value, found nested keys")),
+        }
+    }
+}
+
+fn is_index(segment: &str) -> bool {
+    segment.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// An error while deserializing a query string with the path of the key that failed
+#[derive(Debug)]
+struct QueryError {
+    path: Vec<String>,
+    message: String,
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((first, rest)) = self.path.split_first() else {
+            return f.write_str(&self.message);
+        };
+        write!(f, "{first}")?;
+        for segment in rest {
+            write!(f, "[{segment}]")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl de::Error for QueryError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            path: Vec::new(),
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Deserialize fields that only take one value from the last value of the node
+macro_rules! deserialize_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                Value(self.into_value()?).$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Node {
+    type Error = QueryError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Map(_) => self.deserialize_map(visitor),
+            Node::Values(values) if values.len() > 1 => {
+                visitor.visit_seq(SeqAccess::new(values.into_iter().map(Node::value)))
+            }
+            node => visitor.visit_string(node.into_value()?),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Values(values) => {
+                visitor.visit_seq(SeqAccess::new(values.into_iter().map(Node::value)))
+            }
+            // Indexed nested values like `items[0][name]` in the order of their indices
+            Node::Map(mut entries) => {
+                entries.sort_by_key(|(key, _)| key.parse::<usize>().unwrap_or(usize::MAX));
+                visitor.visit_seq(SeqAccess::new(entries.into_iter().map(|(_, node)| node)))
+            }
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Map(entries) => visitor.visit_map(MapAccess {
+                entries: entries.into_iter(),
+                current: None,
+            }),
+            Node::Values(_) => Err(de::Error::custom("expected nested keys, found a value")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self {
+            Node::Values(values) if values.iter().all(|value| value.is_empty()) => {
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Value(self.into_value()?).deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_identifier
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// A single value of a query string. Numbers and booleans are parsed from the text of the value
+struct Value(String);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(err) => Err(de::Error::custom(format_args!("invalid value {:?}: {err}", self.0))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = QueryError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0.is_empty() {
+            true => visitor.visit_none(),
+            false => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let deserializer: StringDeserializer<QueryError> = self.0.into_deserializer();
+        de::Deserializer::deserialize_enum(deserializer, name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct SeqAccess<I> {
+    nodes: I,
+    index: usize,
+}
+
+impl<I> SeqAccess<I> {
+    fn new(nodes: I) -> Self {
+        Self { nodes, index: 0 }
+    }
+}
+
+impl<'de, I: Iterator<Item = Node>> de::SeqAccess<'de> for SeqAccess<I> {
+    type Error = QueryError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(node) = self.nodes.next() else {
+            return Ok(None);
+        };
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(node).map(Some).map_err(|mut err| {
+            err.path.insert(0, index.to_string());
+            err
+        })
+    }
+}
+
+struct MapAccess<I> {
+    entries: I,
+    current: Option<(String, Node)>,
+}
+
+impl<'de, I: Iterator<Item = (String, Node)>> de::MapAccess<'de> for MapAccess<I> {
+    type Error = QueryError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, node)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let deserializer: StringDeserializer<QueryError> = key.clone().into_deserializer();
+        let key_value = seed.deserialize(deserializer)?;
+        self.current = Some((key, node));
+        Ok(Some(key_value))
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, node) = self
+            .current
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before its key"))?;
+        seed.deserialize(node).map_err(|mut err| {
+            err.path.insert(0, key);
+            err
+        })
+    }
+}
@@ -1,8 +1,12 @@
 #![allow(non_snake_case)]
 //! # Routable
 
+use crate::query::{deserialize_query, serialize_query};
 use dioxus_core::Element;
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
 use std::iter::FlatMap;
+use std::pin::Pin;
 use std::slice::Iter;
 use std::{fmt::Display, str::FromStr};
 
@@ -28,6 +32,38 @@ impl<E: Display> Display for RouteParseError<E> {
 ///
 /// **This trait is automatically implemented for any types that implement `From<&str>`.**
 ///
+/// If a type does not implement `FromQuery` and `Display`, the query segment falls back to serde. Any
+/// type that implements `Deserialize`, `Serialize` and `Default` can be spread into the query. Sequence
+/// fields accept a key once or many times like `?tag=a&tag=b` and nested structs use brackets like
+/// `?range[min]=1&range[max]=5`. Values that fail to deserialize are logged and left out. If the
+/// query can't be deserialized without them, the default value is used.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     // This url will be in the format `/search?text=dioxus&tag=rust&tag=web`
+///     #[route("/search?:..query")]
+///     Search { query: SearchQuery },
+/// }
+///
+/// #[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+/// struct SearchQuery {
+///     #[serde(default)]
+///     text: String,
+///     #[serde(default)]
+///     tag: Vec<String>,
+///     page: Option<usize>,
+/// }
+///
+/// # #[component]
+/// # fn Search(query: SearchQuery) -> Element {
+/// #     unimplemented!()
+/// # }
+/// ```
+///
 /// ```rust
 /// use dioxus::prelude::*;
 ///
@@ -85,6 +121,55 @@ impl<T: for<'a> From<&'a str>> FromQuery for T {
     }
 }
 
+/// The spread query segment `#[route("/?:..query")]` accepts either a type that implements
+/// [`FromQuery`] and [`Display`], or a serde struct that implements `Deserialize`, `Serialize` and
+/// `Default`. The macro calls these methods on `&&SpreadQuery` so that method resolution picks
+/// [`FromQuery`] and [`Display`] first and only falls back to serde if they are not implemented.
+#[doc(hidden)]
+pub struct SpreadQuery<T>(std::marker::PhantomData<T>);
+
+impl<T> SpreadQuery<T> {
+    #[doc(hidden)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait SpreadQueryFromStr<T> {
+    fn parse_query(&self, query: &str, raw_query: &str) -> T;
+
+    fn display_query(&self, value: &T) -> String;
+}
+
+impl<T: FromQuery + Display> SpreadQueryFromStr<T> for &SpreadQuery<T> {
+    fn parse_query(&self, query: &str, _: &str) -> T {
+        T::from_query(query)
+    }
+
+    fn display_query(&self, value: &T) -> String {
+        value.to_string()
+    }
+}
+
+#[doc(hidden)]
+pub trait SpreadQueryFromSerde<T> {
+    fn parse_query(&self, query: &str, raw_query: &str) -> T;
+
+    fn display_query(&self, value: &T) -> String;
+}
+
+impl<T: DeserializeOwned + Serialize + Default> SpreadQueryFromSerde<T> for SpreadQuery<T> {
+    fn parse_query(&self, _: &str, raw_query: &str) -> T {
+        deserialize_query(raw_query)
+    }
+
+    fn display_query(&self, value: &T) -> String {
+        serialize_query(value)
+    }
+}
+
 /// Something that can be created from a query argument. This trait must be implemented for any type that is used as a query argument like `#[route("/?:query")]`.
 ///
 /// **This trait is automatically implemented for any types that implement `FromStr` and `Default`.**
@@ -731,9 +816,123 @@ pub trait Routable: FromStr<Err: Display> + Display + Clone + 'static {
     /// The error that can occur when parsing a route.
     const SITE_MAP: &'static [SiteMapSegment];
 
+    /// The locales every route can be prefixed with.
+    ///
+    /// This is generated from the `#[i18n(locales = ["en", "fr"], default = "en")]` attribute of the
+    /// enum. See [`crate::i18n`] for more details.
+    const LOCALES: Option<crate::i18n::Locales> = None;
+
+    /// The id of the static routes the `#[derive(Routable)]` macro embeds in the binary for the
+    /// sitemap. Parent routers use it to find the routes of their child routers.
+    #[doc(hidden)]
+    const SITEMAP_ID: &'static str = "";
+
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
+    /// Render the component this route fills the named outlet with.
+    ///
+    /// This is generated from the `#[slot("name", Component)]` attributes of the route. Routes that
+    /// don't fill the outlet render nothing.
+    fn render_slot(&self, name: &str) -> Element {
+        _ = name;
+        dioxus_core::VNode::empty()
+    }
+
+    /// Get the named outlet this route renders into when a client side navigation is intercepted.
+    ///
+    /// This is generated from the `intercept` option of the `#[route]` attribute.
+    fn intercept_outlet(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Render only the component of this route, without any of its layouts. This is rendered in
+    /// the [`Self::intercept_outlet`] while the previous page stays visible behind it.
+    fn render_intercepted(&self) -> Element {
+        dioxus_core::VNode::empty()
+    }
+
+    /// Get the data loaders for this route and all of its layouts. The router starts every loader in
+    /// parallel when it navigates to the route.
+    ///
+    /// This is generated from the `loader` option of the `#[route]` and `#[layout]` attributes. Read
+    /// the data with [`crate::hooks::use_loader_data`].
+    fn loaders(&self) -> Vec<crate::RouteLoader> {
+        Vec::new()
+    }
+
+    /// Load the code for this route ahead of time. When the wasm bundle is split, this loads the
+    /// module the route's component lives in. Otherwise, there is nothing to load and the future
+    /// resolves immediately.
+    fn preload(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(std::future::ready(()))
+    }
+
+    /// Get the name of the wasm module this route's component is split into. Only routes marked
+    /// with `#[route("/path", lazy)]` are split into their own module.
+    ///
+    /// The server uses this to preload the module for the current route while the page loads.
+    fn split_module(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Get the sitemap options for this route.
+    ///
+    /// This is generated from the `changefreq`, `priority` and `noindex` options of the `#[route]`
+    /// attribute. See [`crate::sitemap::Sitemap`] for how the options are used.
+    fn sitemap_options(&self) -> crate::sitemap::SitemapOptions {
+        crate::sitemap::SitemapOptions::default()
+    }
+
+    /// Get the title and head metadata of this route.
+    ///
+    /// This is generated from the `title` and `meta` options of the `#[route]` attribute. See
+    /// [`crate::metadata`] for more details.
+    fn metadata(&self) -> crate::metadata::RouteMetadata {
+        crate::metadata::RouteMetadata::default()
+    }
+
+    /// Run the guards of this route and all of its nests and layouts, from the outermost to the
+    /// route itself. The first guard that doesn't allow the route decides the outcome.
+    ///
+    /// This is generated from the `#[guard]` attributes of the enum. See [`crate::guard`] for more
+    /// details.
+    fn guard(&self) -> crate::guard::GuardOutcome<Self> {
+        crate::guard::GuardOutcome::Allow
+    }
+
+    /// Check if any guard of this route, its nests or its layouts runs before the route renders.
+    ///
+    /// The page of a guarded route depends on the visitor, so the server never stores it in the
+    /// incremental cache.
+    fn is_guarded(&self) -> bool {
+        false
+    }
+
+    /// Get the kind of the `#[redirect]` the path matches, if any. Paths that match a route are not
+    /// redirects.
+    ///
+    /// The server uses this to answer requests for redirected paths with a redirect status code.
+    fn redirect_kind(path: &str) -> Option<crate::guard::RedirectKind> {
+        _ = path;
+        None
+    }
+
+    /// Get the http status the server responds with when it renders this route. Routes marked
+    /// `not_found` respond with `404` and the `error` route responds with `500`. Other routes
+    /// respond with the default status.
+    fn http_status(&self) -> Option<u16> {
+        None
+    }
+
+    /// Get the route the [`crate::components::Router`] renders instead of the current route when a
+    /// routed component throws an error.
+    ///
+    /// This is generated from the route marked `error`.
+    fn error_route() -> Option<Self> {
+        None
+    }
+
     /// Checks if this route is a child of the given route.
     ///
     /// # Example
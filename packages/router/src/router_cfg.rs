@@ -29,6 +29,7 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) view_transitions: bool,
 }
 
 #[cfg(not(feature = "html"))]
@@ -37,6 +38,7 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: || VNode::empty(),
             on_update: None,
+            view_transitions: false,
         }
     }
 }
@@ -47,6 +49,7 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: crate::components::FailureExternalNavigation,
             on_update: None,
+            view_transitions: false,
         }
     }
 }
@@ -78,6 +81,24 @@ where
         }
     }
 
+    /// Animate navigations with the [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API).
+    ///
+    /// When enabled, the page is updated inside of `document.startViewTransition` after pushing or
+    /// replacing a route. Name the elements that should transition between pages with the
+    /// `view_transition_name` style attribute. Renderers without view transitions update the page
+    /// without an animation.
+    ///
+    /// Individual [`crate::components::Link`]s can pick a transition type with their
+    /// `view_transition` prop even if this is disabled.
+    ///
+    /// Defaults to `false`.
+    pub fn view_transitions(self, enabled: bool) -> Self {
+        Self {
+            view_transitions: enabled,
+            ..self
+        }
+    }
+
     /// A component to render when an external navigation fails.
     ///
     #[cfg_attr(
@@ -0,0 +1,196 @@
+//! Types for generating a `sitemap.xml` from the routes of an app.
+//!
+//! `#[derive(Routable)]` embeds the static routes of every enum in the binary. `dx build` and
+//! `dx bundle` read them from the client build and write them to `sitemap.xml` next to the client
+//! assets along with the [`SitemapEntry`]s of the `/api/sitemap` server function if the app has one.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::routable::Routable;
+
+/// How often the content of a page is expected to change. This is a hint for search engines.
+///
+/// Set it for a route with `#[route("/path", changefreq = "daily")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeFrequency {
+    /// The page changes every time it is accessed.
+    Always,
+    /// The page changes every hour.
+    Hourly,
+    /// The page changes every day.
+    Daily,
+    /// The page changes every week.
+    Weekly,
+    /// The page changes every month.
+    Monthly,
+    /// The page changes every year.
+    Yearly,
+    /// The page is archived and never changes.
+    Never,
+}
+
+impl Display for ChangeFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        })
+    }
+}
+
+/// The sitemap options of a single route.
+///
+/// This is generated from the `changefreq`, `priority` and `noindex` options of the `#[route]` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SitemapOptions {
+    /// How often the page is expected to change.
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to the other pages of the site, between `0.0` and `1.0`.
+    pub priority: Option<f32>,
+    /// If the page should be left out of the sitemap.
+    pub noindex: bool,
+}
+
+/// A single `<url>` entry in a sitemap.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SitemapEntry {
+    /// The path of the page relative to the root of the site.
+    pub loc: String,
+    /// How often the page is expected to change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to the other pages of the site.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<f32>,
+}
+
+/// Collect the pages of a [`Routable`] enum for the sitemap.
+///
+/// Every static route is included automatically. Routes with dynamic segments can't be enumerated
+/// by the router, so you can list them yourself in [`Sitemap::dynamic_routes`].
+///
+/// # Example
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/", changefreq = "daily", priority = 1.0)]
+///     Home {},
+///     #[route("/blog/:id", changefreq = "monthly")]
+///     Blog { id: usize },
+///     #[route("/admin", noindex)]
+///     Admin {},
+/// }
+///
+/// impl Sitemap for Route {
+///     fn dynamic_routes() -> Vec<Self> {
+///         (0..3).map(|id| Route::Blog { id }).collect()
+///     }
+/// }
+///
+/// assert_eq!(Route::sitemap().len(), 4);
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Blog(id: usize) -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin() -> Element { VNode::empty() }
+/// ```
+///
+/// `dx build` and `dx bundle` include the static routes in `sitemap.xml` automatically. To include
+/// the dynamic routes as well, expose the sitemap from a server function at the `/api/sitemap`
+/// endpoint:
+///
+/// ```rust, ignore
+/// #[server(endpoint = "sitemap")]
+/// async fn sitemap() -> ServerFnResult<Vec<SitemapEntry>> {
+///     Ok(Route::sitemap())
+/// }
+/// ```
+pub trait Sitemap: Routable {
+    /// Get the routes with dynamic segments that should be included in the sitemap.
+    fn dynamic_routes() -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// Get the entries of the sitemap. This includes all static routes and the routes returned from
+    /// [`Sitemap::dynamic_routes`] that are not marked with `noindex`.
+    fn sitemap() -> Vec<SitemapEntry> {
+        Self::static_routes()
+            .into_iter()
+            .chain(Self::dynamic_routes())
+            .filter_map(|route| {
+                let options = route.sitemap_options();
+                if options.noindex {
+                    return None;
+                }
+                Some(SitemapEntry {
+                    loc: sitemap_loc(route.to_string()),
+                    changefreq: options.changefreq,
+                    priority: options.priority,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The root route of a child router or nest is displayed with a trailing slash. Remove it so the
+/// entry matches the static routes `dx build` reads from the binary.
+fn sitemap_loc(mut route: String) -> String {
+    let path_len = route.find(['?', '#']).unwrap_or(route.len());
+    if path_len > 1 && route[..path_len].ends_with('/') {
+        route.remove(path_len - 1);
+    }
+    route
+}
+
+/// Helpers for the `#[derive(Routable)]` macro to embed the static routes of an enum in the binary.
+///
+/// The routes are stored as json after the length of the json as a little endian `u32` in a static
+/// exported with a `__DIOXUS_SITEMAP__` prefix. The json can only be built in a const context from
+/// a list of string parts because the ids of child routers are associated consts.
+#[doc(hidden)]
+pub mod macro_helpers {
+    /// The length of the embedded sitemap section for the given parts
+    pub const fn section_len(parts: &[&str]) -> usize {
+        let mut len = 4;
+        let mut i = 0;
+        while i < parts.len() {
+            len += parts[i].len();
+            i += 1;
+        }
+        len
+    }
+
+    /// Write the length of the json followed by the parts into the sitemap section
+    pub const fn section<const N: usize>(parts: &[&str]) -> [u8; N] {
+        let mut section = [0; N];
+        let len = ((N - 4) as u32).to_le_bytes();
+        let mut offset = 0;
+        while offset < 4 {
+            section[offset] = len[offset];
+            offset += 1;
+        }
+        let mut i = 0;
+        while i < parts.len() {
+            let bytes = parts[i].as_bytes();
+            let mut j = 0;
+            while j < bytes.len() {
+                section[offset] = bytes[j];
+                offset += 1;
+                j += 1;
+            }
+            i += 1;
+        }
+        section
+    }
+}
@@ -162,3 +162,77 @@ fn optional_query_segments_parse() {
         route_without_query_and_other
     );
 }
+
+#[test]
+fn serde_query_structs_parse() {
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/search?:..query")]
+        Search { query: SearchQuery },
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct SearchQuery {
+        #[serde(default)]
+        text: String,
+        #[serde(default)]
+        tag: Vec<String>,
+        page: Option<usize>,
+        range: Option<Range>,
+    }
+
+    #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Range {
+        min: usize,
+        max: usize,
+    }
+
+    #[component]
+    fn Search(query: SearchQuery) -> Element {
+        unimplemented!()
+    }
+
+    // Repeated keys are collected into a sequence
+    let with_tags = Route::from_str("/search?text=hello%20world&tag=a&tag=b").unwrap();
+    assert_eq!(
+        with_tags,
+        Route::Search {
+            query: SearchQuery {
+                text: "hello world".to_string(),
+                tag: vec!["a".to_string(), "b".to_string()],
+                page: None,
+                range: None,
+            }
+        }
+    );
+
+    // Encoded separators stay inside the value
+    let with_group =
+        Route::from_str("/search?text=a%26b&page=2&range[min]=1&range[max]=5").unwrap();
+    assert_eq!(
+        with_group,
+        Route::Search {
+            query: SearchQuery {
+                text: "a&b".to_string(),
+                tag: Vec::new(),
+                page: Some(2),
+                range: Some(Range { min: 1, max: 5 }),
+            }
+        }
+    );
+
+    // The query serializes back into a url that parses into the same route
+    assert_eq!(Route::from_str(&with_tags.to_string()).unwrap(), with_tags);
+    assert_eq!(
+        Route::from_str(&with_group.to_string()).unwrap(),
+        with_group
+    );
+
+    // Invalid queries fall back to the default value
+    assert_eq!(
+        Route::from_str("/search?page=not_a_number").unwrap(),
+        Route::Search {
+            query: SearchQuery::default()
+        }
+    );
+}
@@ -1,4 +1,5 @@
 use dioxus::prelude::*;
+use dioxus_router::{ChangeFrequency, SitemapEntry};
 
 #[test]
 fn with_class() {
@@ -51,3 +52,72 @@ fn with_class() {
         ],
     );
 }
+
+#[test]
+fn sitemap_entries() {
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum ChildRoute {
+        #[route("/", priority = 0.5)]
+        ChildRoot {},
+        #[route("/private", noindex)]
+        Private {},
+    }
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/", changefreq = "daily", priority = 1)]
+        Root {},
+        #[route("/blog/:id", changefreq = "monthly")]
+        Blog { id: usize },
+        #[route("/admin", noindex)]
+        Admin {},
+        #[child("/child")]
+        Nested { child: ChildRoute },
+    }
+
+    impl Sitemap for Route {
+        fn dynamic_routes() -> Vec<Self> {
+            vec![Route::Blog { id: 1 }, Route::Blog { id: 2 }]
+        }
+    }
+
+    #[component]
+    fn Root() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Blog(id: usize) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Admin() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn ChildRoot() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Private() -> Element {
+        unimplemented!()
+    }
+
+    let entry = |loc: &str, changefreq, priority| SitemapEntry {
+        loc: loc.to_string(),
+        changefreq,
+        priority,
+    };
+    assert_eq!(
+        Route::sitemap(),
+        vec![
+            entry("/", Some(ChangeFrequency::Daily), Some(1.0)),
+            entry("/child", None, Some(0.5)),
+            entry("/blog/1", Some(ChangeFrequency::Monthly), None),
+            entry("/blog/2", Some(ChangeFrequency::Monthly), None),
+        ],
+    );
+}
@@ -0,0 +1,125 @@
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{GuardOutcome, RedirectKind};
+use std::rc::Rc;
+
+/// Whether the user of the test app is logged in
+#[derive(Clone, Copy)]
+struct LoggedIn(bool);
+
+fn render_at(at: &str, logged_in: bool) -> (String, Rc<MemoryHistory>) {
+    let history = Rc::new(MemoryHistory::with_initial_path(at));
+    let mut vdom = VirtualDom::new_with_props(app, (history.clone(), LoggedIn(logged_in)));
+    // The guards run before the first render, so the redirect target renders right away
+    vdom.rebuild_in_place();
+    (dioxus_ssr::render(&vdom), history)
+}
+
+#[test]
+fn guards_redirect_blocks() {
+    let (html, history) = render_at("/account/settings", false);
+    assert_eq!(history.current_route(), "/login");
+    assert_eq!(html, "Login");
+}
+
+#[test]
+fn guards_allow_routes() {
+    let (html, history) = render_at("/account/settings", true);
+    assert_eq!(history.current_route(), "/account/settings");
+    assert_eq!(html, "<div>Settings</div>");
+}
+
+#[test]
+fn guards_render_fallbacks() {
+    // The guard of the block runs before the guard of the route
+    let (html, history) = render_at("/account/admin", false);
+    assert_eq!(history.current_route(), "/login");
+    assert_eq!(html, "Login");
+
+    let (html, history) = render_at("/account/admin", true);
+    assert_eq!(history.current_route(), "/account/admin");
+    assert_eq!(html, "Forbidden");
+}
+
+#[test]
+fn guarded_routes_are_known() {
+    assert!(Route::Settings {}.is_guarded());
+    assert!(Route::Admin {}.is_guarded());
+    assert!(!Route::Login {}.is_guarded());
+}
+
+#[test]
+fn redirects_have_a_kind() {
+    assert_eq!(
+        Route::redirect_kind("/old-login"),
+        Some(RedirectKind::Permanent)
+    );
+    assert_eq!(
+        Route::redirect_kind("/profile/settings"),
+        Some(RedirectKind::Temporary)
+    );
+    assert_eq!(Route::redirect_kind("/login"), None);
+    assert_eq!(Route::redirect_kind("/missing"), None);
+}
+
+#[rustfmt::skip]
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[nest("/account")]
+    #[guard(require_login)]
+        #[layout(Account)]
+            #[route("/settings")]
+            Settings {},
+            #[route("/admin")]
+            #[guard(require_admin)]
+            Admin {},
+        #[end_layout]
+    #[end_nest]
+    #[redirect("/old-login", || Route::Login {}, permanent)]
+    #[redirect("/profile/settings", || Route::Settings {})]
+    #[route("/login")]
+    Login {},
+}
+
+fn require_login(_: &Route) -> GuardOutcome<Route> {
+    match consume_context::<LoggedIn>().0 {
+        true => GuardOutcome::Allow,
+        false => GuardOutcome::Redirect(Route::Login {}.into()),
+    }
+}
+
+fn require_admin(_: &Route) -> GuardOutcome<Route> {
+    GuardOutcome::Fallback(rsx! { "Forbidden" })
+}
+
+#[component]
+fn Login() -> Element {
+    rsx! { "Login" }
+}
+
+#[component]
+fn Account() -> Element {
+    rsx! {
+        div { Outlet::<Route> {} }
+    }
+}
+
+#[component]
+fn Settings() -> Element {
+    rsx! { "Settings" }
+}
+
+#[component]
+fn Admin() -> Element {
+    rsx! { "Admin" }
+}
+
+fn app((history, logged_in): (Rc<MemoryHistory>, LoggedIn)) -> Element {
+    use_hook(|| {
+        provide_context(history as Rc<dyn History>);
+        provide_context(logged_in);
+    });
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,104 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{root_router, HistoryState, RouterContext};
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Origin {
+    query: String,
+}
+
+fn prepare() -> (VirtualDom, Rc<MemoryHistory>) {
+    let history = Rc::new(MemoryHistory::default());
+    let mut vdom = VirtualDom::new_with_props(app, history.clone());
+    vdom.rebuild_in_place();
+    (vdom, history)
+}
+
+fn navigate(vdom: &mut VirtualDom, f: impl FnOnce(RouterContext)) {
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| f(root_router().unwrap())));
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+#[test]
+fn state_follows_history_entries() {
+    let (mut vdom, history) = prepare();
+    assert_eq!(dioxus_ssr::render(&vdom), r#"<a href="/post/1">Open</a>"#);
+    assert_eq!(history.current_state(), None);
+
+    navigate(&mut vdom, |router| {
+        router.push_with_state(
+            Route::Post { id: 1 },
+            &Origin {
+                query: "rust".to_string(),
+            },
+        );
+    });
+    assert_eq!(history.current_route(), "/post/1");
+    assert_eq!(
+        history.current_state().as_deref(),
+        Some(r#"{"query":"rust"}"#)
+    );
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<p>Back to the results for rust</p>"
+    );
+
+    // Entries pushed without a state don't have one
+    navigate(&mut vdom, |router| {
+        router.push(Route::Post { id: 2 });
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>Back</p>");
+
+    // Going back restores the state of the entry
+    navigate(&mut vdom, |router| router.go_back());
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<p>Back to the results for rust</p>"
+    );
+
+    // Replacing the entry replaces its state
+    navigate(&mut vdom, |router| {
+        router.replace_with_state(Route::Post { id: 1 }, "search");
+    });
+    assert_eq!(history.current_route(), "/post/1");
+    assert_eq!(router_state::<String>(&mut vdom).as_deref(), Some("search"));
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>Back</p>");
+}
+
+fn router_state<T: serde::de::DeserializeOwned>(vdom: &mut VirtualDom) -> Option<T> {
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().current_state()))
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/post/:id")]
+    Post { id: usize },
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        Link { to: Route::Post { id: 1 }, state: HistoryState::new("search"), "Open" }
+    }
+}
+
+#[component]
+fn Post(id: usize) -> Element {
+    let back = match use_history_state::<Origin>() {
+        Some(origin) => format!("Back to the results for {}", origin.query),
+        None => "Back".to_string(),
+    };
+    rsx! { p { "{back}" } }
+}
+
+fn app(history: Rc<MemoryHistory>) -> Element {
+    use_hook(|| provide_context(history as Rc<dyn History>));
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,74 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::root_router;
+use std::rc::Rc;
+
+fn prepare(path: &str) -> (VirtualDom, Rc<MemoryHistory>) {
+    let history = Rc::new(MemoryHistory::with_initial_path(path));
+    let mut vdom = VirtualDom::new_with_props(app, history.clone());
+    vdom.rebuild_in_place();
+    (vdom, history)
+}
+
+#[test]
+fn routes_without_prefix_use_default_locale() {
+    let (vdom, history) = prepare("/about");
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>en</p><a href=\"/\">Home</a>");
+    assert_eq!(history.current_route(), "/about");
+}
+
+#[test]
+fn links_keep_the_current_locale() {
+    let (vdom, history) = prepare("/fr/about");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<p>fr</p><a href=\"/fr\">Home</a>"
+    );
+    assert_eq!(history.current_route(), "/fr/about");
+}
+
+#[test]
+fn navigation_keeps_the_current_locale() {
+    let (mut vdom, history) = prepare("/fr/about");
+
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().push(Route::Home {})));
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(history.current_route(), "/fr");
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>fr</h1>");
+
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().set_locale("en")));
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(history.current_route(), "/");
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>en</h1>");
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[i18n(locales = ["en", "fr"], default = "en")]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/about")]
+    About {},
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { h1 { {use_locale()} } }
+}
+
+#[component]
+fn About() -> Element {
+    rsx! {
+        p { {use_locale()} }
+        Link { to: Route::Home {}, "Home" }
+    }
+}
+
+fn app(history: Rc<MemoryHistory>) -> Element {
+    // Provide the history in the root component so the test can navigate from outside the router
+    use_hook(|| provide_context(history as Rc<dyn History>));
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,84 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::root_router;
+use std::rc::Rc;
+
+#[test]
+fn intercepted_route_renders_over_previous_page() {
+    let history = Rc::new(MemoryHistory::with_initial_path("/"));
+    let mut vdom = VirtualDom::new_with_props(app, history.clone());
+    vdom.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<main>Gallery</main><dialog></dialog>"
+    );
+
+    // Run the effect that navigates to the photo and then rerender
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(history.current_route(), "/photo/1");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<main>Gallery</main><dialog>Photo 1</dialog>"
+    );
+
+    // Going back closes the modal
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().go_back()));
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<main>Gallery</main><dialog></dialog>"
+    );
+}
+
+#[test]
+fn intercepting_route_renders_as_full_page_on_direct_load() {
+    let history = Rc::new(MemoryHistory::with_initial_path("/photo/2"));
+    let mut vdom = VirtualDom::new_with_props(app, history);
+    vdom.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<main>Photo 2</main><dialog></dialog>"
+    );
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Frame)]
+        #[route("/")]
+        Gallery {},
+        #[route("/photo/:id", intercept = "modal")]
+        Photo { id: usize },
+}
+
+#[component]
+fn Frame() -> Element {
+    rsx! {
+        main { Outlet::<Route> {} }
+        dialog { Outlet::<Route> { name: "modal" } }
+    }
+}
+
+#[component]
+fn Gallery() -> Element {
+    use_effect(|| {
+        if !router().can_go_back() {
+            router().push(Route::Photo { id: 1 });
+        }
+    });
+    rsx! { "Gallery" }
+}
+
+#[component]
+fn Photo(id: usize) -> Element {
+    rsx! { "Photo {id}" }
+}
+
+fn app(history: Rc<MemoryHistory>) -> Element {
+    // Provide the history in the root component so the test can navigate from outside the router
+    use_hook(|| provide_context(history as Rc<dyn History>));
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use std::rc::Rc;
+
+fn render(path: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path });
+    vdom.rebuild_in_place();
+    dioxus_ssr::render(&vdom)
+}
+
+#[test]
+fn lazy_routes_render_without_splitting() {
+    assert_eq!(render(Route::Home {}), "Home");
+    assert_eq!(render(Route::Admin {}), "<h1>Admin</h1>");
+    assert_eq!(render(Route::User { id: 1 }), "<p>User 1</p>");
+}
+
+#[test]
+fn only_lazy_routes_have_split_modules() {
+    assert_eq!(Route::Home {}.split_module(), None);
+
+    let admin = Route::Admin {}.split_module().unwrap();
+    assert!(admin.starts_with("moduleAdmin"));
+
+    // The module only depends on the route variant, not its parameters
+    let user = Route::User { id: 1 }.split_module().unwrap();
+    assert!(user.starts_with("moduleUser"));
+    assert_eq!(Route::User { id: 2 }.split_module(), Some(user));
+}
+
+#[derive(Clone, Routable, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/admin", lazy)]
+    Admin {},
+    #[route("/user/:id", UserPage, lazy)]
+    User { id: usize },
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Admin() -> Element {
+    rsx! { h1 { "Admin" } }
+}
+
+#[component]
+fn UserPage(id: usize) -> Element {
+    rsx! { p { "User {id}" } }
+}
+
+#[component]
+fn App(path: Route) -> Element {
+    rsx! {
+        HistoryProvider {
+            history:  move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            Router::<Route> {}
+        }
+    }
+}
@@ -1,7 +1,12 @@
 use dioxus::prelude::*;
+use dioxus_core::{ElementId, Mutation};
 use dioxus_history::{History, MemoryHistory};
+use dioxus_html::{SerializedHtmlEventConverter, SerializedMouseData};
 use dioxus_router::components::HistoryProvider;
-use std::rc::Rc;
+use dioxus_router::{routable::SiteMapSegment, RouteLoader};
+use std::{
+    any::Any, cell::Cell, fmt, future::Future, pin::Pin, rc::Rc, str::FromStr, time::Duration,
+};
 
 fn prepare<R: Routable>() -> String {
     prepare_at::<R>("/")
@@ -471,3 +476,154 @@ fn with_hash_segment() {
         "<h1>App</h1><a href=\"/#test\" aria-current=\"page\">Link</a><a href=\"/\">Empty</a>"
     );
 }
+
+#[tokio::test]
+async fn prefetch_eager() {
+    let prefetched = prefetch(Some(LinkPrefetch::Eager), Some("mounted")).await;
+    assert!(prefetched.listeners.is_empty());
+    assert_eq!((prefetched.loaded, prefetched.preloaded), (1, 1));
+}
+
+#[tokio::test]
+async fn prefetch_hover() {
+    let prefetched = prefetch(Some(LinkPrefetch::Hover), None).await;
+    assert_eq!(prefetched.listeners, ["mouseenter", "focus"]);
+    assert_eq!((prefetched.loaded, prefetched.preloaded), (0, 0));
+
+    let prefetched = prefetch(Some(LinkPrefetch::Hover), Some("mouseenter")).await;
+    assert_eq!((prefetched.loaded, prefetched.preloaded), (1, 1));
+}
+
+#[tokio::test]
+async fn prefetch_visible() {
+    let prefetched = prefetch(Some(LinkPrefetch::Visible), None).await;
+    assert_eq!(prefetched.listeners, ["visible"]);
+    assert_eq!((prefetched.loaded, prefetched.preloaded), (0, 0));
+}
+
+#[tokio::test]
+async fn prefetch_none() {
+    let prefetched = prefetch(None, Some("mounted")).await;
+    assert!(prefetched.listeners.is_empty());
+    assert_eq!((prefetched.loaded, prefetched.preloaded), (0, 0));
+}
+
+thread_local! {
+    static PREFETCH: Cell<Option<LinkPrefetch>> = const { Cell::new(None) };
+    static LOADED: Cell<usize> = const { Cell::new(0) };
+    static PRELOADED: Cell<usize> = const { Cell::new(0) };
+}
+
+struct Prefetched {
+    /// The listeners of the link other than `click` and `mounted` which every link has
+    listeners: Vec<String>,
+    loaded: usize,
+    preloaded: usize,
+}
+
+/// Render a link to `/target` with the prefetch mode, send the event to the link and count how often
+/// the loader and the split module of the target started
+async fn prefetch(mode: Option<LinkPrefetch>, event: Option<&str>) -> Prefetched {
+    set_event_converter(Box::new(SerializedHtmlEventConverter));
+    PREFETCH.set(mode);
+    LOADED.set(0);
+    PRELOADED.set(0);
+
+    let mut vdom = VirtualDom::new(|| {
+        rsx! {
+            HistoryProvider {
+                history: move |_| Rc::new(MemoryHistory::with_initial_path("/")) as Rc<dyn History>,
+                Router::<PrefetchRoute> {}
+            }
+        }
+    });
+    let listeners: Vec<(String, ElementId)> = vdom
+        .rebuild_to_vec()
+        .edits
+        .into_iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener { name, id } => Some((name, id)),
+            _ => None,
+        })
+        .collect();
+
+    if let Some(event) = event {
+        if let Some((_, id)) = listeners.iter().find(|(name, _)| name == event) {
+            let data = Event::new(
+                Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default()))
+                    as Rc<dyn Any>,
+                true,
+            );
+            vdom.runtime().handle_event(event, data, *id);
+        }
+    }
+    _ = tokio::time::timeout(Duration::from_millis(100), vdom.wait_for_work()).await;
+
+    Prefetched {
+        listeners: listeners
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| name != "click" && name != "mounted")
+            .collect(),
+        loaded: LOADED.get(),
+        preloaded: PRELOADED.get(),
+    }
+}
+
+/// A router with a loader and a split module that count how often they are started
+#[derive(Clone, PartialEq, Debug)]
+enum PrefetchRoute {
+    Home,
+    Target,
+}
+
+impl fmt::Display for PrefetchRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefetchRoute::Home => f.write_str("/"),
+            PrefetchRoute::Target => f.write_str("/target"),
+        }
+    }
+}
+
+impl FromStr for PrefetchRoute {
+    type Err = String;
+
+    fn from_str(route: &str) -> Result<Self, Self::Err> {
+        match route {
+            "/" => Ok(PrefetchRoute::Home),
+            "/target" => Ok(PrefetchRoute::Target),
+            _ => Err(format!("unknown route {route}")),
+        }
+    }
+}
+
+impl Routable for PrefetchRoute {
+    const SITE_MAP: &'static [SiteMapSegment] = &[];
+
+    fn render(&self, level: usize) -> Element {
+        match (level, self) {
+            (0, PrefetchRoute::Home) => rsx! {
+                Link { to: PrefetchRoute::Target, prefetch: PREFETCH.get(), "Target" }
+            },
+            (0, PrefetchRoute::Target) => rsx! { "Target" },
+            _ => VNode::empty(),
+        }
+    }
+
+    fn loaders(&self) -> Vec<RouteLoader> {
+        match self {
+            PrefetchRoute::Target => vec![RouteLoader::new(async {
+                LOADED.set(LOADED.get() + 1);
+            })],
+            PrefetchRoute::Home => Vec::new(),
+        }
+    }
+
+    fn preload(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        if *self == PrefetchRoute::Target {
+            PRELOADED.set(PRELOADED.get() + 1);
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
@@ -0,0 +1,105 @@
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, rc::Rc};
+
+async fn render(path: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path });
+    vdom.rebuild_in_place();
+    vdom.wait_for_suspense().await;
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    dioxus_ssr::render(&vdom)
+}
+
+#[tokio::test]
+async fn loaders_provide_data() {
+    assert_eq!(
+        render(Route::Post { user: 1, id: 2 }).await,
+        "<h1>User 1</h1><p>Post 2 by 1</p>"
+    );
+}
+
+#[tokio::test]
+async fn layout_and_route_loaders_run_in_parallel() {
+    STARTED.with(|started| started.set(0));
+    assert_eq!(
+        render(Route::Post { user: 3, id: 4 }).await,
+        "<h1>User 3</h1><p>Post 4 by 3</p>"
+    );
+    // Both loaders start before either of them finishes
+    assert_eq!(STARTED.with(|started| started.get()), 2);
+}
+
+#[tokio::test]
+async fn routes_without_loaders_render() {
+    assert_eq!(render(Route::Home {}).await, "Home");
+}
+
+thread_local! {
+    static STARTED: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Clone, Routable, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/user/:user")]
+        #[layout(User, loader = load_user)]
+            #[route("/post/:id", loader = load_post)]
+            Post { user: usize, id: usize },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct UserData(String);
+
+#[derive(Clone, Serialize, Deserialize)]
+struct PostData(String);
+
+async fn wait_for_both_loaders() {
+    STARTED.with(|started| started.set(started.get() + 1));
+    while STARTED.with(|started| started.get()) < 2 {
+        tokio::task::yield_now().await;
+    }
+}
+
+async fn load_user(user: usize) -> UserData {
+    wait_for_both_loaders().await;
+    UserData(format!("User {user}"))
+}
+
+async fn load_post(user: usize, id: usize) -> PostData {
+    wait_for_both_loaders().await;
+    PostData(format!("Post {id} by {user}"))
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn User(user: usize) -> Element {
+    let data = use_loader_data::<UserData>()?;
+    rsx! {
+        h1 { "{data.0}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Post(user: usize, id: usize) -> Element {
+    let data = use_loader_data::<PostData>()?;
+    rsx! { p { "{data.0}" } }
+}
+
+#[component]
+fn App(path: Route) -> Element {
+    rsx! {
+        HistoryProvider {
+            history:  move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            Router::<Route> {}
+        }
+    }
+}
@@ -1,5 +1,17 @@
+mod guard;
+mod history_state;
+mod i18n;
+mod intercept;
+mod lazy;
 mod link;
+mod loader;
+mod metadata;
 mod navigation;
 mod outlet;
+mod query_state;
 mod redirect;
+mod relative;
+mod scroll;
+mod status;
+mod view_transition;
 mod without_index;
@@ -0,0 +1,171 @@
+use dioxus::document::{Document, Eval, MetaProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::root_router;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// The attributes of a `<meta>` tag
+type MetaAttributes = Vec<(&'static str, String)>;
+
+/// A document that records the head changes of the router
+#[derive(Clone, Default)]
+struct RecordingDocument {
+    titles: Rc<RefCell<Vec<String>>>,
+    meta: Rc<RefCell<Vec<MetaAttributes>>>,
+    scripts: Rc<RefCell<Vec<String>>>,
+}
+
+impl Document for RecordingDocument {
+    fn eval(&self, js: String) -> Eval {
+        self.scripts.borrow_mut().push(js.clone());
+        NoOpDocument.eval(js)
+    }
+
+    fn set_title(&self, title: String) {
+        self.titles.borrow_mut().push(title);
+    }
+
+    fn create_meta(&self, props: MetaProps) {
+        self.meta.borrow_mut().push(props.attributes());
+    }
+}
+
+async fn flush(vdom: &mut VirtualDom) {
+    vdom.render_immediate(&mut NoOpMutations);
+    _ = tokio::time::timeout(Duration::from_millis(50), vdom.wait_for_work()).await;
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+#[test]
+fn metadata_formats_route_fields() {
+    let metadata = Route::Post { id: 3 }.metadata();
+    assert_eq!(metadata.title.as_deref(), Some("Post 3"));
+    assert_eq!(metadata.get("description"), Some("Post number 3"));
+    assert_eq!(metadata.get("og:type"), Some("article"));
+    assert!(Route::Plain {}.metadata().is_empty());
+
+    // Child routers provide the metadata of their routes
+    let nested = Route::Docs {
+        child: DocsRoute::Page {
+            name: "intro".to_string(),
+        },
+    };
+    assert_eq!(nested.metadata().title.as_deref(), Some("Docs: intro"));
+}
+
+#[test]
+fn breadcrumbs_walk_the_parent_chain() {
+    let crumbs = dioxus_router::metadata::breadcrumbs(&Route::Post { id: 3 });
+    let crumbs: Vec<_> = crumbs
+        .into_iter()
+        .map(|crumb| (crumb.route, crumb.title))
+        .collect();
+    assert_eq!(
+        crumbs,
+        [
+            (Route::Home {}, "Home".to_string()),
+            (Route::Blog {}, "Blog".to_string()),
+            (Route::Post { id: 3 }, "Post 3".to_string()),
+        ]
+    );
+
+    // Routes without a title are left out
+    let crumbs = dioxus_router::metadata::breadcrumbs(&Route::Plain {});
+    assert_eq!(crumbs.len(), 1);
+}
+
+#[tokio::test]
+async fn router_updates_the_head() {
+    let document = RecordingDocument::default();
+    let history = Rc::new(MemoryHistory::with_initial_path("/blog/1"));
+    let mut vdom = VirtualDom::new_with_props(app, (history, document.clone()));
+    vdom.rebuild_in_place();
+    flush(&mut vdom).await;
+    assert_eq!(*document.titles.borrow(), ["Post 1"]);
+    assert_eq!(
+        *document.meta.borrow(),
+        [
+            vec![
+                ("name", "description".to_string()),
+                ("content", "Post number 1".to_string())
+            ],
+            vec![
+                ("property", "og:type".to_string()),
+                ("content", "article".to_string())
+            ],
+        ]
+    );
+
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().push(Route::Blog {})));
+    flush(&mut vdom).await;
+    assert_eq!(*document.titles.borrow(), ["Post 1", "Blog"]);
+    // Later routes update the tags in place instead of creating new ones
+    assert_eq!(document.meta.borrow().len(), 2);
+    let scripts = document.scripts.borrow();
+    let script = scripts
+        .iter()
+        .find(|script| script.contains("new Map("))
+        .expect("the meta tags are updated");
+    assert!(script.contains(r#"new Map([["description","Everything we wrote"]])"#));
+    assert!(script.contains(r#"for (const key of ["description","og:type"])"#));
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/", title = "Home")]
+    Home {},
+    #[route("/blog", title = "Blog", meta(description = "Everything we wrote"))]
+    Blog {},
+    #[route(
+        "/blog/:id",
+        title = "Post {id}",
+        meta(description = "Post number {id}", "og:type" = "article")
+    )]
+    Post { id: usize },
+    #[route("/plain")]
+    Plain {},
+    #[child("/docs")]
+    Docs { child: DocsRoute },
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum DocsRoute {
+    #[route("/:name", title = "Docs: {name}")]
+    Page { name: String },
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Blog() -> Element {
+    rsx! { "Blog" }
+}
+
+#[component]
+fn Post(id: usize) -> Element {
+    rsx! { "Post {id}" }
+}
+
+#[component]
+fn Plain() -> Element {
+    rsx! { "Plain" }
+}
+
+#[component]
+fn Page(name: String) -> Element {
+    rsx! { "{name}" }
+}
+
+fn app((history, document): (Rc<MemoryHistory>, RecordingDocument)) -> Element {
+    use_hook(|| {
+        provide_context(history as Rc<dyn History>);
+        provide_context(Rc::new(document) as Rc<dyn Document>);
+    });
+    rsx! {
+        Router::<Route> {}
+    }
+}
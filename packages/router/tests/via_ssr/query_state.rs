@@ -0,0 +1,113 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+#[test]
+fn query_state_reads_and_replaces_the_url() {
+    let history = Rc::new(MemoryHistory::with_initial_path(
+        "/search?text=dioxus&tag=a&tag=b",
+    ));
+    let mut vdom = VirtualDom::new_with_props(app, history.clone());
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>dioxus</p><p>a b</p><p>1</p>");
+
+    // Run the effect that changes the page, the effect that replaces the url and then rerender
+    // with the new url
+    for _ in 0..3 {
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>dioxus</p><p>a b</p><p>2</p>");
+    assert_eq!(
+        history.current_route(),
+        "/search?text=dioxus&tag[0]=a&tag[1]=b&page=2"
+    );
+    // The state replaces the current entry instead of pushing a new one
+    assert!(!history.can_go_back());
+}
+
+fn parse_query(route: &str) -> SearchQuery {
+    match route.parse::<Route>().unwrap() {
+        Route::Search { query } => query,
+    }
+}
+
+#[test]
+fn single_value_of_a_sequence() {
+    assert_eq!(
+        parse_query("/search?text=hi&tag=a"),
+        SearchQuery {
+            text: "hi".to_string(),
+            tag: vec!["a".to_string()],
+            page: None,
+        }
+    );
+}
+
+#[test]
+fn repeated_values_of_a_sequence() {
+    assert_eq!(
+        parse_query("/search?tag=a&tag=b").tag,
+        vec!["a".to_string(), "b".to_string()]
+    );
+    // The indexed keys the query is serialized with are read back into the sequence
+    assert_eq!(
+        parse_query("/search?tag[0]=a&tag[1]=b").tag,
+        vec!["a".to_string(), "b".to_string()]
+    );
+}
+
+#[test]
+fn invalid_field_keeps_the_other_fields() {
+    assert_eq!(
+        parse_query("/search?text=hello+world&tag=a&page=abc"),
+        SearchQuery {
+            text: "hello world".to_string(),
+            tag: vec!["a".to_string()],
+            page: None,
+        }
+    );
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/search?:..query")]
+    Search { query: SearchQuery },
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tag: Vec<String>,
+    page: Option<usize>,
+}
+
+#[component]
+fn Search(query: SearchQuery) -> Element {
+    let mut query = use_query_state::<SearchQuery>();
+    use_effect(move || {
+        if query.peek().page.is_none() {
+            query.write().page = Some(2);
+        }
+    });
+
+    let query = query.read();
+    rsx! {
+        p { "{query.text}" }
+        p { "{query.tag.join(\" \")}" }
+        p { "{query.page.unwrap_or(1)}" }
+    }
+}
+
+fn app(history: Rc<MemoryHistory>) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| history.clone() as Rc<dyn History>,
+            Router::<Route> {}
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use dioxus::prelude::*;
+use dioxus_core::{current_scope_id, NoOpMutations};
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{navigation::NavigationTargetResolveError, RouterContext};
+use std::{cell::Cell, rc::Rc};
+
+/// The scope of the layout inside of the router
+type FrameScope = Rc<Cell<Option<ScopeId>>>;
+
+fn prepare(at: &str) -> (VirtualDom, Rc<MemoryHistory>, FrameScope) {
+    let history = Rc::new(MemoryHistory::with_initial_path(at));
+    let frame = FrameScope::default();
+    let mut vdom = VirtualDom::new_with_props(app, (history.clone(), frame.clone()));
+    vdom.rebuild_in_place();
+    (vdom, history, frame)
+}
+
+/// Run a closure in a component inside of the router, like an event handler would
+fn in_router<T>(
+    vdom: &mut VirtualDom,
+    frame: &FrameScope,
+    f: impl FnOnce(RouterContext) -> T,
+) -> T {
+    let scope = frame.get().unwrap();
+    let result = vdom.in_runtime(|| scope.in_runtime(|| f(router())));
+    vdom.render_immediate(&mut NoOpMutations);
+    result
+}
+
+#[test]
+fn links_resolve_relative_targets() {
+    let (vdom, _, _) = prepare("/blog/5?tab=info");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        concat!(
+            r#"<a href="/blog/5/edit">Edit</a>"#,
+            r#"<a href="/blog">All posts</a>"#,
+            r#"<a href="/blog/6">Next</a>"#,
+            r#"<a href="/blog/5?tab=comments">Comments</a>"#,
+            r#"<a href="/blog/5?tab=info#top">Top</a>"#,
+        )
+    );
+}
+
+#[test]
+fn navigator_resolves_relative_targets() {
+    let (mut vdom, history, frame) = prepare("/blog/5");
+
+    in_router(&mut vdom, &frame, |_| navigator().push("./edit"));
+    assert_eq!(history.current_route(), "/blog/5/edit");
+
+    in_router(&mut vdom, &frame, |_| navigator().push("../.."));
+    assert_eq!(history.current_route(), "/blog");
+
+    in_router(&mut vdom, &frame, |_| {
+        navigator().replace("./5?tab=comments")
+    });
+    assert_eq!(history.current_route(), "/blog/5?tab=comments");
+}
+
+#[test]
+fn unknown_relative_targets_are_errors() {
+    let (mut vdom, history, frame) = prepare("/blog/5");
+    let start = history.current_route();
+
+    let result = in_router(&mut vdom, &frame, |_| {
+        navigator().try_push("./missing/page")
+    });
+    assert_eq!(
+        result.unwrap_err(),
+        NavigationTargetResolveError::UnknownRoute("/blog/5/missing/page".to_string())
+    );
+    assert_eq!(history.current_route(), start);
+
+    // Typed targets report the parse error of the route
+    let parsed = in_router(&mut vdom, &frame, |_| {
+        "./missing".parse::<NavigationTarget<Route>>()
+    });
+    assert!(parsed.is_err());
+    let parsed = in_router(&mut vdom, &frame, |_| {
+        "..".parse::<NavigationTarget<Route>>()
+    });
+    assert_eq!(parsed.unwrap(), NavigationTarget::Internal(Route::Blog {}));
+
+    // Absolute urls are still external
+    let result = in_router(&mut vdom, &frame, |router| {
+        router.resolve("https://dioxuslabs.com")
+    });
+    assert_eq!(
+        result.unwrap(),
+        NavigationTarget::External("https://dioxuslabs.com".to_string())
+    );
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[layout(Frame)]
+    #[route("/blog")]
+    Blog {},
+    #[route("/blog/:id?:tab")]
+    Post { id: usize, tab: String },
+    #[route("/blog/:id/edit")]
+    Edit { id: usize },
+}
+
+#[component]
+fn Frame() -> Element {
+    let frame = use_context::<FrameScope>();
+    frame.set(Some(current_scope_id().unwrap()));
+    rsx! { Outlet::<Route> {} }
+}
+
+#[component]
+fn Blog() -> Element {
+    rsx! { "Blog" }
+}
+
+#[component]
+fn Post(id: usize, tab: String) -> Element {
+    rsx! {
+        Link { to: "./edit", "Edit" }
+        Link { to: "..", "All posts" }
+        Link { to: "../{id + 1}", "Next" }
+        Link { to: "?tab=comments", "Comments" }
+        Link { to: "#top", "Top" }
+    }
+}
+
+#[component]
+fn Edit(id: usize) -> Element {
+    rsx! { "Edit {id}" }
+}
+
+fn app((history, frame): (Rc<MemoryHistory>, FrameScope)) -> Element {
+    use_hook(|| {
+        provide_context(history as Rc<dyn History>);
+        provide_context(frame);
+    });
+    rsx! {
+        Router::<Route> {}
+    }
+}
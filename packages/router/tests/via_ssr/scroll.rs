@@ -0,0 +1,175 @@
+use dioxus::document::{Document, Eval, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{root_router, RouterContext};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// A document that records the scripts the router runs
+#[derive(Clone, Default)]
+struct RecordingDocument(Rc<RefCell<Vec<String>>>);
+
+impl Document for RecordingDocument {
+    fn eval(&self, js: String) -> Eval {
+        self.0.borrow_mut().push(js.clone());
+        NoOpDocument.eval(js)
+    }
+}
+
+impl RecordingDocument {
+    /// The arguments of the last scroll navigation
+    fn last_navigation(&self) -> Option<String> {
+        let scripts = self.0.borrow();
+        let script = scripts.last()?;
+        let (_, call) = script.rsplit_once("window.__dioxusRouterScroll.navigate(")?;
+        Some(call.trim_end_matches(");").to_string())
+    }
+}
+
+async fn flush(vdom: &mut VirtualDom) {
+    vdom.render_immediate(&mut NoOpMutations);
+    _ = tokio::time::timeout(Duration::from_millis(50), vdom.wait_for_work()).await;
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+fn navigate(vdom: &mut VirtualDom, f: impl FnOnce(RouterContext)) {
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| f(root_router().unwrap())));
+}
+
+#[tokio::test]
+async fn restores_scroll_per_history_entry() {
+    let history = Rc::new(MemoryHistory::with_initial_path("/"));
+    let document = RecordingDocument::default();
+    let mut vdom = VirtualDom::new_with_props(app, (history.clone(), document.clone()));
+    vdom.rebuild_in_place();
+    flush(&mut vdom).await;
+    assert_eq!(
+        document.last_navigation().as_deref(),
+        Some("0, \"reset\", \"\"")
+    );
+
+    // New entries scroll to the hash target
+    navigate(&mut vdom, |router| {
+        router.push(NavigationTarget::Internal("/page#details".to_string()));
+    });
+    flush(&mut vdom).await;
+    assert_eq!(history.current_route(), "/page#details");
+    assert_eq!(
+        document.last_navigation().as_deref(),
+        Some("1, \"reset\", \"details\"")
+    );
+
+    // Going back and forward restores the positions of the entries
+    navigate(&mut vdom, |router| router.go_back());
+    flush(&mut vdom).await;
+    assert_eq!(
+        document.last_navigation().as_deref(),
+        Some("0, \"restore\", \"\"")
+    );
+
+    navigate(&mut vdom, |router| router.go_forward());
+    flush(&mut vdom).await;
+    assert_eq!(
+        document.last_navigation().as_deref(),
+        Some("1, \"restore\", \"details\"")
+    );
+
+    // Replacing an entry starts it over
+    navigate(&mut vdom, |router| {
+        router.replace(Route::Home {});
+    });
+    flush(&mut vdom).await;
+    assert_eq!(
+        document.last_navigation().as_deref(),
+        Some("2, \"reset\", \"\"")
+    );
+    let scripts = document.0.borrow().len();
+
+    // Rendering the same entry again doesn't scroll
+    flush(&mut vdom).await;
+    assert_eq!(document.0.borrow().len(), scripts);
+}
+
+/// A history that records if the router disabled its scroll restoration
+#[derive(Default)]
+struct ScrollRestoringHistory {
+    history: MemoryHistory,
+    disabled: Cell<bool>,
+}
+
+impl History for ScrollRestoringHistory {
+    fn current_route(&self) -> String {
+        self.history.current_route()
+    }
+
+    fn go_back(&self) {
+        self.history.go_back()
+    }
+
+    fn go_forward(&self) {
+        self.history.go_forward()
+    }
+
+    fn push(&self, route: String) {
+        self.history.push(route)
+    }
+
+    fn replace(&self, path: String) {
+        self.history.replace(path)
+    }
+
+    fn disable_scroll_restoration(&self) {
+        self.disabled.set(true);
+    }
+}
+
+#[tokio::test]
+async fn takes_over_scroll_restoration_from_the_history() {
+    let history = Rc::new(ScrollRestoringHistory::default());
+    let mut vdom = VirtualDom::new_with_props(
+        |history: Rc<ScrollRestoringHistory>| {
+            use_hook(|| {
+                provide_context(history as Rc<dyn History>);
+                provide_context(Rc::new(RecordingDocument::default()) as Rc<dyn Document>);
+            });
+            rsx! {
+                Router::<Route> {}
+            }
+        },
+        history.clone(),
+    );
+    vdom.rebuild_in_place();
+    assert!(history.disabled.get());
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/page")]
+    Page {},
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { h1 { "Home" } }
+}
+
+#[component]
+fn Page() -> Element {
+    rsx! { h1 { id: "details", "Page" } }
+}
+
+fn app((history, document): (Rc<MemoryHistory>, RecordingDocument)) -> Element {
+    use_hook(|| {
+        provide_context(history as Rc<dyn History>);
+        provide_context(Rc::new(document) as Rc<dyn Document>);
+    });
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,100 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use std::rc::Rc;
+
+fn prepare(at: &str) -> (VirtualDom, Rc<MemoryHistory>) {
+    let history = Rc::new(MemoryHistory::with_initial_path(at));
+    let mut vdom = VirtualDom::new_with_props(app, history.clone());
+    vdom.rebuild_in_place();
+    // Render again after a routed component threw an error
+    vdom.render_immediate(&mut NoOpMutations);
+    (vdom, history)
+}
+
+#[test]
+fn unknown_paths_render_the_not_found_route() {
+    let (vdom, _) = prepare("/missing/page");
+    assert_eq!(dioxus_ssr::render(&vdom), "Nothing at /missing/page");
+    assert_eq!(
+        Route::NotFound {
+            segments: vec!["missing".to_string()]
+        }
+        .http_status(),
+        Some(404)
+    );
+    assert_eq!(Route::Home {}.http_status(), None);
+}
+
+#[test]
+fn failing_routes_render_the_error_route() {
+    let (mut vdom, _) = prepare("/broken");
+    assert_eq!(dioxus_ssr::render(&vdom), "Something went wrong");
+    assert_eq!(Route::error_route(), Some(Route::ServerError {}));
+
+    // The error is cleared when the router navigates away
+    vdom.in_runtime(|| {
+        ScopeId::APP.in_runtime(|| {
+            dioxus_router::root_router().unwrap().push(Route::Home {});
+        })
+    });
+    vdom.render_immediate(&mut NoOpMutations);
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "Home");
+}
+
+#[test]
+#[cfg(feature = "streaming")]
+fn status_routes_set_the_http_status() {
+    use dioxus_fullstack_hooks::HttpStatusContext;
+
+    for (path, status) in [("/", None), ("/missing", Some(404)), ("/broken", Some(500))] {
+        let history = Rc::new(MemoryHistory::with_initial_path(path));
+        let mut vdom = VirtualDom::new_with_props(app, history);
+        let context = vdom.in_runtime(|| ScopeId::ROOT.in_runtime(HttpStatusContext::new));
+        vdom.provide_root_context(context);
+        vdom.rebuild_in_place();
+        vdom.render_immediate(&mut NoOpMutations);
+        assert_eq!(context.current(), status, "{path}");
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/broken")]
+    Broken {},
+    #[route("/500", error)]
+    ServerError {},
+    #[route("/:..segments", not_found)]
+    NotFound { segments: Vec<String> },
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Broken() -> Element {
+    let count: usize = "not a number".parse()?;
+    rsx! { "{count}" }
+}
+
+#[component]
+fn ServerError() -> Element {
+    rsx! { "Something went wrong" }
+}
+
+#[component]
+fn NotFound(segments: Vec<String>) -> Element {
+    rsx! { "Nothing at /{segments.join(\"/\")}" }
+}
+
+fn app(history: Rc<MemoryHistory>) -> Element {
+    use_hook(|| provide_context(history as Rc<dyn History>));
+    rsx! {
+        Router::<Route> {}
+    }
+}
@@ -0,0 +1,97 @@
+use dioxus::document::{Document, Eval, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{root_router, RouterConfig};
+use std::{cell::RefCell, rc::Rc};
+
+/// A document that records the view transitions the router starts
+#[derive(Clone, Default)]
+struct RecordingDocument(Rc<RefCell<Vec<Vec<String>>>>);
+
+impl Document for RecordingDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn start_view_transition(&self, types: Vec<String>) {
+        self.0.borrow_mut().push(types);
+    }
+}
+
+fn prepare(view_transitions: bool) -> (VirtualDom, RecordingDocument) {
+    let document = RecordingDocument::default();
+    let mut vdom = VirtualDom::new_with_props(
+        app,
+        AppProps {
+            view_transitions,
+            document: document.clone(),
+        },
+    );
+    vdom.rebuild_in_place();
+    (vdom, document)
+}
+
+fn push(vdom: &mut VirtualDom, route: Route) {
+    vdom.in_runtime(|| ScopeId::APP.in_runtime(|| root_router().unwrap().push(route)));
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+#[test]
+fn navigations_start_view_transitions_when_enabled() {
+    let (mut vdom, document) = prepare(true);
+    push(&mut vdom, Route::Page {});
+    assert_eq!(*document.0.borrow(), vec![Vec::<String>::new()]);
+
+    // Navigating to the current route doesn't update the page
+    push(&mut vdom, Route::Page {});
+    assert_eq!(document.0.borrow().len(), 1);
+}
+
+#[test]
+fn navigations_skip_view_transitions_by_default() {
+    let (mut vdom, document) = prepare(false);
+    push(&mut vdom, Route::Page {});
+    assert!(document.0.borrow().is_empty());
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/page")]
+    Page {},
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { h1 { view_transition_name: "title", "Home" } }
+}
+
+#[component]
+fn Page() -> Element {
+    rsx! { h1 { view_transition_name: "title", "Page" } }
+}
+
+#[derive(Props, Clone)]
+struct AppProps {
+    view_transitions: bool,
+    document: RecordingDocument,
+}
+
+impl PartialEq for AppProps {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+fn app(props: AppProps) -> Element {
+    use_hook(|| {
+        provide_context(Rc::new(MemoryHistory::default()) as Rc<dyn History>);
+        provide_context(Rc::new(props.document.clone()) as Rc<dyn Document>);
+    });
+    let view_transitions = props.view_transitions;
+    rsx! {
+        Router::<Route> { config: move || RouterConfig::default().view_transitions(view_transitions) }
+    }
+}
@@ -143,7 +143,12 @@ impl ToTokens for TemplateBody {
                 fn __original_template() -> &'static dioxus_core::internal::HotReloadedTemplate {
                     static __ORIGINAL_TEMPLATE: ::std::sync::OnceLock<dioxus_core::internal::HotReloadedTemplate> = ::std::sync::OnceLock::new();
                     if __ORIGINAL_TEMPLATE.get().is_none() {
-                        _ = __ORIGINAL_TEMPLATE.set(#hot_reload_mapping);
+                        let __template = #hot_reload_mapping;
+                        dioxus_core::internal::register_template_location(
+                            __template.roots,
+                            concat!(file!(), ":", line!(), ":", column!()),
+                        );
+                        _ = __ORIGINAL_TEMPLATE.set(__template);
                     }
                     __ORIGINAL_TEMPLATE.get().unwrap()
                 }
@@ -0,0 +1,72 @@
+//! The expansion of the #[macro@crate::island] macro
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, FnArg, ItemFn, Pat, Type};
+
+pub(crate) fn expand_island(args: TokenStream, body: TokenStream) -> syn::Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(syn::Error::new(
+            args.span(),
+            "the island macro does not take any arguments",
+        ));
+    }
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = syn::parse2::<ItemFn>(body)?;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "islands can't be generic because the client needs to know the type of their props",
+        ));
+    }
+
+    // Every argument is serialized on the server and deserialized on the client
+    let mut idents: Vec<Ident> = Vec::new();
+    let mut tys: Vec<Type> = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(pat_type) = input else {
+            return Err(syn::Error::new(
+                input.span(),
+                "islands can't take a self argument",
+            ));
+        };
+        let Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+            return Err(syn::Error::new(
+                pat_type.pat.span(),
+                "the arguments of an island must be identifiers",
+            ));
+        };
+        idents.push(pat_ident.ident.clone());
+        tys.push((*pat_type.ty).clone());
+    }
+
+    let fn_ident = &sig.ident;
+    let name = quote! { concat!(module_path!(), "::", stringify!(#fn_ident)) };
+    let build_virtual_dom = if idents.is_empty() {
+        quote! { dioxus::prelude::VirtualDom::new(#fn_ident) }
+    } else {
+        let props_ident = format_ident!("{fn_ident}Props");
+        quote! { dioxus::prelude::VirtualDom::new_with_props(#fn_ident, #props_ident { #(#idents),* }) }
+    };
+
+    Ok(quote! {
+        #[dioxus::prelude::component]
+        #(#attrs)*
+        #vis #sig {
+            dioxus_fullstack::islands::use_island(#name, || (#(&#idents,)*));
+            #block
+        }
+
+        dioxus_fullstack::islands::inventory::submit! {
+            dioxus_fullstack::islands::IslandRegistration::new(#name, |props| {
+                let (#(#idents,)*): (#(#tys,)*) =
+                    dioxus_fullstack::islands::decode_island_props(props)?;
+                Ok(#build_virtual_dom)
+            })
+        }
+    })
+}
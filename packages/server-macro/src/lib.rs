@@ -6,8 +6,12 @@
 //! See the [server_fn_macro] crate for more information.
 
 use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, TokenTree};
+use quote::{format_ident, quote};
 use server_fn_macro::ServerFnCall;
-use syn::{__private::ToTokens, parse_quote};
+use syn::{__private::ToTokens, parse::Parser, parse_quote, punctuated::Punctuated, Token};
+
+mod island;
 
 /// Declares that a function is a [server function](https://docs.rs/server_fn/).
 /// This means that its body will only run on the server, i.e., when the `ssr`
@@ -54,7 +58,9 @@ use syn::{__private::ToTokens, parse_quote};
 ///       - `Json`: A response encoded as JSON (default). This is ideal for most web applications.
 ///       - `Cbor`: A response encoded in the CBOR format for efficient, binary-encoded data.
 /// - `client`: a custom `Client` implementation that will be used for this server function. This allows
-///   customization of the client-side behavior if needed.
+///   customization of the client-side behavior if needed. In the browser, the default client sends the
+///   CSRF token of the page with the request. Custom clients need to send the token themselves if the
+///   server enables CSRF protection.
 /// - `encoding`: (legacy, may be deprecated in future) specifies the encoding, which may be one
 ///   of the following (not case sensitive):
 ///     - `"Url"`: `POST` request with URL-encoded arguments and JSON response
@@ -64,6 +70,8 @@ use syn::{__private::ToTokens, parse_quote};
 /// - `req` and `res`: specify the HTTP request and response types to be used on the server. These
 ///   are typically necessary if you are integrating with a custom server framework (other than Actix/Axum).
 ///   Example: `req = SomeRequestType`, `res = SomeResponseType`.
+/// - `middleware`: a list of tower layers that wrap the handler of this server function only.
+///   Example: `middleware = [TimeoutLayer::new(Duration::from_secs(5))]`.
 ///
 /// ## Advanced Usage of `input` and `output` Fields
 ///
@@ -160,6 +168,42 @@ use syn::{__private::ToTokens, parse_quote};
 /// }
 /// ```
 ///
+/// Or list the layers in the `middleware` argument. This is the same as writing one `#[middleware]`
+/// attribute for each layer in the order they are listed:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// #[server(middleware = [
+///     tower_http::timeout::TimeoutLayer::new(Duration::from_secs(5)),
+///     tower_http::trace::TraceLayer::new_for_http(),
+/// ])]
+/// pub async fn my_wacky_server_fn(input: Vec<String>) -> ServerFnResult<usize> {
+///     unimplemented!()
+/// }
+/// ```
+///
+/// Layers run inside of the server context of the request. You can create a layer that reads the
+/// [`DioxusServerContext`](https://docs.rs/dioxus-server/latest/dioxus_server/context/struct.DioxusServerContext.html)
+/// and returns a typed `ServerFnError` before the server function runs with `middleware_fn`:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// use dioxus::fullstack::{middleware::middleware_fn, DioxusServerContext};
+///
+/// async fn require_login(context: DioxusServerContext) -> Result<(), ServerFnError> {
+///     match context.request_parts().headers.contains_key("authorization") {
+///         true => Ok(()),
+///         false => Err(ServerFnError::new("You must be logged in")),
+///     }
+/// }
+///
+/// #[server(middleware = [middleware_fn(require_login)])]
+/// pub async fn delete_account() -> ServerFnResult<()> {
+///     unimplemented!()
+/// }
+/// ```
+///
 /// ## Extracting additional data from requests
 ///
 /// Server functions automatically handle serialization and deserialization of arguments and responses.
@@ -205,19 +249,137 @@ use syn::{__private::ToTokens, parse_quote};
 /// ```
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, body: TokenStream) -> TokenStream {
+    // Turn the `middleware = [...]` argument into `#[middleware]` attributes on the function
+    let (args, body) = match extract_middleware(args.into(), body.into()) {
+        Ok(split) => split,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
     // If there is no input codec, use json as the default
-    let parsed = match ServerFnCall::parse("/api", args.into(), body.into()) {
+    let mut parsed = match ServerFnCall::parse("/api", args, body) {
         Ok(parsed) => parsed,
         Err(e) => return e.to_compile_error().into(),
     };
 
-    parsed
+    parsed = parsed
         .default_protocol(Some(
             parse_quote!(server_fn::Http<server_fn::codec::Json, server_fn::codec::Json>),
         ))
         .default_input_encoding(Some(parse_quote!(server_fn::codec::Json)))
         .default_output_encoding(Some(parse_quote!(server_fn::codec::Json)))
-        .default_server_fn_path(Some(parse_quote!(server_fn)))
-        .to_token_stream()
-        .into()
+        .default_server_fn_path(Some(parse_quote!(server_fn)));
+
+    // In the browser, use the client that sends the CSRF token of the page with the request. Only
+    // `dioxus_fullstack` knows if it is built for the browser, so it picks the client
+    let client = parsed.get_args().client.is_none().then(|| {
+        let client = format_ident!("__{}Client", parsed.struct_name());
+        let default_client = parsed.client_type();
+        parsed.get_args_mut().client = Some(parse_quote!(#client));
+        quote! {
+            #[doc(hidden)]
+            type #client = dioxus_fullstack::DefaultClient<#default_client>;
+        }
+    });
+
+    let server_fn = parsed.to_token_stream();
+    quote! {
+        #client
+        #server_fn
+    }
+    .into()
+}
+
+/// Declares that a component is an island. When islands are enabled with
+/// [`ServeConfigBuilder::enable_islands`](https://docs.rs/dioxus-server/latest/dioxus_server/struct.ServeConfigBuilder.html#method.enable_islands),
+/// the page is rendered to static html and only islands are hydrated on the client.
+///
+/// The island macro works like `#[component]`, but every argument is serialized on the server and sent to the
+/// client with the island, so arguments must implement `Serialize` and `DeserializeOwned`. Islands can't be
+/// generic and can't take children.
+///
+/// ## Usage
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// // Only the counter is interactive, the rest of the page is static html
+/// #[component]
+/// fn Article() -> Element {
+///     rsx! {
+///         h1 { "A long article" }
+///         p { "..." }
+///         Counter { initial: 10 }
+///     }
+/// }
+///
+/// #[island]
+/// fn Counter(initial: i32) -> Element {
+///     let mut count = use_signal(|| initial);
+///     rsx! {
+///         button { onclick: move |_| count += 1, "Liked {count} times" }
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn island(args: proc_macro::TokenStream, body: TokenStream) -> TokenStream {
+    match island::expand_island(args.into(), body.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Remove the `middleware = [...]` argument from the arguments of the server macro and add each layer
+/// to the body as a `#[middleware]` attribute. The layers are added before any `#[middleware]` attributes
+/// on the function so they are applied in the order they are listed.
+fn extract_middleware(
+    args: proc_macro2::TokenStream,
+    body: proc_macro2::TokenStream,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let mut remaining = proc_macro2::TokenStream::new();
+    let mut layers = Vec::new();
+
+    // Split the arguments on top level commas. Commas inside of groups stay in the group
+    let mut arguments = vec![Vec::new()];
+    for token in args {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => arguments.push(Vec::new()),
+            _ => arguments.last_mut().unwrap().push(token),
+        }
+    }
+
+    for argument in arguments
+        .into_iter()
+        .filter(|argument| !argument.is_empty())
+    {
+        match argument.as_slice() {
+            [TokenTree::Ident(ident), TokenTree::Punct(eq), value, ..]
+                if ident == "middleware" && eq.as_char() == '=' =>
+            {
+                let list = match (value, argument.len()) {
+                    (TokenTree::Group(group), 3) if group.delimiter() == Delimiter::Bracket => {
+                        group.stream()
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "expected a list of layers like `middleware = [TimeoutLayer::new(duration)]`",
+                        ))
+                    }
+                };
+                let parsed = Punctuated::<syn::Expr, Token![,]>::parse_terminated.parse2(list)?;
+                layers.extend(parsed);
+            }
+            _ => {
+                if !remaining.is_empty() {
+                    remaining.extend(quote! { , });
+                }
+                remaining.extend(argument);
+            }
+        }
+    }
+
+    let body = quote! {
+        #(#[middleware(#layers)])*
+        #body
+    };
+
+    Ok((remaining, body))
 }
@@ -0,0 +1,215 @@
+//! On the fly compression for server rendered pages. See
+//! [`ServeConfigBuilder::enable_compression`](crate::ServeConfigBuilder::enable_compression).
+
+use async_compression::{
+    futures::write::{BrotliEncoder, GzipEncoder, ZstdEncoder},
+    Level,
+};
+use axum::body::Body;
+use bytes::Bytes;
+use futures_util::{AsyncWriteExt, StreamExt};
+use http::{header, HeaderMap, HeaderValue, Response, StatusCode};
+
+/// An encoding the server can compress pages with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Zstd,
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    /// The encodings the server supports, from most to least preferred
+    const SUPPORTED: [Self; 3] = [Self::Zstd, Self::Brotli, Self::Gzip];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    /// Pick the encoding with the highest quality in the `Accept-Encoding` header of a request
+    pub(crate) fn negotiate(headers: &HeaderMap) -> Option<Self> {
+        let accepted: Vec<(&str, f32)> = headers
+            .get_all(header::ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|encoding| {
+                let mut parts = encoding.split(';').map(str::trim);
+                let name = parts.next().filter(|name| !name.is_empty())?;
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.parse().ok())?;
+                Some((name, quality))
+            })
+            .collect();
+        let quality = |encoding: Self| {
+            let find = |name: &str| {
+                accepted
+                    .iter()
+                    .find(|(accepted, _)| accepted.eq_ignore_ascii_case(name))
+                    .map(|(_, quality)| *quality)
+            };
+            find(encoding.name()).or_else(|| find("*")).unwrap_or(0.0)
+        };
+
+        // Ties go to the encoding the server prefers
+        let mut best: Option<(Self, f32)> = None;
+        for encoding in Self::SUPPORTED {
+            let quality = quality(encoding);
+            let better = match best {
+                Some((_, best)) => quality > best,
+                None => quality > 0.0,
+            };
+            if better {
+                best = Some((encoding, quality));
+            }
+        }
+        best.map(|(encoding, _)| encoding)
+    }
+}
+
+/// A compressor that flushes after every chunk so streamed chunks are not held back
+enum Encoder {
+    Zstd(ZstdEncoder<Vec<u8>>),
+    Brotli(Box<BrotliEncoder<Vec<u8>>>),
+    Gzip(GzipEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Zstd => Self::Zstd(ZstdEncoder::with_quality(Vec::new(), Level::Default)),
+            // The default brotli quality is too slow to compress pages while they are sent
+            Encoding::Brotli => Self::Brotli(Box::new(BrotliEncoder::with_quality(
+                Vec::new(),
+                Level::Precise(4),
+            ))),
+            Encoding::Gzip => Self::Gzip(GzipEncoder::with_quality(Vec::new(), Level::Default)),
+        }
+    }
+
+    /// Compress a chunk and take the compressed bytes
+    async fn compress(&mut self, chunk: &[u8]) -> std::io::Result<Bytes> {
+        match self {
+            Self::Zstd(encoder) => {
+                encoder.write_all(chunk).await?;
+                encoder.flush().await?;
+                Ok(std::mem::take(encoder.get_mut()).into())
+            }
+            Self::Brotli(encoder) => {
+                encoder.write_all(chunk).await?;
+                encoder.flush().await?;
+                Ok(std::mem::take(encoder.get_mut()).into())
+            }
+            Self::Gzip(encoder) => {
+                encoder.write_all(chunk).await?;
+                encoder.flush().await?;
+                Ok(std::mem::take(encoder.get_mut()).into())
+            }
+        }
+    }
+
+    /// Finish the compressed stream and take the remaining bytes
+    async fn finish(mut self) -> std::io::Result<Bytes> {
+        match &mut self {
+            Self::Zstd(encoder) => encoder.close().await?,
+            Self::Brotli(encoder) => encoder.close().await?,
+            Self::Gzip(encoder) => encoder.close().await?,
+        }
+        Ok(match self {
+            Self::Zstd(encoder) => encoder.into_inner(),
+            Self::Brotli(encoder) => (*encoder).into_inner(),
+            Self::Gzip(encoder) => encoder.into_inner(),
+        }
+        .into())
+    }
+}
+
+/// Compress the body of a response. `204` and `304` responses and responses that are already encoded are not changed.
+pub(crate) fn compress(response: Response<Body>, encoding: Encoding) -> Response<Body> {
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+    if matches!(
+        parts.status,
+        StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED
+    ) || parts.headers.contains_key(header::CONTENT_ENCODING)
+    {
+        return Response::from_parts(parts, body);
+    }
+    parts.headers.insert(
+        header::CONTENT_ENCODING,
+        HeaderValue::from_static(encoding.name()),
+    );
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    let chunks = body.into_data_stream();
+    let compressed =
+        futures_util::stream::unfold(Some((chunks, Encoder::new(encoding))), |state| async move {
+            let (mut chunks, mut encoder) = state?;
+            match chunks.next().await {
+                Some(Ok(chunk)) => {
+                    let compressed = encoder.compress(&chunk).await.map_err(axum::Error::new);
+                    Some((compressed, Some((chunks, encoder))))
+                }
+                Some(Err(err)) => Some((Err(err), None)),
+                None => Some((encoder.finish().await.map_err(axum::Error::new), None)),
+            }
+        });
+    Response::from_parts(parts, Body::from_stream(compressed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> Option<Encoding> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, value.parse().unwrap());
+        Encoding::negotiate(&headers)
+    }
+
+    #[test]
+    fn negotiate_encoding() {
+        assert_eq!(accept("gzip, deflate, br, zstd"), Some(Encoding::Zstd));
+        assert_eq!(accept("gzip, br"), Some(Encoding::Brotli));
+        assert_eq!(accept("gzip;q=1.0, br;q=0.5"), Some(Encoding::Gzip));
+        assert_eq!(accept("zstd;q=0, gzip"), Some(Encoding::Gzip));
+        assert_eq!(accept("*"), Some(Encoding::Zstd));
+        assert_eq!(accept("identity"), None);
+        assert_eq!(Encoding::negotiate(&HeaderMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn compressed_chunks_are_flushed() {
+        let (mut sender, receiver) =
+            futures_channel::mpsc::unbounded::<Result<String, axum::Error>>();
+        let response = compress(Response::new(Body::from_stream(receiver)), Encoding::Gzip);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        let mut body = response.into_body().into_data_stream();
+        let mut decoder = async_compression::futures::write::GzipDecoder::new(Vec::new());
+
+        // The first chunk can be decompressed before the rest of the page is rendered
+        sender.start_send(Ok("<html>".repeat(10))).unwrap();
+        let first = body.next().await.unwrap().unwrap();
+        decoder.write_all(&first).await.unwrap();
+        decoder.flush().await.unwrap();
+        assert_eq!(decoder.get_ref(), "<html>".repeat(10).as_bytes());
+
+        sender.start_send(Ok("</html>".to_string())).unwrap();
+        drop(sender);
+        while let Some(chunk) = body.next().await {
+            decoder.write_all(&chunk.unwrap()).await.unwrap();
+        }
+        decoder.close().await.unwrap();
+        assert_eq!(
+            decoder.into_inner(),
+            ("<html>".repeat(10) + "</html>").as_bytes()
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Configuration for how to serve a Dioxus application
 #![allow(non_snake_case)]
 
+use crate::csrf::CsrfConfig;
+use crate::IncrementalCache;
 use dioxus_core::LaunchConfig;
 use std::any::Any;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
@@ -22,6 +25,13 @@ pub struct ServeConfigBuilder {
     pub(crate) context_providers:
         Vec<Arc<dyn Fn() -> Box<dyn std::any::Any> + Send + Sync + 'static>>,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) route_streaming_modes: Vec<(String, StreamingMode)>,
+    pub(crate) csrf_protection: bool,
+    pub(crate) csrf_exempt: Vec<String>,
+    pub(crate) etags: bool,
+    pub(crate) compression: bool,
+    pub(crate) islands: bool,
+    pub(crate) site_origin: Option<String>,
 }
 
 impl LaunchConfig for ServeConfigBuilder {}
@@ -36,12 +46,22 @@ impl ServeConfigBuilder {
             incremental: None,
             context_providers: Default::default(),
             streaming_mode: StreamingMode::default(),
+            route_streaming_modes: Vec::new(),
+            csrf_protection: dioxus_cli_config::csrf_protection().unwrap_or(false),
+            csrf_exempt: Vec::new(),
+            etags: false,
+            compression: false,
+            islands: false,
+            site_origin: None,
         }
     }
 
     /// Enable incremental static generation. Incremental static generation caches the
     /// rendered html in memory and/or the file system. It can be used to improve performance of heavy routes.
     ///
+    /// Pages can be removed from the cache with the [`IncrementalCache`] handle, either by route or by the tags
+    /// they were rendered with.
+    ///
     /// ```rust, no_run
     /// # fn app() -> Element { todo!() }
     /// use dioxus::prelude::*;
@@ -319,6 +339,28 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Set the streaming mode for the page at `route` and every page under it. This overrides
+    /// [`Self::streaming_mode`] for those pages. If several routes match a page, the longest route wins.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// use dioxus::fullstack::StreamingMode;
+    ///
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder()
+    ///             .streaming_mode(StreamingMode::OutOfOrder)
+    ///             // The docs are read without javascript, so they are streamed in order
+    ///             .route_streaming_mode("/docs", StreamingMode::InOrder)
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn route_streaming_mode(mut self, route: impl Into<String>, mode: StreamingMode) -> Self {
+        self.route_streaming_modes.push((route.into(), mode));
+        self
+    }
+
     /// Enable out of order streaming. This will cause server futures to be resolved out of order and streamed to the client as they resolve.
     ///
     /// It is equivalent to calling `streaming_mode(StreamingMode::OutOfOrder)`
@@ -337,6 +379,183 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Send an `ETag` header with pages that are rendered completely before they are sent. This includes every
+    /// page when streaming is disabled and pages served from the incremental cache. Requests with a matching
+    /// `If-None-Match` header get an empty `304 Not Modified` response.
+    ///
+    /// Pages that are streamed to the client as they render don't get an `ETag`.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_etags()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_etags(mut self) -> Self {
+        self.etags = true;
+        self
+    }
+
+    /// Compress server rendered pages with zstd, brotli or gzip based on the `Accept-Encoding` header of
+    /// the request. Streamed pages are compressed chunk by chunk, so each chunk still reaches the client as
+    /// soon as it is rendered.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_compression()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_compression(mut self) -> Self {
+        self.compression = true;
+        self
+    }
+
+    /// Only hydrate the components marked with [`#[island]`](https://docs.rs/dioxus-fullstack/latest/dioxus_fullstack/attr.island.html). The rest of
+    /// the page is rendered to static html without hydration ids or hydration data. Each island is rendered
+    /// with its serialized props and hydrated on its own on the client.
+    ///
+    /// Islands swap in content without scripts, so [`StreamingMode::OutOfOrder`] streams pages in order
+    /// when islands are enabled.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_islands()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_islands(mut self) -> Self {
+        self.islands = true;
+        self
+    }
+
+    /// Enable cross site request forgery protection for server functions. New projects should enable this.
+    ///
+    /// Server functions accept form encoded `POST` requests, so without CSRF protection a form on another
+    /// site could call them with the cookies of the user. When CSRF protection is enabled, the server stores
+    /// a random token in the `dioxus-csrf-token` cookie and renders it into a `dioxus-csrf-token` meta tag in
+    /// the head of server rendered pages. Server functions called from the web client send the token back
+    /// in the `x-dioxus-csrf-token` header automatically, and requests where the header doesn't match the
+    /// cookie are rejected with a `403 Forbidden` error.
+    ///
+    /// Requests with a safe method like `GET` and requests without any cookies are not checked because they
+    /// can't act on behalf of the user. Clients that send cookies from outside of the browser need to send
+    /// the header themselves or call endpoints that are exempt with [`Self::csrf_exempt`].
+    ///
+    /// Projects created with `dx new` or `dx config init` turn this on with `csrf_protection = true` in the
+    /// `[web.app]` section of their `Dioxus.toml`.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_csrf_protection()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_csrf_protection(mut self) -> Self {
+        self.csrf_protection = true;
+        self
+    }
+
+    /// Skip the CSRF check for the server function mounted at `path`. This is useful for endpoints that are
+    /// called by other services, like webhooks. It has no effect unless CSRF protection is enabled.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// #[server(endpoint = "payment_webhook")]
+    /// async fn payment_webhook(event: String) -> ServerFnResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder()
+    ///             .enable_csrf_protection()
+    ///             .csrf_exempt("/api/payment_webhook")
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn csrf_exempt(mut self, path: impl Into<String>) -> Self {
+        self.csrf_exempt.push(path.into());
+        self
+    }
+
+    /// Set the origin of the site, like `https://example.com`. The router uses it to make the `hreflang` links
+    /// of localized pages absolute. Without an origin, the links are relative to the page.
+    ///
+    /// The origin is not taken from the `Host` header of the request because clients control that header and
+    /// the page may be cached and served to other clients.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().site_origin("https://example.com")
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn site_origin(mut self, origin: impl Into<String>) -> Self {
+        let origin = origin.into();
+        self.site_origin = Some(origin.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Set the key used to sign and encrypt cookies with [`Cookies::signed`](crate::cookies::Cookies::signed)
+    /// and [`Cookies::private`](crate::cookies::Cookies::private). The key should be kept secret and stay the
+    /// same between restarts, otherwise cookies from older sessions can't be read.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// use dioxus_server::cookies::Key;
+    ///
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         let secret = std::env::var("COOKIE_SECRET").unwrap();
+    ///         dioxus_server::ServeConfig::builder().cookie_key(Key::derive_from(secret.as_bytes()))
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn cookie_key(mut self, key: crate::cookies::Key) -> Self {
+        let key = crate::cookies::CookieKey(key);
+        self.context_providers
+            .push(Arc::new(move || Box::new(key.clone())));
+        self
+    }
+
+    /// Set the store that keeps the data of [`Session`](crate::session::Session)s.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// use dioxus_server::session::FileSessionStore;
+    ///
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus_server::ServeConfig::builder().session_store(FileSessionStore::new("sessions"))
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn session_store(mut self, store: impl crate::session::SessionStore) -> Self {
+        let store = crate::session::SharedSessionStore(Arc::new(store));
+        self.context_providers
+            .push(Arc::new(move || Box::new(store.clone())));
+        self
+    }
+
     /// Build the ServeConfig. This may fail if the index.html file is not found.
     ///
     /// ## WASM compatibility
@@ -370,8 +589,16 @@ impl ServeConfigBuilder {
         };
 
         let index = load_index_html(index_html, root_id);
+        let wasm_split_manifest = Arc::new(load_wasm_split_manifest());
+
+        // Share one cache between every renderer and let server functions invalidate it
+        let incremental = self.incremental.map(IncrementalCache::new);
+        let mut context_providers = self.context_providers;
+        if let Some(incremental) = incremental.clone() {
+            context_providers.push(Arc::new(move || Box::new(incremental.clone())));
+        }
         let context_providers = Arc::new(
-            self.context_providers
+            context_providers
                 .into_iter()
                 .map(|f| {
                     Box::new(move || f()) as Box<dyn Fn() -> Box<dyn std::any::Any> + Send + Sync>
@@ -381,9 +608,18 @@ impl ServeConfigBuilder {
 
         Ok(ServeConfig {
             index,
-            incremental: self.incremental,
+            incremental,
             context_providers,
             streaming_mode: self.streaming_mode,
+            route_streaming_modes: Arc::new(self.route_streaming_modes),
+            wasm_split_manifest,
+            csrf: self.csrf_protection.then_some(CsrfConfig {
+                exempt: self.csrf_exempt,
+            }),
+            etags: self.etags,
+            compression: self.compression,
+            islands: self.islands,
+            site_origin: self.site_origin,
         })
     }
 }
@@ -431,6 +667,19 @@ fn load_index_path(path: PathBuf) -> Result<String, UnableToLoadIndex> {
     Ok(contents)
 }
 
+/// Load the assets each wasm-split module needs. The CLI writes this manifest next to the index.html
+/// file when bundle splitting is enabled. If the bundle isn't split, there are no modules to preload.
+fn load_wasm_split_manifest() -> HashMap<String, Vec<String>> {
+    if cfg!(target_family = "wasm") {
+        return HashMap::new();
+    }
+
+    std::fs::read_to_string(public_path().join("__wasm_split_manifest.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
 fn load_index_html(contents: String, root_id: &'static str) -> IndexHtml {
     let (pre_main, post_main) = contents.split_once(&format!("id=\"{root_id}\"")).unwrap_or_else(|| panic!("Failed to find id=\"{root_id}\" in index.html. The id is used to inject the application into the page."));
 
@@ -495,6 +744,16 @@ pub enum StreamingMode {
     /// Out of order streaming is enabled; server futures are resolved out of order and streamed to the client
     /// as they resolve
     OutOfOrder,
+    /// In order streaming is enabled; the page is sent up to the first suspense boundary that is still loading,
+    /// and the rest of the page is sent in document order as the boundaries resolve.
+    ///
+    /// Unlike [`StreamingMode::OutOfOrder`], no scripts are used to swap in resolved content, so the page
+    /// works without javascript and with a strict content security policy. A slow boundary near the top of
+    /// the page holds back everything after it.
+    ///
+    /// If content that was already sent changes after a boundary resolves, the rest of the page falls back to
+    /// out of order streaming.
+    InOrder,
 }
 
 /// Used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
@@ -502,11 +761,25 @@ pub enum StreamingMode {
 #[derive(Clone)]
 pub struct ServeConfig {
     pub(crate) index: IndexHtml,
-    pub(crate) incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
+    pub(crate) incremental: Option<IncrementalCache>,
     // This is used in the axum integration
     #[allow(unused)]
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    /// Streaming modes that override the default for some routes
+    pub(crate) route_streaming_modes: Arc<Vec<(String, StreamingMode)>>,
+    /// The assets each wasm-split module needs, keyed by the module name
+    pub(crate) wasm_split_manifest: Arc<HashMap<String, Vec<String>>>,
+    /// The CSRF settings for server functions, if CSRF protection is enabled
+    pub(crate) csrf: Option<CsrfConfig>,
+    /// Send ETags with pages that are rendered completely before they are sent
+    pub(crate) etags: bool,
+    /// Compress server rendered pages based on the Accept-Encoding header
+    pub(crate) compression: bool,
+    /// Only hydrate the components marked with `#[island]`
+    pub(crate) islands: bool,
+    /// The origin of the site that absolute links start with
+    pub(crate) site_origin: Option<String>,
 }
 
 impl LaunchConfig for ServeConfig {}
@@ -522,4 +795,61 @@ impl ServeConfig {
     pub fn builder() -> ServeConfigBuilder {
         ServeConfigBuilder::new()
     }
+
+    /// Get the incremental render cache, if incremental rendering is enabled. Every clone of the config
+    /// shares the same cache.
+    pub fn incremental_cache(&self) -> Option<IncrementalCache> {
+        self.incremental.clone()
+    }
+
+    /// Get the streaming mode for a route. The longest route set with
+    /// [`ServeConfigBuilder::route_streaming_mode`] that matches wins.
+    pub(crate) fn streaming_mode_for(&self, route: &str) -> StreamingMode {
+        let path = route.split(['?', '#']).next().unwrap_or_default();
+        let mode = self
+            .route_streaming_modes
+            .iter()
+            .filter(|(route, _)| route_contains(route, path))
+            .max_by_key(|(route, _)| route.trim_end_matches('/').len())
+            .map_or(self.streaming_mode, |(_, mode)| *mode);
+        // Only the root virtual dom can swap in resolved suspense boundaries with scripts
+        match mode {
+            StreamingMode::OutOfOrder if self.islands => StreamingMode::InOrder,
+            mode => mode,
+        }
+    }
+}
+
+/// Check if a path is the route or a page under it
+fn route_contains(route: &str, path: &str) -> bool {
+    let route = route.trim_end_matches('/');
+    match path.strip_prefix(route) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+#[test]
+fn streaming_mode_per_route() {
+    let cfg = ServeConfig::builder()
+        .streaming_mode(StreamingMode::OutOfOrder)
+        .route_streaming_mode("/docs", StreamingMode::InOrder)
+        .route_streaming_mode("/docs/live/", StreamingMode::Disabled)
+        .build()
+        .unwrap();
+    let mode = |route| cfg.streaming_mode_for(route);
+    assert!(mode("/") == StreamingMode::OutOfOrder);
+    assert!(mode("/docsearch") == StreamingMode::OutOfOrder);
+    assert!(mode("/docs") == StreamingMode::InOrder);
+    assert!(mode("/docs/intro?page=2") == StreamingMode::InOrder);
+    assert!(mode("/docs/live") == StreamingMode::Disabled);
+    assert!(mode("/docs/live/demo") == StreamingMode::Disabled);
+
+    // Islands can't be streamed out of order
+    let cfg = ServeConfig::builder()
+        .streaming_mode(StreamingMode::OutOfOrder)
+        .enable_islands()
+        .build()
+        .unwrap();
+    assert!(cfg.streaming_mode_for("/") == StreamingMode::InOrder);
 }
@@ -401,8 +401,12 @@ mod server_fn_impl {
                 .is_modified(ResponsePartsModified::Headers)
             {
                 let mut_headers = response.headers_mut();
-                for (key, value) in parts.headers.iter() {
-                    mut_headers.insert(key, value.clone());
+                // Headers like `Set-Cookie` can have multiple values, so replace all values of each header
+                for key in parts.headers.keys() {
+                    mut_headers.remove(key);
+                    for value in parts.headers.get_all(key) {
+                        mut_headers.append(key, value.clone());
+                    }
                 }
             }
             if self
@@ -0,0 +1,353 @@
+//! Typed cookies for server functions and server side rendering.
+//!
+//! [`Cookies`] reads the cookies of the current request and writes `Set-Cookie` headers to the response.
+//! Signed and encrypted cookies use the key set with
+//! [`ServeConfigBuilder::cookie_key`](crate::ServeConfigBuilder::cookie_key):
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus_server::cookies::{Cookie, Cookies};
+//!
+//! #[server]
+//! async fn set_theme(theme: String) -> ServerFnResult {
+//!     let cookies: Cookies = extract().await?;
+//!     cookies.set(Cookie::build(("theme", theme)).path("/").permanent());
+//!     Ok(())
+//! }
+//!
+//! #[server]
+//! async fn visits() -> ServerFnResult<u32> {
+//!     let cookies: Cookies = extract().await?;
+//!     let signed = cookies.signed()?;
+//!     let visits = signed
+//!         .get("visits")
+//!         .and_then(|cookie| cookie.value().parse().ok())
+//!         .unwrap_or(0)
+//!         + 1;
+//!     signed.set(("visits", visits.to_string()));
+//!     Ok(visits)
+//! }
+//! ```
+//!
+//! Cookies set while server side rendering a page are only sent if they are set before the initial chunk of
+//! the response is sent. Pages that use the cookies of the request are sent with `Vary: Cookie` and are
+//! never stored in the incremental cache.
+
+use crate::{DioxusServerContext, FromServerContext};
+use cookie::CookieJar;
+use http::{header, HeaderValue};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+pub use cookie::{Cookie, CookieBuilder, Key, SameSite};
+
+/// The key used to sign and encrypt cookies
+#[derive(Clone)]
+pub(crate) struct CookieKey(pub(crate) Key);
+
+/// The cookies of the current request, shared by every [`Cookies`] handle for the request
+#[derive(Clone)]
+struct SharedCookieJar(Arc<Mutex<CookieJar>>);
+
+/// The cookies of the current request. Changes are sent to the client with `Set-Cookie` headers.
+///
+/// You can get the cookies with [`extract`](crate::extract) in a server function or [`Cookies::from_context`].
+#[derive(Clone)]
+pub struct Cookies {
+    context: DioxusServerContext,
+    jar: Arc<Mutex<CookieJar>>,
+}
+
+impl Cookies {
+    /// Get the cookies of the request the server context belongs to.
+    ///
+    /// If this is called while a page is rendered on the server, the page depends on the cookies of the
+    /// visitor and is sent with `Vary: Cookie`.
+    pub fn from_context(context: &DioxusServerContext) -> Self {
+        dioxus_fullstack_hooks::vary_response("Cookie");
+        let jar = match context.get::<SharedCookieJar>() {
+            Some(SharedCookieJar(jar)) => jar,
+            None => {
+                let mut jar = CookieJar::new();
+                for header in context.request_parts().headers.get_all(header::COOKIE) {
+                    let Ok(header) = header.to_str() else {
+                        continue;
+                    };
+                    for cookie in Cookie::split_parse_encoded(header).flatten() {
+                        jar.add_original(cookie.into_owned());
+                    }
+                }
+                let jar = Arc::new(Mutex::new(jar));
+                context.insert(SharedCookieJar(jar.clone()));
+                jar
+            }
+        };
+        Self {
+            context: context.clone(),
+            jar,
+        }
+    }
+
+    /// Get a cookie by name. This includes cookies set earlier in the same request.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.lock().get(name).cloned()
+    }
+
+    /// Set a cookie on the client.
+    pub fn set(&self, cookie: impl Into<Cookie<'static>>) {
+        let cookie = cookie.into();
+        let name = cookie.name().to_string();
+        self.jar.lock().add(cookie);
+        self.write_header(&name);
+    }
+
+    /// Remove a cookie from the client. The path and domain of the cookie must match the cookie that was set.
+    pub fn remove(&self, cookie: impl Into<Cookie<'static>>) {
+        let cookie = cookie.into();
+        let name = cookie.name().to_string();
+        self.jar.lock().remove(cookie);
+        self.write_header(&name);
+    }
+
+    /// Get the cookies that are signed with the cookie key. Signed cookies can be read by the client, but
+    /// the server rejects them if they were modified.
+    pub fn signed(&self) -> Result<SignedCookies, MissingCookieKey> {
+        Ok(SignedCookies {
+            key: self.key()?,
+            cookies: self.clone(),
+        })
+    }
+
+    /// Get the cookies that are encrypted with the cookie key. Private cookies can't be read or modified
+    /// by the client.
+    pub fn private(&self) -> Result<PrivateCookies, MissingCookieKey> {
+        Ok(PrivateCookies {
+            key: self.key()?,
+            cookies: self.clone(),
+        })
+    }
+
+    fn key(&self) -> Result<Key, MissingCookieKey> {
+        self.context
+            .get::<CookieKey>()
+            .map(|CookieKey(key)| key)
+            .ok_or(MissingCookieKey)
+    }
+
+    /// Replace the `Set-Cookie` header for the cookie with the latest change in the jar. Cookies with the
+    /// same name but a different path or domain are separate cookies on the client and keep their headers.
+    fn write_header(&self, name: &str) {
+        let Some(cookie) = self
+            .jar
+            .lock()
+            .delta()
+            .filter(|cookie| cookie.name() == name)
+            .last()
+            .cloned()
+        else {
+            return;
+        };
+        let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) else {
+            tracing::error!("Cookie {name} is not a valid header value");
+            return;
+        };
+
+        let same_cookie = |other: &Cookie| {
+            other.name() == cookie.name()
+                && other.path() == cookie.path()
+                && other.domain() == cookie.domain()
+        };
+        let mut headers = self.context.headers_mut();
+        let other_cookies: Vec<_> = headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter(|value| {
+                let set_cookie = value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| Cookie::parse_encoded(value).ok());
+                !matches!(set_cookie, Some(set_cookie) if same_cookie(&set_cookie))
+            })
+            .cloned()
+            .collect();
+        headers.remove(header::SET_COOKIE);
+        for value in other_cookies {
+            headers.append(header::SET_COOKIE, value);
+        }
+        headers.append(header::SET_COOKIE, value);
+    }
+}
+
+#[async_trait::async_trait]
+impl FromServerContext for Cookies {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_context(req))
+    }
+}
+
+/// Cookies signed with the cookie key. Created with [`Cookies::signed`].
+pub struct SignedCookies {
+    cookies: Cookies,
+    key: Key,
+}
+
+impl SignedCookies {
+    /// Get a signed cookie by name. Returns `None` if the cookie doesn't exist or the signature is invalid.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.cookies.jar.lock().signed(&self.key).get(name)
+    }
+
+    /// Sign a cookie and set it on the client.
+    pub fn set(&self, cookie: impl Into<Cookie<'static>>) {
+        let cookie = cookie.into();
+        let name = cookie.name().to_string();
+        self.cookies.jar.lock().signed_mut(&self.key).add(cookie);
+        self.cookies.write_header(&name);
+    }
+
+    /// Remove a signed cookie from the client.
+    pub fn remove(&self, cookie: impl Into<Cookie<'static>>) {
+        self.cookies.remove(cookie);
+    }
+}
+
+/// Cookies encrypted with the cookie key. Created with [`Cookies::private`].
+pub struct PrivateCookies {
+    cookies: Cookies,
+    key: Key,
+}
+
+impl PrivateCookies {
+    /// Get and decrypt a private cookie by name. Returns `None` if the cookie doesn't exist or can't be decrypted.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.cookies.jar.lock().private(&self.key).get(name)
+    }
+
+    /// Encrypt a cookie and set it on the client.
+    pub fn set(&self, cookie: impl Into<Cookie<'static>>) {
+        let cookie = cookie.into();
+        let name = cookie.name().to_string();
+        self.cookies.jar.lock().private_mut(&self.key).add(cookie);
+        self.cookies.write_header(&name);
+    }
+
+    /// Remove a private cookie from the client.
+    pub fn remove(&self, cookie: impl Into<Cookie<'static>>) {
+        self.cookies.remove(cookie);
+    }
+}
+
+/// Signed or private cookies were used without setting a key with
+/// [`ServeConfigBuilder::cookie_key`](crate::ServeConfigBuilder::cookie_key)
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[error("No cookie key was set. Set a key with `ServeConfigBuilder::cookie_key` to use signed or private cookies")]
+pub struct MissingCookieKey;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(cookie: Option<&str>) -> DioxusServerContext {
+        let mut request = http::Request::builder();
+        if let Some(cookie) = cookie {
+            request = request.header(header::COOKIE, cookie);
+        }
+        let context = DioxusServerContext::new(request.body(()).unwrap().into_parts().0);
+        context.insert(CookieKey(Key::generate()));
+        context
+    }
+
+    fn set_cookies(context: &DioxusServerContext) -> Vec<String> {
+        let response = context.response_parts();
+        let values = response.headers.get_all(header::SET_COOKIE).iter();
+        values
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn cookies_read_and_write_headers() {
+        let context = context(Some("theme=dark; lang=en"));
+        let cookies = Cookies::from_context(&context);
+        assert_eq!(cookies.get("theme").unwrap().value(), "dark");
+
+        cookies.set(("theme", "light"));
+        cookies.set(("theme", "system"));
+        cookies.remove("lang");
+        assert_eq!(cookies.get("theme").unwrap().value(), "system");
+        assert!(cookies.get("lang").is_none());
+
+        // Every handle for the request shares the same cookies
+        assert_eq!(
+            Cookies::from_context(&context)
+                .get("theme")
+                .unwrap()
+                .value(),
+            "system"
+        );
+
+        let headers = set_cookies(&context);
+        assert_eq!(headers.len(), 2);
+        assert!(headers.contains(&"theme=system".to_string()));
+        assert!(headers.iter().any(|header| header.starts_with("lang=;")));
+    }
+
+    #[test]
+    fn cookies_with_another_path_or_domain_keep_their_headers() {
+        let context = context(None);
+        let cookies = Cookies::from_context(&context);
+        cookies.set(Cookie::build(("id", "root")).path("/"));
+        cookies.set(Cookie::build(("id", "admin")).path("/admin"));
+        cookies.set(Cookie::build(("id", "docs")).domain("docs.example.com"));
+        cookies.set(Cookie::build(("id", "new admin")).path("/admin"));
+
+        let headers = set_cookies(&context);
+        assert_eq!(headers.len(), 3);
+        assert!(headers.contains(&"id=root; Path=/".to_string()));
+        assert!(headers.contains(&"id=new%20admin; Path=/admin".to_string()));
+        assert!(headers.contains(&"id=docs; Domain=docs.example.com".to_string()));
+    }
+
+    #[test]
+    fn signed_and_private_cookies() {
+        let server = context(None);
+        let key = server.get::<CookieKey>().unwrap();
+        let cookies = Cookies::from_context(&server);
+        cookies.signed().unwrap().set(("user", "ada"));
+        cookies.private().unwrap().set(("secret", "hunter2"));
+        let headers = set_cookies(&server);
+        assert!(!headers.iter().any(|header| header.contains("hunter2")));
+
+        // Send the cookies back in the next request
+        let sent: Vec<_> = headers
+            .iter()
+            .map(|header| header.split(';').next().unwrap())
+            .collect();
+        let next = context(Some(&sent.join("; ")));
+        next.insert(key.clone());
+        let cookies = Cookies::from_context(&next);
+        assert_eq!(
+            cookies.signed().unwrap().get("user").unwrap().value(),
+            "ada"
+        );
+        assert_eq!(
+            cookies.private().unwrap().get("secret").unwrap().value(),
+            "hunter2"
+        );
+
+        // Modified cookies are rejected
+        let tampered = sent[0].replace("ada", "eve");
+        let next = context(Some(&tampered));
+        next.insert(key);
+        assert!(Cookies::from_context(&next)
+            .signed()
+            .unwrap()
+            .get("user")
+            .is_none());
+
+        let without_key = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        assert!(Cookies::from_context(&without_key).signed().is_err());
+    }
+}
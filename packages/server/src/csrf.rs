@@ -0,0 +1,134 @@
+//! Cross site request forgery protection for server functions. See
+//! [`ServeConfigBuilder::enable_csrf_protection`](crate::ServeConfigBuilder::enable_csrf_protection)
+//! for how the token is shared with the client.
+
+use base64::Engine;
+use dioxus_fullstack_hooks::{CSRF_COOKIE_NAME, CSRF_HEADER_NAME, CSRF_META_NAME};
+use http::{header, request::Parts, HeaderValue};
+
+use crate::DioxusServerContext;
+
+/// The CSRF settings of a [`ServeConfig`](crate::ServeConfig)
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CsrfConfig {
+    /// The paths of server functions that skip the CSRF check
+    pub(crate) exempt: Vec<String>,
+}
+
+impl CsrfConfig {
+    /// Check if a request to the server function at `path` is allowed
+    pub(crate) fn allows(&self, path: &str, parts: &Parts) -> bool {
+        if parts.method.is_safe() || self.exempt.iter().any(|exempt| exempt == path) {
+            return true;
+        }
+        // Without cookies the request can't use the credentials of the user
+        if !parts.headers.contains_key(header::COOKIE) {
+            return true;
+        }
+        let (Some(cookie), Some(header)) = (
+            request_token(parts),
+            parts
+                .headers
+                .get(CSRF_HEADER_NAME)
+                .and_then(|value| value.to_str().ok()),
+        ) else {
+            return false;
+        };
+        constant_time_eq(cookie.as_bytes(), header.as_bytes())
+    }
+}
+
+/// Get the CSRF token of the request or create a new one and set the cookie on the response
+pub(crate) fn csrf_token(server_context: &DioxusServerContext) -> String {
+    if let Some(token) = request_token(&server_context.request_parts()) {
+        return token;
+    }
+
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).expect("Failed to generate a CSRF token");
+    let token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+    let cookie = format!("{CSRF_COOKIE_NAME}={token}; Path=/; SameSite=Strict");
+    if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+        server_context
+            .headers_mut()
+            .append(header::SET_COOKIE, cookie);
+    }
+    token
+}
+
+/// Render the meta tag the web client reads the CSRF token from
+pub(crate) fn render_meta_tag<R: std::fmt::Write>(to: &mut R, token: &str) -> std::fmt::Result {
+    write!(to, r#"<meta name="{CSRF_META_NAME}" content="{token}">"#)
+}
+
+/// Read the CSRF token from the cookies of a request
+fn request_token(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .find_map(|cookie| {
+            let (name, value) = cookie.trim().split_once('=')?;
+            (name == CSRF_COOKIE_NAME && !value.is_empty()).then(|| value.to_string())
+        })
+}
+
+/// Compare two tokens without leaking how much of the token matched through timing
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+
+    fn parts(method: Method, cookie: Option<&str>, token: Option<&str>) -> Parts {
+        let mut request = http::Request::builder().method(method).uri("/api/f");
+        if let Some(cookie) = cookie {
+            request = request.header(header::COOKIE, cookie);
+        }
+        if let Some(token) = token {
+            request = request.header(CSRF_HEADER_NAME, token);
+        }
+        request.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn csrf_check() {
+        let csrf = CsrfConfig {
+            exempt: vec!["/api/webhook".to_string()],
+        };
+        let cookie = "session=abc; dioxus-csrf-token=secret";
+
+        assert!(csrf.allows("/api/f", &parts(Method::POST, Some(cookie), Some("secret"))));
+        assert!(!csrf.allows("/api/f", &parts(Method::POST, Some(cookie), Some("guess"))));
+        assert!(!csrf.allows("/api/f", &parts(Method::POST, Some(cookie), None)));
+        assert!(!csrf.allows(
+            "/api/f",
+            &parts(Method::POST, Some("session=abc"), Some("secret"))
+        ));
+
+        assert!(csrf.allows("/api/f", &parts(Method::GET, Some(cookie), None)));
+        assert!(csrf.allows("/api/f", &parts(Method::POST, None, None)));
+        assert!(csrf.allows("/api/webhook", &parts(Method::POST, Some(cookie), None)));
+    }
+
+    #[test]
+    fn csrf_token_sets_cookie() {
+        let context = DioxusServerContext::new(parts(Method::GET, None, None));
+        let token = csrf_token(&context);
+        let cookie = context.response_parts().headers[header::SET_COOKIE].clone();
+        assert!(cookie
+            .to_str()
+            .unwrap()
+            .starts_with(&format!("{CSRF_COOKIE_NAME}={token};")));
+
+        let cookie = format!("{CSRF_COOKIE_NAME}={token}");
+        let context = DioxusServerContext::new(parts(Method::GET, Some(&cookie), None));
+        assert_eq!(csrf_token(&context), token);
+        assert!(context.response_parts().headers.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+//! ETags for server rendered pages. See
+//! [`ServeConfigBuilder::enable_etags`](crate::ServeConfigBuilder::enable_etags).
+
+use base64::Engine;
+use http::{header, HeaderMap, HeaderValue};
+use sha2::{Digest, Sha256};
+
+/// Create a weak ETag for a page. The ETag is weak because the same page can be sent with different
+/// compression.
+pub(crate) fn etag(body: &[u8]) -> HeaderValue {
+    let hash = Sha256::digest(body);
+    let hash = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&hash[..16]);
+    HeaderValue::from_str(&format!(r#"W/"{hash}""#)).expect("ETags are valid header values")
+}
+
+/// Check if the `If-None-Match` header of a request matches an ETag
+pub(crate) fn if_none_match(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || weak_eq(tag, etag))
+}
+
+/// Compare two ETags ignoring whether they are weak
+fn weak_eq(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+#[test]
+fn etags_match_if_none_match() {
+    let etag = etag(b"<html></html>");
+    assert_eq!(etag, self::etag(b"<html></html>"));
+    assert_ne!(etag, self::etag(b"<html>changed</html>"));
+
+    let strong = etag.to_str().unwrap().trim_start_matches("W/").to_string();
+    for (header, matches) in [
+        (etag.to_str().unwrap().to_string(), true),
+        (strong, true),
+        (format!(r#""other", {}"#, etag.to_str().unwrap()), true),
+        ("*".to_string(), true),
+        (r#""other""#.to_string(), false),
+    ] {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, header.parse().unwrap());
+        assert_eq!(if_none_match(&headers, &etag), matches, "{header}");
+    }
+    assert!(!if_none_match(&HeaderMap::new(), &etag));
+}
@@ -0,0 +1,199 @@
+//! Invalidate pages in the incremental render cache.
+//!
+//! Pages can be tagged while they render with [`use_cache_tag`](dioxus_fullstack_hooks::use_cache_tag). When the
+//! data behind a page changes, a server function can invalidate every page with the tag:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus_server::IncrementalCache;
+//!
+//! #[server]
+//! async fn update_post(id: usize, content: String) -> ServerFnResult {
+//!     // Save the post...
+//!     let cache: IncrementalCache = extract().await?;
+//!     cache.invalidate_tag(&format!("post:{id}")).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Other services can invalidate pages through the endpoint mounted with
+//! [`DioxusRouterExt::serve_revalidation_endpoint`](crate::DioxusRouterExt::serve_revalidation_endpoint).
+
+use crate::{DioxusServerContext, FromServerContext};
+use dioxus_isrg::{IncrementalRenderer, IncrementalRendererConfig, IncrementalRendererError};
+use http::{header, HeaderMap, StatusCode};
+use std::sync::Arc;
+
+/// A handle to the incremental render cache of a [`ServeConfig`](crate::ServeConfig). Every clone of the
+/// handle shares the same cache.
+#[derive(Clone)]
+pub struct IncrementalCache {
+    pub(crate) renderer: Arc<IncrementalRenderer>,
+}
+
+impl IncrementalCache {
+    pub(crate) fn new(config: IncrementalRendererConfig) -> Self {
+        Self {
+            renderer: Arc::new(config.build()),
+        }
+    }
+
+    /// Get the incremental cache of the server the server context belongs to.
+    pub fn from_context(
+        context: &DioxusServerContext,
+    ) -> Result<Self, IncrementalRenderingDisabled> {
+        context
+            .get::<IncrementalCache>()
+            .ok_or(IncrementalRenderingDisabled)
+    }
+
+    /// Remove a route from the cache. The next request for the route will render it again.
+    pub async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.renderer.invalidate(route).await
+    }
+
+    /// Remove every route that was rendered with a tag from the cache. Returns the routes that were removed.
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        self.renderer.invalidate_tag(tag).await
+    }
+
+    /// Remove every route from the cache.
+    pub async fn invalidate_all(&self) -> Result<(), IncrementalRendererError> {
+        self.renderer.invalidate_all().await
+    }
+}
+
+#[async_trait::async_trait]
+impl FromServerContext for IncrementalCache {
+    type Rejection = IncrementalRenderingDisabled;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        Self::from_context(req)
+    }
+}
+
+/// The incremental cache was used without enabling incremental rendering with
+/// [`ServeConfigBuilder::incremental`](crate::ServeConfigBuilder::incremental)
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[error("Incremental rendering is not enabled. Enable it with `ServeConfigBuilder::incremental` to use the incremental cache")]
+pub struct IncrementalRenderingDisabled;
+
+/// The body of a request to the revalidation endpoint
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default)]
+struct RevalidationRequest {
+    /// The routes to remove from the cache
+    routes: Vec<String>,
+    /// The tags to remove from the cache
+    tags: Vec<String>,
+    /// Remove every route from the cache
+    all: bool,
+}
+
+/// The response of the revalidation endpoint
+#[derive(Debug, serde::Serialize)]
+struct RevalidationResponse {
+    /// The routes that were removed from the cache
+    invalidated: Vec<String>,
+}
+
+/// The state of the revalidation endpoint
+#[derive(Clone)]
+pub(crate) struct RevalidationState {
+    pub(crate) cache: IncrementalCache,
+    pub(crate) secret: Arc<str>,
+}
+
+/// Handle a request to the revalidation endpoint. Requests must send the secret as a bearer token.
+pub(crate) async fn revalidate_handler(
+    axum::extract::State(state): axum::extract::State<RevalidationState>,
+    headers: HeaderMap,
+    body: bytes::Bytes,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let secret = state.secret.as_bytes();
+    if !matches!(token, Some(token) if crate::csrf::constant_time_eq(token.as_bytes(), secret)) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let request: RevalidationRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+    };
+
+    let invalidate = async {
+        let mut invalidated = Vec::new();
+        if request.all {
+            state.cache.invalidate_all().await?;
+            return Ok(invalidated);
+        }
+        for route in request.routes {
+            state.cache.invalidate(&route).await?;
+            invalidated.push(route);
+        }
+        for tag in &request.tags {
+            invalidated.extend(state.cache.invalidate_tag(tag).await?);
+        }
+        Ok::<_, IncrementalRendererError>(invalidated)
+    };
+    let invalidated = match invalidate.await {
+        Ok(invalidated) => invalidated,
+        Err(err) => {
+            tracing::error!("Failed to invalidate the incremental cache: {err}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let body = serde_json::to_string(&RevalidationResponse { invalidated }).unwrap_or_default();
+    ([(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn revalidation_endpoint() {
+        let dir = std::env::temp_dir().join(format!("dioxus-isrg-{}", std::process::id()));
+        let cache = IncrementalCache::new(IncrementalRendererConfig::new().static_dir(&dir));
+        let renderer = &cache.renderer;
+        renderer
+            .cache_with_tags("/posts/1".into(), "1", vec!["post:1".into()])
+            .await
+            .unwrap();
+        renderer.cache("/about".into(), "about").await.unwrap();
+        let router = Router::new()
+            .route("/revalidate", post(revalidate_handler))
+            .with_state(RevalidationState {
+                cache: cache.clone(),
+                secret: "secret".into(),
+            });
+        let request = |token: &str| {
+            http::Request::post("/revalidate")
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::from(r#"{"tags": ["post:1"]}"#))
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(request("guess")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(renderer.get("/posts/1").await.unwrap().is_some());
+
+        let response = router.oneshot(request("secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], br#"{"invalidated":["/posts/1"]}"#);
+        assert!(renderer.get("/posts/1").await.unwrap().is_none());
+        assert!(renderer.get("/about").await.unwrap().is_some());
+        _ = std::fs::remove_dir_all(dir);
+    }
+}
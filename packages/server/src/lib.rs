@@ -57,8 +57,16 @@
 
 pub mod config;
 pub mod context;
+pub mod cookies;
+pub mod incremental;
+pub mod middleware;
+pub mod session;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod compression;
+mod csrf;
 mod document;
+mod etag;
 mod render;
 mod server;
 mod streaming;
@@ -76,6 +84,7 @@ pub use context::{
 };
 pub use dioxus_isrg::{IncrementalRenderer, IncrementalRendererConfig};
 pub use document::ServerDocument;
+pub use incremental::IncrementalCache;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod launch;
@@ -0,0 +1,171 @@
+//! Middleware for individual server functions.
+//!
+//! Any tower layer can be added to a server function with the `middleware` argument of the
+//! `#[server]` macro or with the stacking `#[middleware]` attribute. The layers only wrap the
+//! handler of that server function.
+//!
+//! [`middleware_fn`] creates a layer from an async function that can read the [`DioxusServerContext`]
+//! of the request and short-circuit the server function with a typed error:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus_server::{middleware::middleware_fn, DioxusServerContext};
+//!
+//! async fn require_api_key(context: DioxusServerContext) -> Result<(), ServerFnError> {
+//!     if context.request_parts().headers.contains_key("x-api-key") {
+//!         return Ok(());
+//!     }
+//!     *context.status_mut() = http::StatusCode::UNAUTHORIZED;
+//!     Err(ServerFnError::new("missing api key"))
+//! }
+//!
+//! #[server(middleware = [middleware_fn(require_api_key)])]
+//! async fn delete_everything() -> ServerFnResult<()> {
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{server_context, DioxusServerContext};
+use axum::body::Body;
+use http::{Request, Response};
+use parking_lot::Mutex;
+use server_fn::{error::FromServerFnError, response::Res};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Create a server function middleware from an async function. The function is called with the
+/// [`DioxusServerContext`] of the request before the server function runs.
+///
+/// If the function returns an error, the server function is skipped and the error is sent to the
+/// client the same way an error returned from the server function would be. The error type should
+/// match the error type of the server function so the client can decode it. The response has a
+/// `500 Internal Server Error` status unless the middleware sets another status on the server context.
+pub fn middleware_fn<F, Fut, E>(f: F) -> ServerFnMiddleware<F>
+where
+    F: Fn(DioxusServerContext) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: FromServerFnError,
+{
+    ServerFnMiddleware { f }
+}
+
+/// A tower layer created with [`middleware_fn`].
+#[derive(Clone, Copy, Debug)]
+pub struct ServerFnMiddleware<F> {
+    f: F,
+}
+
+impl<F: Clone, S> tower_layer::Layer<S> for ServerFnMiddleware<F> {
+    type Service = ServerFnMiddlewareService<F, S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServerFnMiddlewareService {
+            f: self.f.clone(),
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+}
+
+/// The service produced by [`ServerFnMiddleware`].
+#[derive(Clone, Debug)]
+pub struct ServerFnMiddlewareService<F, S> {
+    f: F,
+    inner: Arc<Mutex<S>>,
+}
+
+impl<F, Fut, E, S> tower::Service<Request<Body>> for ServerFnMiddlewareService<F, S>
+where
+    F: Fn(DioxusServerContext) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: FromServerFnError,
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.lock().poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // The server function handler provides the server context while this service runs
+        let check = (self.f)(server_context());
+        let inner = self.inner.clone();
+        Box::pin(async move {
+            if let Err(err) = check.await {
+                return Ok(Response::error_response(req.uri().path(), err.ser()));
+            }
+            let response = inner.lock().call(req);
+            response.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{with_server_context, ProvideServerContext};
+    use server_fn::ServerFnError;
+    use tower::{Layer, Service};
+
+    async fn require_header(context: DioxusServerContext) -> Result<(), ServerFnError> {
+        match context.request_parts().headers.contains_key("x-api-key") {
+            true => Ok(()),
+            false => {
+                *context.status_mut() = http::StatusCode::UNAUTHORIZED;
+                Err(ServerFnError::new("missing api key"))
+            }
+        }
+    }
+
+    async fn run(api_key: Option<&str>) -> Response<Body> {
+        let mut builder = Request::builder().uri("/api/f");
+        if let Some(api_key) = api_key {
+            builder = builder.header("x-api-key", api_key);
+        }
+        let (parts, body) = builder.body(Body::empty()).unwrap().into_parts();
+        let context = DioxusServerContext::new(parts.clone());
+        let req = Request::from_parts(parts, body);
+
+        let handler = tower::service_fn(|_| async {
+            Ok::<_, std::convert::Infallible>(Response::new(Body::from("ran")))
+        });
+        let mut service = middleware_fn(require_header).layer(handler);
+        let fut = with_server_context(context.clone(), || service.call(req));
+        let mut res = ProvideServerContext::new(fut, context.clone())
+            .await
+            .unwrap();
+        context.send_response(&mut res);
+        res
+    }
+
+    #[tokio::test]
+    async fn middleware_short_circuits_with_typed_errors() {
+        let res = run(None).await;
+        assert_eq!(res.status(), http::StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers()
+                .get(server_fn::error::SERVER_FN_ERROR_HEADER)
+                .unwrap(),
+            "/api/f"
+        );
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let err = <ServerFnError as FromServerFnError>::de(body);
+        assert_eq!(err, ServerFnError::new("missing api key"));
+
+        let res = run(Some("secret")).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "ran");
+    }
+}
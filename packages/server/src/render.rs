@@ -1,5 +1,5 @@
 //! A shared pool of renderers for efficient server side rendering.
-use crate::{document::ServerDocument, ProvideServerContext, ServeConfig};
+use crate::{document::ServerDocument, IncrementalCache, ProvideServerContext, ServeConfig};
 use crate::{
     streaming::{Mount, StreamingRenderer},
     DioxusServerContext,
@@ -10,14 +10,29 @@ use dioxus_core::{
     VNode, VirtualDom,
 };
 use dioxus_fullstack_hooks::history::FullstackHistory;
-use dioxus_fullstack_hooks::{StreamingContext, StreamingStatus};
-use dioxus_fullstack_protocol::{HydrationContext, SerializedHydrationData};
+use dioxus_fullstack_hooks::islands::{IslandContext, ISLANDS_META_NAME, ISLAND_ELEMENT_NAME};
+use dioxus_fullstack_hooks::{
+    AcceptLanguage, CacheControlContext, CacheTagsContext, HttpStatusContext, RedirectContext,
+    SiteOrigin, SplitModuleContext, StreamingContext, StreamingStatus,
+};
+use dioxus_fullstack_protocol::{
+    HydrationContext, LazyHydration, SerializedHydrationData, LAZY_HYDRATION_ELEMENT_NAME,
+};
 use dioxus_isrg::{CachedRender, IncrementalRendererError, RenderFreshness};
 use dioxus_router::ParseRouteError;
 use dioxus_ssr::Renderer;
 use futures_channel::mpsc::Sender;
 use futures_util::{Stream, StreamExt};
-use std::{collections::HashMap, fmt::Write, future::Future, rc::Rc, sync::Arc, sync::RwLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    future::Future,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
 use tokio::task::JoinHandle;
 
 use crate::StreamingMode;
@@ -67,55 +82,54 @@ pub enum SSRError {
 
 struct SsrRendererPool {
     renderers: RwLock<Vec<Renderer>>,
-    incremental_cache: Option<RwLock<dioxus_isrg::IncrementalRenderer>>,
+    incremental_cache: Option<IncrementalCache>,
+    /// The stale routes that are currently being rendered again in the background
+    revalidating: Mutex<HashSet<String>>,
 }
 
 impl SsrRendererPool {
-    fn new(
-        initial_size: usize,
-        incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
-    ) -> Self {
+    fn new(initial_size: usize, incremental_cache: Option<IncrementalCache>) -> Self {
         let renderers = RwLock::new((0..initial_size).map(|_| pre_renderer()).collect());
         Self {
             renderers,
-            incremental_cache: incremental.map(|cache| RwLock::new(cache.build())),
+            incremental_cache,
+            revalidating: Default::default(),
         }
     }
 
     /// Look for a cached route in the incremental cache and send it into the render channel if it exists
-    fn check_cached_route(
+    async fn check_cached_route(
         &self,
         route: &str,
         render_into: &mut Sender<Result<String, dioxus_isrg::IncrementalRendererError>>,
     ) -> Option<RenderFreshness> {
-        if let Some(incremental) = &self.incremental_cache {
-            if let Ok(mut incremental) = incremental.write() {
-                match incremental.get(route) {
-                    Ok(Some(cached_render)) => {
-                        let CachedRender {
-                            freshness,
-                            response,
-                            ..
-                        } = cached_render;
-                        _ = render_into.start_send(String::from_utf8(response.to_vec()).map_err(
-                            |err| dioxus_isrg::IncrementalRendererError::Other(Box::new(err)),
-                        ));
-                        return Some(freshness);
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to get route \"{route}\" from incremental cache: {e}"
-                        );
-                    }
-                    _ => {}
-                }
+        let incremental = self.incremental_cache.as_ref()?;
+        match incremental.renderer.get(route).await {
+            Ok(Some(cached_render)) => {
+                let CachedRender {
+                    freshness,
+                    response,
+                    ..
+                } = cached_render;
+                _ = render_into
+                    .start_send(String::from_utf8(response).map_err(|err| {
+                        dioxus_isrg::IncrementalRendererError::Other(Box::new(err))
+                    }));
+                Some(freshness)
             }
+            Err(e) => {
+                tracing::error!("Failed to get route \"{route}\" from incremental cache: {e}");
+                None
+            }
+            _ => None,
         }
-        None
     }
 
     /// Render a virtual dom into a stream. This method will return immediately and continue streaming the result in the background
     /// The streaming is canceled when the stream the function returns is dropped
+    ///
+    /// The returned bool is true if the whole page is ready once the stream starts, either because it came from
+    /// the cache or because streaming is disabled.
     async fn render_to(
         self: Arc<Self>,
         cfg: &ServeConfig,
@@ -125,6 +139,7 @@ impl SsrRendererPool {
     ) -> Result<
         (
             RenderFreshness,
+            bool,
             impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>,
         ),
         SSRError,
@@ -156,16 +171,37 @@ impl SsrRendererPool {
             }
         }
 
-        let (mut into, rx) = futures_channel::mpsc::channel::<
-            Result<String, dioxus_isrg::IncrementalRendererError>,
-        >(1000);
-
-        let (initial_result_tx, initial_result_rx) = futures_channel::oneshot::channel();
+        // Make sure the client has a CSRF token before it can call any server functions. Cached pages
+        // don't include the meta tag, so the client reads the token from the cookie instead
+        let csrf_token = cfg
+            .csrf
+            .as_ref()
+            .map(|_| crate::csrf::csrf_token(server_context));
 
         // before we even spawn anything, we can check synchronously if we have the route cached
-        if let Some(freshness) = self.check_cached_route(&route, &mut into) {
+        let (mut into, rx) = futures_channel::mpsc::channel(1);
+        if let Some(freshness) = self.check_cached_route(&route, &mut into).await {
+            // Stale pages are sent right away and rendered again in the background for the next request
+            if freshness.is_stale() && self.start_revalidating(&route) {
+                // The render is shared between visitors, so it must not see the cookies, headers or response
+                // of the request that happened to find the stale page
+                let revalidation_context = revalidation_context(cfg, &route);
+                let (render, _, _) = self.clone().spawn_render(
+                    cfg,
+                    route.clone(),
+                    virtual_dom_factory,
+                    &revalidation_context,
+                    None,
+                );
+                let myself = self.clone();
+                spawn_platform(move || async move {
+                    _ = render.await;
+                    myself.revalidating.lock().unwrap().remove(&route);
+                });
+            }
             return Ok((
                 freshness,
+                true,
                 ReceiverWithDrop {
                     receiver: rx,
                     cancel_task: None,
@@ -173,9 +209,67 @@ impl SsrRendererPool {
             ));
         }
 
-        let wrapper = FullstackHTMLTemplate { cfg: cfg.clone() };
+        let complete = cfg.streaming_mode_for(&route) == StreamingMode::Disabled;
+        let (join_handle, rx, initial_result_rx) =
+            self.spawn_render(cfg, route, virtual_dom_factory, server_context, csrf_token);
+
+        // Wait for the initial result which determines the status code
+        initial_result_rx.await.map_err(|err| {
+            SSRError::Incremental(IncrementalRendererError::Other(Box::new(err)))
+        })??;
+
+        Ok((
+            RenderFreshness::now(None),
+            complete,
+            ReceiverWithDrop {
+                receiver: rx,
+                cancel_task: Some(join_handle),
+            },
+        ))
+    }
+
+    /// Mark a route as revalidating. Returns false if the route is already being rendered again in the background
+    fn start_revalidating(&self, route: &str) -> bool {
+        self.revalidating.lock().unwrap().insert(route.to_string())
+    }
+
+    /// Render a virtual dom into a channel in the background. The returned oneshot resolves once the initial
+    /// chunk is ready or the render failed
+    #[allow(clippy::type_complexity)]
+    fn spawn_render(
+        self: Arc<Self>,
+        cfg: &ServeConfig,
+        route: String,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        csrf_token: Option<String>,
+    ) -> (
+        JoinHandle<()>,
+        futures_channel::mpsc::Receiver<Result<String, IncrementalRendererError>>,
+        futures_channel::oneshot::Receiver<Result<(), SSRError>>,
+    ) {
+        let (mut into, rx) = futures_channel::mpsc::channel::<
+            Result<String, dioxus_isrg::IncrementalRendererError>,
+        >(1000);
+
+        let (initial_result_tx, initial_result_rx) = futures_channel::oneshot::channel();
+
+        // The cached render is shared between users, so it is rendered without the CSRF token
+        let cache_wrapper = FullstackHTMLTemplate::new(cfg);
+        let wrapper = FullstackHTMLTemplate {
+            cfg: cfg.clone(),
+            csrf_token,
+        };
 
         let server_context = server_context.clone();
+        let accept_language = server_context
+            .request_parts()
+            .headers
+            .get(http::header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(AcceptLanguage::parse)
+            .unwrap_or_default();
+        let site_origin = SiteOrigin::new(cfg.site_origin.clone());
         let mut renderer = self
             .renderers
             .write()
@@ -184,7 +278,9 @@ impl SsrRendererPool {
             .unwrap_or_else(pre_renderer);
 
         let myself = self.clone();
-        let streaming_mode = cfg.streaming_mode;
+        let streaming_mode = cfg.streaming_mode_for(&route);
+        let islands = cfg.islands;
+        let response = server_context.clone();
 
         let create_render_future = move || async move {
             let mut virtual_dom = virtual_dom_factory();
@@ -206,9 +302,21 @@ impl SsrRendererPool {
             let history = FullstackHistory::new_server(history);
 
             let streaming_context = in_root_scope(&virtual_dom, StreamingContext::new);
+            let split_module_context = in_root_scope(&virtual_dom, SplitModuleContext::new);
+            let redirect_context = in_root_scope(&virtual_dom, RedirectContext::new);
+            let status_context = in_root_scope(&virtual_dom, HttpStatusContext::new);
+            let cache_tags_context = in_root_scope(&virtual_dom, CacheTagsContext::new);
+            let cache_control_context = in_root_scope(&virtual_dom, CacheControlContext::new);
             virtual_dom.provide_root_context(Rc::new(history) as Rc<dyn dioxus_history::History>);
             virtual_dom.provide_root_context(document.clone() as Rc<dyn dioxus_document::Document>);
             virtual_dom.provide_root_context(streaming_context);
+            virtual_dom.provide_root_context(split_module_context);
+            virtual_dom.provide_root_context(redirect_context);
+            virtual_dom.provide_root_context(status_context);
+            virtual_dom.provide_root_context(cache_tags_context);
+            virtual_dom.provide_root_context(cache_control_context);
+            virtual_dom.provide_root_context(accept_language);
+            virtual_dom.provide_root_context(site_origin);
 
             // rebuild the virtual dom
             virtual_dom.rebuild_in_place();
@@ -239,6 +347,74 @@ impl SsrRendererPool {
                 }
             }
 
+            // If the page redirected while rendering the initial chunk, answer with the redirect
+            // instead of the page
+            if let Some(redirect) = in_root_scope(&virtual_dom, || redirect_context.current()) {
+                match http::HeaderValue::from_str(&redirect.location) {
+                    Ok(location) => {
+                        response
+                            .headers_mut()
+                            .insert(http::header::LOCATION, location);
+                        *response.status_mut() = http::StatusCode::from_u16(redirect.status.code())
+                            .unwrap_or(http::StatusCode::TEMPORARY_REDIRECT);
+                        _ = initial_result_tx.send(Ok(()));
+                        return;
+                    }
+                    Err(err) => {
+                        tracing::error!(
+                            "Invalid redirect location \"{}\": {err}",
+                            redirect.location
+                        );
+                    }
+                }
+            }
+
+            // Apply the status the page set while rendering the initial chunk, like the 404 status of
+            // a not found route. Pages with an error status are not cached
+            let status = in_root_scope(&virtual_dom, || status_context.current())
+                .and_then(|status| http::StatusCode::from_u16(status).ok());
+            let mut cache_render = true;
+            if let Some(status) = status {
+                *response.status_mut() = status;
+                cache_render = status.is_success();
+            }
+
+            // Pages that vary on a request header can't be cached since the cache only stores one render per route
+            let vary = in_root_scope(&virtual_dom, || cache_control_context.vary());
+            if !vary.is_empty() {
+                cache_render = false;
+                match http::HeaderValue::from_str(&vary.join(", ")) {
+                    Ok(value) => {
+                        response.headers_mut().append(http::header::VARY, value);
+                    }
+                    Err(err) => tracing::error!("Invalid vary header {vary:?}: {err}"),
+                }
+            }
+
+            // Pages that depend on the visitor, like guarded routes, are never shared between visitors
+            let private = in_root_scope(&virtual_dom, || cache_control_context.is_private());
+            let cache_control = match private {
+                true => {
+                    cache_render = false;
+                    Some("private, no-store".to_string())
+                }
+                false => in_root_scope(&virtual_dom, || cache_control_context.current()),
+            };
+
+            // Apply the cache control header the page set while rendering the initial chunk
+            if let Some(cache_control) = cache_control {
+                match http::HeaderValue::from_str(&cache_control) {
+                    Ok(value) => {
+                        response
+                            .headers_mut()
+                            .insert(http::header::CACHE_CONTROL, value);
+                    }
+                    Err(err) => {
+                        tracing::error!("Invalid cache control header \"{cache_control}\": {err}");
+                    }
+                }
+            }
+
             // check if there are any errors
             let errors = virtual_dom.in_runtime(|| {
                 let error_context: ErrorContext = ScopeId::APP
@@ -281,17 +457,8 @@ impl SsrRendererPool {
             }
 
             let stream = Arc::new(StreamingRenderer::new(pre_body, into));
-            let scope_to_mount_mapping = Arc::new(RwLock::new(HashMap::new()));
-
-            renderer.pre_render = true;
-            {
-                let scope_to_mount_mapping = scope_to_mount_mapping.clone();
-                let stream = stream.clone();
-                renderer.set_render_components(streaming_render_component_callback(
-                    stream,
-                    scope_to_mount_mapping,
-                ));
-            }
+            // With islands, only the islands are rendered with hydration ids
+            renderer.pre_render = !islands;
 
             macro_rules! throw_error {
                 ($e:expr) => {
@@ -300,63 +467,15 @@ impl SsrRendererPool {
                 };
             }
 
-            // Render the initial frame with loading placeholders
-            let mut initial_frame = renderer.render(&virtual_dom);
-
-            // Along with the initial frame, we render the html after the main element, but before the body tag closes. This should include the script that starts loading the wasm bundle.
-            if let Err(err) = wrapper.render_after_main(&mut initial_frame, &virtual_dom) {
-                throw_error!(err);
-            }
-            stream.render(initial_frame);
-
-            // After the initial render, we need to resolve suspense
-            while virtual_dom.suspended_tasks_remaining() {
-                virtual_dom.wait_for_suspense_work().await;
-                let resolved_suspense_nodes = virtual_dom.render_suspense_immediate().await;
-
-                // Just rerender the resolved nodes
-                for scope in resolved_suspense_nodes {
-                    let pending_suspense_boundary = {
-                        let mut lock = scope_to_mount_mapping.write().unwrap();
-                        lock.remove(&scope)
-                    };
-                    // If the suspense boundary was immediately removed, it may not have a mount. We can just skip resolving it
-                    if let Some(pending_suspense_boundary) = pending_suspense_boundary {
-                        let mut resolved_chunk = String::new();
-                        // After we replace the placeholder in the dom with javascript, we need to send down the resolved data so that the client can hydrate the node
-                        let render_suspense = |into: &mut String| {
-                            renderer.reset_hydration();
-                            renderer.render_scope(into, &virtual_dom, scope)
-                        };
-                        let resolved_data = serialize_server_data(&virtual_dom, scope);
-                        if let Err(err) = stream.replace_placeholder(
-                            pending_suspense_boundary.mount,
-                            render_suspense,
-                            resolved_data,
-                            &mut resolved_chunk,
-                        ) {
-                            throw_error!(dioxus_isrg::IncrementalRendererError::RenderError(err));
-                        }
-
-                        stream.render(resolved_chunk);
-                        // Freeze the suspense boundary to prevent future reruns of any child nodes of the suspense boundary
-                        if let Some(suspense) =
-                            SuspenseContext::downcast_suspense_boundary_from_scope(
-                                &virtual_dom.runtime(),
-                                scope,
-                            )
-                        {
-                            suspense.freeze();
-                            // Go to every child suspense boundary and add an error boundary. Since we cannot rerun any nodes above the child suspense boundary,
-                            // we need to capture the errors and send them to the client as it resolves
-                            virtual_dom.in_runtime(|| {
-                                for &suspense_scope in pending_suspense_boundary.children.iter() {
-                                    start_capturing_errors(suspense_scope);
-                                }
-                            });
-                        }
-                    }
+            // In order streaming sends the page in document order without placeholders
+            let rendered = match streaming_mode {
+                StreamingMode::InOrder => {
+                    render_in_order(&mut renderer, &mut virtual_dom, &stream, &wrapper).await
                 }
+                _ => render_out_of_order(&mut renderer, &mut virtual_dom, &stream, &wrapper).await,
+            };
+            if let Err(err) = rendered {
+                throw_error!(err);
             }
 
             // After suspense is done, we render the html after the body
@@ -367,9 +486,9 @@ impl SsrRendererPool {
             }
 
             // If incremental rendering is enabled, add the new render to the cache without the streaming bits
-            if let Some(incremental) = &self.incremental_cache {
+            if let Some(incremental) = self.incremental_cache.as_ref().filter(|_| cache_render) {
                 let mut cached_render = String::new();
-                if let Err(err) = wrapper.render_head(&mut cached_render, &virtual_dom) {
+                if let Err(err) = cache_wrapper.render_head(&mut cached_render, &virtual_dom) {
                     throw_error!(err);
                 }
                 renderer.reset_hydration();
@@ -381,8 +500,13 @@ impl SsrRendererPool {
                 }
                 cached_render.push_str(&post_streaming);
 
-                if let Ok(mut incremental) = incremental.write() {
-                    let _ = incremental.cache(route, cached_render);
+                let tags = in_root_scope(&virtual_dom, || cache_tags_context.current());
+                if let Err(err) = incremental
+                    .renderer
+                    .cache_with_tags(route.clone(), cached_render, tags)
+                    .await
+                {
+                    tracing::error!("Failed to cache route \"{route}\": {err}");
                 }
             }
 
@@ -396,21 +520,21 @@ impl SsrRendererPool {
             ProvideServerContext::new(create_render_future(), server_context)
         });
 
-        // Wait for the initial result which determines the status code
-        initial_result_rx.await.map_err(|err| {
-            SSRError::Incremental(IncrementalRendererError::Other(Box::new(err)))
-        })??;
-
-        Ok((
-            RenderFreshness::now(None),
-            ReceiverWithDrop {
-                receiver: rx,
-                cancel_task: Some(join_handle),
-            },
-        ))
+        (join_handle, rx, initial_result_rx)
     }
 }
 
+/// Create the server context for a render of a route in the background that isn't tied to any request
+fn revalidation_context(cfg: &ServeConfig, route: &str) -> DioxusServerContext {
+    let request = http::Request::builder()
+        .uri(route)
+        .body(())
+        .unwrap_or_else(|_| http::Request::new(()));
+    let server_context = DioxusServerContext::new(request.into_parts().0);
+    crate::server::add_server_context(&server_context, &cfg.context_providers);
+    server_context
+}
+
 /// Create the streaming render component callback. It will keep track of what scopes are mounted to what pending
 /// suspense boundaries in the DOM.
 ///
@@ -479,6 +603,368 @@ fn streaming_render_component_callback(
     }
 }
 
+/// Create the in order render component callback. It records where each component starts in the page and stops
+/// the render at the first suspense boundary that is still pending, since nothing after it can be sent yet.
+fn in_order_render_component_callback(
+    pass: Arc<Mutex<InOrderPass>>,
+    written: Arc<AtomicUsize>,
+) -> impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+       + Send
+       + Sync
+       + 'static {
+    move |renderer, to, vdom, scope| {
+        let position = written.load(Ordering::SeqCst);
+        {
+            let mut pass = pass.lock().unwrap();
+            pass.positions.insert(scope, position);
+            if is_pending_in_order(vdom, scope) {
+                pass.first_pending = Some((scope, position));
+                return Err(std::fmt::Error);
+            }
+        }
+        renderer.render_scope(to, vdom, scope)
+    }
+}
+
+/// Check if a scope holds back the rest of an in order page. Lazy hydration boundaries are rendered differently
+/// once their children resolve, so they count as pending until then.
+fn is_pending_in_order(vdom: &VirtualDom, scope: ScopeId) -> bool {
+    let is_pending_suspense_boundary =
+        SuspenseContext::downcast_suspense_boundary_from_scope(&vdom.runtime(), scope)
+            .filter(|s| s.has_suspended_tasks())
+            .is_some();
+    is_pending_suspense_boundary
+        || lazy_hydration_children(vdom, scope)
+            .is_some_and(|(_, children)| has_pending_suspense(vdom, children))
+}
+
+/// Wrap a render component callback to render islands. Pages with islands are rendered without hydration ids, so
+/// the renderer only pre renders while it is inside an island. Each island is rendered into its own element with
+/// the props and the hydration data the client needs to hydrate it.
+fn render_islands(
+    render_component: impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+        + Send
+        + Sync
+        + 'static,
+) -> impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+       + Send
+       + Sync
+       + 'static {
+    move |renderer, to, vdom, scope| {
+        // Islands inside of other islands are hydrated as part of the outer island
+        let island = match renderer.pre_render {
+            true => None,
+            false => vdom.in_runtime(|| scope.has_context::<IslandContext>()),
+        };
+        let Some(island) = island else {
+            return render_component(renderer, to, vdom, scope);
+        };
+
+        let resolved_data = serialize_server_data(vdom, scope);
+        write!(
+            to,
+            r#"<{ISLAND_ELEMENT_NAME} data-name="{}" data-props="{}" data-hydration="{}" style="display: contents">"#,
+            island.name(),
+            island.props(),
+            resolved_data.data
+        )?;
+        // The client hydrates each island in a new virtual dom, so the hydration ids start over
+        renderer.pre_render = true;
+        renderer.reset_hydration();
+        let result = render_component(renderer, to, vdom, scope);
+        renderer.pre_render = false;
+        result?;
+        write!(to, "</{ISLAND_ELEMENT_NAME}>")
+    }
+}
+
+/// Wrap a render component callback to render lazy hydration boundaries. The client hydrates the children of a
+/// boundary separately from the rest of the page, so they are rendered with their own hydration ids and data. If
+/// anything in the children is still pending, the boundary is rendered like any other component and hydrates with
+/// the rest of the page instead.
+fn render_lazy_hydration(
+    render_component: impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+        + Send
+        + Sync
+        + 'static,
+) -> impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+       + Send
+       + Sync
+       + 'static {
+    move |renderer, to, vdom, scope| {
+        let deferred = match renderer.pre_render {
+            true => lazy_hydration_children(vdom, scope)
+                .filter(|(_, children)| !has_pending_suspense(vdom, *children)),
+            false => None,
+        };
+        let Some((_, children)) = deferred else {
+            return render_component(renderer, to, vdom, scope);
+        };
+
+        let resolved_data = serialize_server_data(vdom, children);
+        // The element and the placeholder the client renders in place of the children are hydrated with the
+        // rest of the page
+        let element_id = renderer.next_hydration_id();
+        let placeholder_id = renderer.next_hydration_id();
+        write!(
+            to,
+            r#"<{LAZY_HYDRATION_ELEMENT_NAME} data-node-hydration="{element_id}" data-hydration="{}" style="display: contents"><!--placeholder{placeholder_id}-->"#,
+            resolved_data.data
+        )?;
+        renderer.render_scope_with_new_hydration(to, vdom, children)?;
+        write!(to, "</{LAZY_HYDRATION_ELEMENT_NAME}>")
+    }
+}
+
+/// Get the context of a lazy hydration boundary and the scope of its children
+fn lazy_hydration_children(vdom: &VirtualDom, scope: ScopeId) -> Option<(LazyHydration, ScopeId)> {
+    let lazy = vdom.in_runtime(|| scope.has_context::<LazyHydration>())?;
+    let node = vdom.get_scope(scope)?.try_root_node()?;
+    let children =
+        node.dynamic_nodes
+            .iter()
+            .enumerate()
+            .find_map(|(index, dynamic)| match dynamic {
+                DynamicNode::Component(comp) => comp.mounted_scope(index, node, vdom),
+                _ => None,
+            })?;
+    Some((lazy, children.id()))
+}
+
+/// Check if any suspense boundary in a scope or its children is still pending
+fn has_pending_suspense(vdom: &VirtualDom, scope: ScopeId) -> bool {
+    let pending = SuspenseContext::downcast_suspense_boundary_from_scope(&vdom.runtime(), scope)
+        .is_some_and(|suspense| suspense.has_suspended_tasks());
+    pending
+        || vdom
+            .get_scope(scope)
+            .and_then(|scope| scope.try_root_node())
+            .is_some_and(|node| vnode_has_pending_suspense(vdom, node))
+}
+
+fn vnode_has_pending_suspense(vdom: &VirtualDom, vnode: &VNode) -> bool {
+    vnode
+        .dynamic_nodes
+        .iter()
+        .enumerate()
+        .any(|(index, dynamic)| match dynamic {
+            DynamicNode::Component(comp) => comp
+                .mounted_scope(index, vnode, vdom)
+                .is_some_and(|scope| has_pending_suspense(vdom, scope.id())),
+            DynamicNode::Fragment(nodes) => nodes
+                .iter()
+                .any(|node| vnode_has_pending_suspense(vdom, node)),
+            _ => false,
+        })
+}
+
+/// A writer that shares how much of the page was written with the in order render callback
+struct CountingWriter {
+    html: String,
+    written: Arc<AtomicUsize>,
+}
+
+impl Write for CountingWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.html.push_str(s);
+        self.written.store(self.html.len(), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// The components an in order render pass reached before it stopped
+#[derive(Default)]
+struct InOrderPass {
+    /// Where each component starts in the page
+    positions: HashMap<ScopeId, usize>,
+    /// The first suspense boundary that is still pending and where it starts in the page
+    first_pending: Option<(ScopeId, usize)>,
+}
+
+/// Render the page in document order. Everything before the first pending suspense boundary is sent right away
+/// and the rest of the page is sent as the boundaries resolve. The hydration data is sent once the whole page
+/// is rendered, so the client hydrates the same way it does when streaming is disabled.
+///
+/// Each pass only renders the page up to the first pending boundary, and a new pass only starts once that
+/// boundary resolves. If the html that was already sent changes, the rest of the page is streamed out of order.
+async fn render_in_order(
+    renderer: &mut Renderer,
+    virtual_dom: &mut VirtualDom,
+    stream: &Arc<StreamingRenderer<IncrementalRendererError>>,
+    wrapper: &FullstackHTMLTemplate,
+) -> Result<(), IncrementalRendererError> {
+    let pass = Arc::new(Mutex::new(InOrderPass::default()));
+    let written = Arc::new(AtomicUsize::new(0));
+    renderer.set_render_components(render_islands(render_lazy_hydration(
+        in_order_render_component_callback(pass.clone(), written.clone()),
+    )));
+
+    let mut sent = String::new();
+    let mut blocking = None;
+    loop {
+        *pass.lock().unwrap() = InOrderPass::default();
+        let mut page = CountingWriter {
+            html: String::new(),
+            written: written.clone(),
+        };
+        let rendered = renderer.render_to(&mut page, virtual_dom);
+        let InOrderPass {
+            positions,
+            first_pending,
+        } = std::mem::take(&mut *pass.lock().unwrap());
+        // The pass stops with an error at the first pending boundary
+        if first_pending.is_none() {
+            rendered?;
+        }
+        let page = page.html;
+
+        // Content above a boundary can change after the boundary resolves. The client already has the old html, so
+        // the rest of the page is streamed with placeholders that are swapped in as the boundaries resolve
+        if !page.starts_with(&sent) {
+            tracing::warn!(
+                "The page changed above a suspense boundary after it was streamed in order. The rest of the page will be streamed out of order"
+            );
+            let resume = blocking
+                .and_then(|scope| positions.get(&scope).copied())
+                .unwrap_or_else(|| common_prefix_len(&sent, &page));
+            return render_rest_out_of_order(renderer, virtual_dom, stream, wrapper, resume).await;
+        }
+
+        let ready = first_pending
+            .map_or(page.len(), |(_, position)| position)
+            .max(sent.len());
+        let chunk = &page[sent.len()..ready];
+        if !chunk.is_empty() {
+            stream.render(chunk);
+            sent.push_str(chunk);
+        }
+
+        blocking = first_pending.map(|(scope, _)| scope);
+        if blocking.is_none() && !virtual_dom.suspended_tasks_remaining() {
+            break;
+        }
+
+        // Wait until the boundary that holds back the rest of the page resolves before rendering it again
+        loop {
+            virtual_dom.wait_for_suspense_work().await;
+            let resolved_suspense_nodes = virtual_dom.render_suspense_immediate().await;
+
+            // Freeze the resolved suspense boundaries so the html that was already sent doesn't change
+            for scope in resolved_suspense_nodes {
+                if let Some(suspense) = SuspenseContext::downcast_suspense_boundary_from_scope(
+                    &virtual_dom.runtime(),
+                    scope,
+                ) {
+                    suspense.freeze();
+                }
+            }
+
+            let still_blocked =
+                blocking.is_some_and(|scope| is_pending_in_order(virtual_dom, scope));
+            if !still_blocked || !virtual_dom.suspended_tasks_remaining() {
+                break;
+            }
+        }
+    }
+
+    // The html after the main element includes the hydration data and the script that loads the wasm bundle
+    let mut after_main = String::new();
+    wrapper.render_after_main(&mut after_main, virtual_dom)?;
+    stream.render(after_main);
+
+    Ok(())
+}
+
+/// The length of the longest common prefix of two strings that ends on a char boundary
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(a.len().min(b.len()), |((index, _), _)| index)
+}
+
+/// Render the page with placeholders for the pending suspense boundaries and stream in each boundary as it resolves
+async fn render_out_of_order(
+    renderer: &mut Renderer,
+    virtual_dom: &mut VirtualDom,
+    stream: &Arc<StreamingRenderer<IncrementalRendererError>>,
+    wrapper: &FullstackHTMLTemplate,
+) -> Result<(), IncrementalRendererError> {
+    render_rest_out_of_order(renderer, virtual_dom, stream, wrapper, 0).await
+}
+
+/// Render the page with placeholders and stream everything after `resume` out of order. The part of the page
+/// before `resume` was already sent.
+async fn render_rest_out_of_order(
+    renderer: &mut Renderer,
+    virtual_dom: &mut VirtualDom,
+    stream: &Arc<StreamingRenderer<IncrementalRendererError>>,
+    wrapper: &FullstackHTMLTemplate,
+    resume: usize,
+) -> Result<(), IncrementalRendererError> {
+    let scope_to_mount_mapping = Arc::new(RwLock::new(HashMap::new()));
+    renderer.set_render_components(render_islands(render_lazy_hydration(
+        streaming_render_component_callback(stream.clone(), scope_to_mount_mapping.clone()),
+    )));
+
+    // Render the initial frame with loading placeholders
+    let mut initial_frame = String::new();
+    renderer.render_to(&mut initial_frame, virtual_dom)?;
+    let mut initial_frame = initial_frame.split_off(resume.min(initial_frame.len()));
+
+    // Along with the initial frame, we render the html after the main element, but before the body tag closes. This should include the script that starts loading the wasm bundle.
+    wrapper.render_after_main(&mut initial_frame, virtual_dom)?;
+    stream.render(initial_frame);
+
+    // After the initial render, we need to resolve suspense
+    while virtual_dom.suspended_tasks_remaining() {
+        virtual_dom.wait_for_suspense_work().await;
+        let resolved_suspense_nodes = virtual_dom.render_suspense_immediate().await;
+
+        // Just rerender the resolved nodes
+        for scope in resolved_suspense_nodes {
+            let pending_suspense_boundary = {
+                let mut lock = scope_to_mount_mapping.write().unwrap();
+                lock.remove(&scope)
+            };
+            // If the suspense boundary was immediately removed, it may not have a mount. We can just skip resolving it
+            if let Some(pending_suspense_boundary) = pending_suspense_boundary {
+                let mut resolved_chunk = String::new();
+                // After we replace the placeholder in the dom with javascript, we need to send down the resolved data so that the client can hydrate the node
+                let render_suspense = |into: &mut String| {
+                    renderer.reset_hydration();
+                    renderer.render_scope(into, virtual_dom, scope)
+                };
+                let resolved_data = serialize_server_data(virtual_dom, scope);
+                stream.replace_placeholder(
+                    pending_suspense_boundary.mount,
+                    render_suspense,
+                    resolved_data,
+                    &mut resolved_chunk,
+                )?;
+
+                stream.render(resolved_chunk);
+                // Freeze the suspense boundary to prevent future reruns of any child nodes of the suspense boundary
+                if let Some(suspense) = SuspenseContext::downcast_suspense_boundary_from_scope(
+                    &virtual_dom.runtime(),
+                    scope,
+                ) {
+                    suspense.freeze();
+                    // Go to every child suspense boundary and add an error boundary. Since we cannot rerun any nodes above the child suspense boundary,
+                    // we need to capture the errors and send them to the client as it resolves
+                    virtual_dom.in_runtime(|| {
+                        for &suspense_scope in pending_suspense_boundary.children.iter() {
+                            start_capturing_errors(suspense_scope);
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Start capturing errors at a suspense boundary. If the parent suspense boundary is frozen, we need to capture the errors in the suspense boundary
 /// and send them to the client to continue bubbling up
 fn start_capturing_errors(suspense_scope: ScopeId) {
@@ -522,6 +1008,14 @@ fn serialize_errors(context: &HydrationContext, vdom: &VirtualDom, scope: ScopeI
 }
 
 fn take_from_scope(context: &HydrationContext, vdom: &VirtualDom, scope: ScopeId) {
+    // The children of a deferred lazy hydration boundary are serialized separately with the boundary. The client
+    // learns if the boundary is deferred from the entry in the context of the boundary itself
+    let deferred = lazy_hydration_children(vdom, scope).is_some_and(|(lazy, children)| {
+        let deferred = !has_pending_suspense(vdom, children);
+        lazy.set_deferred(deferred);
+        deferred
+    });
+
     vdom.in_runtime(|| {
         scope.in_runtime(|| {
             // Grab any serializable server context from this scope
@@ -531,6 +1025,9 @@ fn take_from_scope(context: &HydrationContext, vdom: &VirtualDom, scope: ScopeId
             }
         });
     });
+    if deferred {
+        return;
+    }
 
     // then continue to any children
     if let Some(scope) = vdom.get_scope(scope) {
@@ -594,6 +1091,28 @@ impl SSRState {
             impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>,
         ),
         SSRError,
+    > {
+        let (freshness, _, stream) = self
+            .render_page(route, cfg, virtual_dom_factory, server_context)
+            .await?;
+        Ok((freshness, stream))
+    }
+
+    /// Render the application to HTML. The returned bool is true if the whole page is ready once the stream
+    /// starts.
+    pub(crate) async fn render_page<'a>(
+        &'a self,
+        route: String,
+        cfg: &'a ServeConfig,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &'a DioxusServerContext,
+    ) -> Result<
+        (
+            RenderFreshness,
+            bool,
+            impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>,
+        ),
+        SSRError,
     > {
         self.renderers
             .clone()
@@ -605,12 +1124,16 @@ impl SSRState {
 /// The template that wraps the body of the HTML for a fullstack page. This template contains the data needed to hydrate server functions that were run on the server.
 pub struct FullstackHTMLTemplate {
     cfg: ServeConfig,
+    csrf_token: Option<String>,
 }
 
 impl FullstackHTMLTemplate {
     /// Create a new [`FullstackHTMLTemplate`].
     pub fn new(cfg: &ServeConfig) -> Self {
-        Self { cfg: cfg.clone() }
+        Self {
+            cfg: cfg.clone(),
+            csrf_token: None,
+        }
     }
 }
 
@@ -621,7 +1144,11 @@ impl FullstackHTMLTemplate {
         to: &mut R,
         virtual_dom: &VirtualDom,
     ) -> Result<(), dioxus_isrg::IncrementalRendererError> {
-        let ServeConfig { index, .. } = &self.cfg;
+        let ServeConfig {
+            index,
+            wasm_split_manifest,
+            ..
+        } = &self.cfg;
 
         let title = {
             let document: Option<Rc<ServerDocument>> =
@@ -638,6 +1165,31 @@ impl FullstackHTMLTemplate {
         }
         to.write_str(&index.head_after_title)?;
 
+        if let Some(token) = &self.csrf_token {
+            crate::csrf::render_meta_tag(to, token)?;
+        }
+
+        // Tell the client to hydrate each island instead of the whole page
+        if self.cfg.islands {
+            write!(to, r#"<meta name="{ISLANDS_META_NAME}">"#)?;
+        }
+
+        // Preload the wasm-split modules the page needs so they download in parallel with the main bundle
+        let split_modules: Option<SplitModuleContext> =
+            virtual_dom.in_runtime(|| ScopeId::ROOT.consume_context());
+        let mut preloaded = Vec::new();
+        for module in split_modules.map(|m| m.modules()).unwrap_or_default() {
+            for asset in wasm_split_manifest.get(&module).into_iter().flatten() {
+                if !preloaded.contains(&asset) {
+                    write!(
+                        to,
+                        r#"<link rel="preload" href="{asset}" as="fetch" type="application/wasm" crossorigin>"#
+                    )?;
+                    preloaded.push(asset);
+                }
+            }
+        }
+
         let document: Option<Rc<ServerDocument>> =
             virtual_dom.in_runtime(|| ScopeId::ROOT.consume_context());
         if let Some(document) = document {
@@ -679,6 +1231,12 @@ impl FullstackHTMLTemplate {
     ) -> Result<(), dioxus_isrg::IncrementalRendererError> {
         let ServeConfig { index, .. } = &self.cfg;
 
+        // Islands send their own hydration data, so the rest of the page doesn't need any
+        if self.cfg.islands {
+            to.write_str(&index.post_main)?;
+            return Ok(());
+        }
+
         // Collect the initial server data from the root node. For most apps, no use_server_futures will be resolved initially, so this will be full on `None`s.
         // Sending down those Nones are still important to tell the client not to run the use_server_futures that are already running on the backend
         let resolved_data = serialize_server_data(virtual_dom, ScopeId::ROOT);
@@ -726,3 +1284,542 @@ fn pre_renderer() -> Renderer {
     renderer.pre_render = true;
     renderer
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::*;
+
+    #[tokio::test]
+    async fn in_order_streaming() {
+        fn app() -> Element {
+            dioxus_fullstack_hooks::commit_initial_chunk();
+            rsx! {
+                p { "before" }
+                SuspenseBoundary { fallback: |_| rsx! { "loading" }, Slow {} }
+                p { "after" }
+            }
+        }
+
+        #[component]
+        fn Slow() -> Element {
+            let value = use_resource(|| async {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                "slow"
+            })
+            .suspend()?;
+            rsx! { p { "{value}" } }
+        }
+
+        let cfg = ServeConfig::builder()
+            .streaming_mode(StreamingMode::InOrder)
+            .build()
+            .unwrap();
+        let server_context = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut stream = Box::pin(stream);
+
+        // The head is sent first, followed by the page up to the pending suspense boundary
+        stream.next().await.unwrap().unwrap();
+        let shell = stream.next().await.unwrap().unwrap();
+        assert!(shell.contains("before"));
+        assert!(!shell.contains("loading") && !shell.contains("after"));
+
+        let mut rest = String::new();
+        while let Some(chunk) = stream.next().await {
+            rest += &chunk.unwrap();
+        }
+        let slow = rest.find("slow").unwrap();
+        assert!(slow < rest.find("after").unwrap());
+        assert!(!rest.contains("loading"));
+        assert!(!rest.contains("dx_hydrate"));
+        assert!(rest.contains("initial_dioxus_hydration_data"));
+    }
+
+    #[tokio::test]
+    async fn in_order_streaming_falls_back_when_sent_html_changes() {
+        fn app() -> Element {
+            use_context_provider(|| Signal::new(0));
+            rsx! {
+                SuspenseBoundary { fallback: |_| rsx! { "loading" },
+                    Loaded {}
+                    Commit {}
+                    SuspenseBoundary { fallback: |_| rsx! { "loading" }, Slow {} }
+                    p { "after" }
+                }
+            }
+        }
+
+        // The outer boundary resolves before the initial chunk is committed, like a route that loads its data
+        // first, so the components in it still rerun while the page streams
+        #[component]
+        fn Commit() -> Element {
+            use_resource(|| async {}).suspend()?;
+            dioxus_fullstack_hooks::commit_initial_chunk();
+            rsx! {}
+        }
+
+        #[component]
+        fn Loaded() -> Element {
+            let loaded = use_context::<Signal<i32>>();
+            rsx! { p { "loaded {loaded}" } }
+        }
+
+        #[component]
+        fn Slow() -> Element {
+            let mut loaded = use_context::<Signal<i32>>();
+            let value = use_resource(move || async move {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                // Changes the paragraph above the boundary, which was already sent
+                loaded += 1;
+                "slow"
+            })
+            .suspend()?;
+            rsx! { p { "{value}" } }
+        }
+
+        let cfg = ServeConfig::builder()
+            .streaming_mode(StreamingMode::InOrder)
+            .build()
+            .unwrap();
+        let server_context = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut stream = Box::pin(stream);
+
+        stream.next().await.unwrap().unwrap();
+        let shell = stream.next().await.unwrap().unwrap();
+        assert!(shell.contains("loaded 0"));
+
+        // The rest of the page is still sent once the html above the boundary changes
+        let mut rest = String::new();
+        while let Some(chunk) = stream.next().await {
+            rest += &chunk.unwrap();
+        }
+        assert!(!rest.contains("loaded"));
+        let slow = rest.find("slow").unwrap();
+        assert!(slow < rest.find("after").unwrap());
+        assert!(!rest.contains("loading"));
+        assert!(rest.contains("initial_dioxus_hydration_data"));
+    }
+
+    #[tokio::test]
+    async fn islands() {
+        fn app() -> Element {
+            rsx! {
+                p { "static" }
+                Counter { start: 5 }
+            }
+        }
+
+        #[component]
+        fn Counter(start: i32) -> Element {
+            dioxus_fullstack_hooks::islands::use_island("counter", || &start);
+            rsx! { button { "{start}" } }
+        }
+
+        let cfg = ServeConfig::builder().enable_islands().build().unwrap();
+        let server_context = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut html = String::new();
+        let mut stream = Box::pin(stream);
+        while let Some(chunk) = stream.next().await {
+            html += &chunk.unwrap();
+        }
+
+        assert!(html.contains(r#"<meta name="dioxus-islands""#));
+        // Only the island is rendered with hydration ids
+        assert!(html.contains("<p>static</p>"));
+        let island = &html[html.find("<dx-island").unwrap()..html.find("</dx-island>").unwrap()];
+        assert!(island.contains(r#"data-name="counter""#));
+        assert!(island.contains("data-node-hydration"));
+        assert!(!html.contains("initial_dioxus_hydration_data"));
+    }
+
+    #[tokio::test]
+    async fn guarded_routes() {
+        use dioxus_router::GuardOutcome;
+
+        #[derive(Clone, Copy)]
+        struct LoggedIn(bool);
+
+        #[derive(Routable, Clone, Debug, PartialEq)]
+        enum Route {
+            #[route("/admin")]
+            #[guard(require_login)]
+            Admin {},
+            #[route("/login")]
+            Login {},
+        }
+
+        fn require_login(_: &Route) -> GuardOutcome<Route> {
+            match consume_context::<LoggedIn>().0 {
+                true => GuardOutcome::Allow,
+                false => GuardOutcome::Redirect(Route::Login {}.into()),
+            }
+        }
+
+        #[component]
+        fn Admin() -> Element {
+            rsx! { "admin" }
+        }
+
+        #[component]
+        fn Login() -> Element {
+            rsx! { "login" }
+        }
+
+        fn app() -> Element {
+            rsx! { Router::<Route> {} }
+        }
+
+        let cfg = ServeConfig::builder()
+            .incremental(
+                dioxus_isrg::IncrementalRendererConfig::new()
+                    .backend(dioxus_isrg::MemoryBackend::new(10)),
+            )
+            .build()
+            .unwrap();
+        let state = SSRState::new(&cfg);
+        let render = |logged_in: bool| {
+            let state = &state;
+            let cfg = &cfg;
+            async move {
+                let server_context =
+                    DioxusServerContext::new(http::Request::new(()).into_parts().0);
+                let (_, stream) = state
+                    .render(
+                        "/admin".to_string(),
+                        cfg,
+                        move || VirtualDom::new(app).with_root_context(LoggedIn(logged_in)),
+                        &server_context,
+                    )
+                    .await
+                    .unwrap_or_else(|_| panic!("failed to render the page"));
+                let mut html = String::new();
+                let mut stream = Box::pin(stream);
+                while let Some(chunk) = stream.next().await {
+                    html += &chunk.unwrap();
+                }
+                let response = server_context.response_parts().clone();
+                (response, html)
+            }
+        };
+
+        // A guard that redirects answers the request with a temporary redirect
+        let (response, _) = render(false).await;
+        assert_eq!(response.status, http::StatusCode::TEMPORARY_REDIRECT);
+        assert_eq!(response.headers[http::header::LOCATION], "/login");
+
+        // A guarded page the visitor may see is only sent to that visitor
+        let (response, html) = render(true).await;
+        assert_eq!(response.status, http::StatusCode::OK);
+        assert!(html.contains("admin"));
+        assert_eq!(
+            response.headers[http::header::CACHE_CONTROL],
+            "private, no-store"
+        );
+        let incremental = cfg.incremental_cache().unwrap();
+        assert!(incremental.renderer.get("/admin").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn stale_pages_revalidate_without_the_request() {
+        fn app() -> Element {
+            let user = crate::server_context()
+                .request_parts()
+                .headers
+                .get("x-user")
+                .and_then(|user| user.to_str().ok())
+                .unwrap_or("anonymous")
+                .to_string();
+            rsx! { p { "{user}" } }
+        }
+
+        let cfg = ServeConfig::builder()
+            .incremental(
+                dioxus_isrg::IncrementalRendererConfig::new()
+                    .backend(dioxus_isrg::MemoryBackend::new(10))
+                    .invalidate_after(std::time::Duration::ZERO)
+                    .stale_while_revalidate(std::time::Duration::from_secs(60)),
+            )
+            .build()
+            .unwrap();
+        let state = SSRState::new(&cfg);
+        let render = |user: Option<&'static str>| {
+            let state = &state;
+            let cfg = &cfg;
+            async move {
+                let mut request = http::Request::builder();
+                if let Some(user) = user {
+                    request = request.header("x-user", user);
+                }
+                let server_context =
+                    DioxusServerContext::new(request.body(()).unwrap().into_parts().0);
+                let (_, stream) = state
+                    .render(
+                        "/".to_string(),
+                        cfg,
+                        || VirtualDom::new(app),
+                        &server_context,
+                    )
+                    .await
+                    .unwrap_or_else(|_| panic!("failed to render the page"));
+                let mut html = String::new();
+                let mut stream = Box::pin(stream);
+                while let Some(chunk) = stream.next().await {
+                    html += &chunk.unwrap();
+                }
+                html
+            }
+        };
+
+        assert!(render(None).await.contains("anonymous"));
+        // Wait until the cached page is stale
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+        // The stale page is sent and rendered again in the background
+        assert!(render(Some("ada")).await.contains("anonymous"));
+        while !state.renderers.revalidating.lock().unwrap().is_empty() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        // The page in the cache is shared between visitors, so it can't contain the data of the visitor that
+        // found the stale page
+        let incremental = cfg.incremental_cache().unwrap();
+        let cached = incremental.renderer.get("/").await.unwrap().unwrap();
+        let cached = String::from_utf8(cached.response).unwrap();
+        assert!(cached.contains("anonymous"));
+        assert!(!cached.contains("ada"));
+    }
+
+    #[tokio::test]
+    async fn localized_routes() {
+        #[derive(Routable, Clone, Debug, PartialEq)]
+        #[i18n(locales = ["en", "fr"], default = "en")]
+        enum Route {
+            #[route("/about")]
+            About {},
+        }
+
+        #[component]
+        fn About() -> Element {
+            rsx! { p { {dioxus_router::use_locale()} } }
+        }
+
+        fn app() -> Element {
+            rsx! { Router::<Route> {} }
+        }
+
+        let cfg = ServeConfig::builder()
+            .incremental(
+                dioxus_isrg::IncrementalRendererConfig::new()
+                    .backend(dioxus_isrg::MemoryBackend::new(10)),
+            )
+            .site_origin("https://example.com")
+            .build()
+            .unwrap();
+        let state = SSRState::new(&cfg);
+        let render = |route: &'static str| {
+            let state = &state;
+            let cfg = &cfg;
+            async move {
+                let request = http::Request::builder()
+                    .header(http::header::ACCEPT_LANGUAGE, "fr")
+                    .body(())
+                    .unwrap();
+                let server_context = DioxusServerContext::new(request.into_parts().0);
+                let (_, stream) = state
+                    .render(
+                        route.to_string(),
+                        cfg,
+                        || VirtualDom::new(app),
+                        &server_context,
+                    )
+                    .await
+                    .unwrap_or_else(|_| panic!("failed to render the page"));
+                let mut html = String::new();
+                let mut stream = Box::pin(stream);
+                while let Some(chunk) = stream.next().await {
+                    html += &chunk.unwrap();
+                }
+                let response = server_context.response_parts().clone();
+                (response, html)
+            }
+        };
+        let incremental = cfg.incremental_cache().unwrap();
+
+        // Routes without a locale prefix depend on the Accept-Language header
+        let (response, html) = render("/about").await;
+        assert!(html.contains("-->fr<!--#-->"));
+        assert_eq!(response.headers[http::header::VARY], "Accept-Language");
+        assert!(html.contains(r#"href="https://example.com/fr/about""#));
+        assert!(html.contains(r#"href="https://example.com/en/about""#));
+        assert!(html.contains(r#"href="https://example.com/about""#));
+        assert!(incremental.renderer.get("/about").await.unwrap().is_none());
+
+        let (response, html) = render("/fr/about").await;
+        assert!(html.contains("-->fr<!--#-->"));
+        assert!(!response.headers.contains_key(http::header::VARY));
+        assert!(incremental
+            .renderer
+            .get("/fr/about")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn lazy_hydration() {
+        use dioxus_fullstack_hooks::LazyHydrate;
+
+        fn app() -> Element {
+            rsx! {
+                button { onclick: |_| {}, "eager" }
+                LazyHydrate {
+                    button { onclick: |_| {}, "lazy" }
+                }
+            }
+        }
+
+        let cfg = ServeConfig::builder().build().unwrap();
+        let server_context = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut html = String::new();
+        let mut stream = Box::pin(stream);
+        while let Some(chunk) = stream.next().await {
+            html += &chunk.unwrap();
+        }
+
+        // The children are rendered with their own hydration data and ids after the placeholder the client
+        // renders while the boundary is deferred
+        let lazy = &html[html.find("<dx-lazy").unwrap()..html.find("</dx-lazy>").unwrap()];
+        assert!(lazy.contains("data-node-hydration="));
+        assert!(lazy.contains("data-hydration="));
+        assert!(lazy.contains("<!--placeholder"));
+        assert!(lazy.contains(r#"<button data-node-hydration="0,click:1">lazy</button>"#));
+        assert!(html.contains("initial_dioxus_hydration_data"));
+    }
+
+    #[tokio::test]
+    async fn hreflang_links_ignore_the_host_header() {
+        #[derive(Routable, Clone, Debug, PartialEq)]
+        #[i18n(locales = ["en", "fr"], default = "en")]
+        enum Route {
+            #[route("/about")]
+            About {},
+        }
+
+        #[component]
+        fn About() -> Element {
+            rsx! { "about" }
+        }
+
+        fn app() -> Element {
+            rsx! { Router::<Route> {} }
+        }
+
+        // The host header is controlled by the client and must not end up in the page
+        let cfg = ServeConfig::builder().build().unwrap();
+        let request = http::Request::builder()
+            .uri("/fr/about")
+            .header(http::header::HOST, "attacker.example")
+            .header("x-forwarded-proto", "ftp")
+            .body(())
+            .unwrap();
+        let server_context = DioxusServerContext::new(request.into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/fr/about".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut html = String::new();
+        let mut stream = Box::pin(stream);
+        while let Some(chunk) = stream.next().await {
+            html += &chunk.unwrap();
+        }
+
+        assert!(!html.contains("attacker.example"));
+        // Without a configured origin the links are relative
+        assert!(html.contains(r#"href="/fr/about" hreflang="fr""#));
+        assert!(html.contains(r#"href="/about" hreflang="x-default""#));
+    }
+
+    #[tokio::test]
+    async fn pages_that_read_cookies_are_not_cached() {
+        fn app() -> Element {
+            let cookies = crate::cookies::Cookies::from_context(&crate::server_context());
+            let theme = cookies.get("theme");
+            let theme = theme.as_ref().map(|theme| theme.value()).unwrap_or("light");
+            rsx! { p { "{theme}" } }
+        }
+
+        let cfg = ServeConfig::builder()
+            .incremental(
+                dioxus_isrg::IncrementalRendererConfig::new()
+                    .backend(dioxus_isrg::MemoryBackend::new(10)),
+            )
+            .build()
+            .unwrap();
+        let request = http::Request::builder()
+            .header(http::header::COOKIE, "theme=dark")
+            .body(())
+            .unwrap();
+        let server_context = DioxusServerContext::new(request.into_parts().0);
+        let (_, stream) = SSRState::new(&cfg)
+            .render(
+                "/".to_string(),
+                &cfg,
+                || VirtualDom::new(app),
+                &server_context,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("failed to render the page"));
+        let mut html = String::new();
+        let mut stream = Box::pin(stream);
+        while let Some(chunk) = stream.next().await {
+            html += &chunk.unwrap();
+        }
+
+        assert!(html.contains("dark"));
+        let vary = server_context.response_parts().headers[http::header::VARY].clone();
+        assert_eq!(vary, "Cookie");
+        let incremental = cfg.incremental_cache().unwrap();
+        assert!(incremental.renderer.get("/").await.unwrap().is_none());
+    }
+}
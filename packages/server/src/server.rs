@@ -1,5 +1,6 @@
+use crate::incremental::{revalidate_handler, RevalidationState};
+use crate::{csrf::CsrfConfig, ContextProviders, ProvideServerContext};
 use crate::{render::SSRError, with_server_context, DioxusServerContext, SSRState, ServeConfig};
-use crate::{ContextProviders, ProvideServerContext};
 use axum::body;
 use axum::extract::State;
 use axum::routing::*;
@@ -10,8 +11,9 @@ use axum::{
 };
 
 use dioxus_core::{Element, VirtualDom};
+use futures_util::TryStreamExt;
 use http::header::*;
-use server_fn::ServerFnTraitObj;
+use server_fn::{error::ServerFnErrorErr, response::Res, ServerFnTraitObj};
 use std::path::Path;
 use std::sync::Arc;
 use tower::util::MapResponse;
@@ -72,6 +74,48 @@ pub trait DioxusRouterExt<S>: DioxusRouterFnExt<S> {
     fn serve_dioxus_application(self, cfg: ServeConfig, app: fn() -> Element) -> Self
     where
         Self: Sized;
+
+    /// Serves an endpoint that removes pages from the incremental render cache of the [`ServeConfig`]. Requests
+    /// must be `POST`s with the secret as a bearer token and a JSON body with the `routes` and `tags` to remove, or
+    /// `"all": true` to clear the cache. The endpoint responds with the routes that were removed.
+    ///
+    /// If incremental rendering is not enabled in the config, no endpoint is added.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # #![allow(non_snake_case)]
+    /// # use dioxus::prelude::*;
+    /// use dioxus_server::{DioxusRouterExt, IncrementalRendererConfig, ServeConfig};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = dioxus::cli_config::fullstack_address_or_localhost();
+    ///     let cfg = ServeConfig::builder()
+    ///         .incremental(IncrementalRendererConfig::new())
+    ///         .build()
+    ///         .unwrap();
+    ///     let secret = std::env::var("REVALIDATION_SECRET").unwrap();
+    ///     let router = axum::Router::new()
+    ///         // curl -X POST -H "Authorization: Bearer $REVALIDATION_SECRET" -d '{"tags": ["post:42"]}' localhost:8080/api/revalidate
+    ///         .serve_revalidation_endpoint("/api/revalidate", &cfg, secret)
+    ///         .serve_dioxus_application(cfg, app)
+    ///         .into_make_service();
+    ///     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    ///     axum::serve(listener, router).await.unwrap();
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     rsx! { "Hello World" }
+    /// }
+    /// ```
+    fn serve_revalidation_endpoint(
+        self,
+        path: &str,
+        cfg: &ServeConfig,
+        secret: impl Into<String>,
+    ) -> Self
+    where
+        Self: Sized;
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -92,9 +136,7 @@ where
 
     fn serve_dioxus_application(self, cfg: ServeConfig, app: fn() -> Element) -> Self {
         // Add server functions and render index.html
-        let server = self
-            .serve_static_assets()
-            .register_server_functions_with_context(cfg.context_providers.clone());
+        let server = register_server_functions_with_config(self.serve_static_assets(), &cfg);
 
         let ssr_state = SSRState::new(&cfg);
 
@@ -103,6 +145,25 @@ where
                 .with_state(RenderHandleState::new(cfg, app).with_ssr_state(ssr_state)),
         )
     }
+
+    fn serve_revalidation_endpoint(
+        self,
+        path: &str,
+        cfg: &ServeConfig,
+        secret: impl Into<String>,
+    ) -> Self {
+        let Some(cache) = cfg.incremental_cache() else {
+            tracing::warn!(
+                "The revalidation endpoint was not added because incremental rendering is disabled"
+            );
+            return self;
+        };
+        let state = RevalidationState {
+            cache,
+            secret: secret.into().into(),
+        };
+        self.route(path, post(revalidate_handler).with_state(state))
+    }
 }
 
 /// A extension trait with server function utilities for integrating Dioxus with your Axum router.
@@ -197,7 +258,7 @@ where
     where
         Self: Sized,
     {
-        let server = self.register_server_functions_with_context(cfg.context_providers.clone());
+        let server = register_server_functions_with_config(self, &cfg);
 
         let ssr_state = SSRState::new(&cfg);
 
@@ -208,11 +269,34 @@ where
     }
 }
 
+/// Register all server functions with the context providers and CSRF settings of a [`ServeConfig`]
+fn register_server_functions_with_config<S>(mut router: Router<S>, cfg: &ServeConfig) -> Router<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    for f in collect_raw_server_fns() {
+        router = register_server_fn(f, router, cfg.context_providers.clone(), cfg.csrf.clone());
+    }
+    router
+}
+
 pub fn register_server_fn_on_router<S>(
     f: &'static AxumServerFn,
     router: Router<S>,
     context_providers: ContextProviders,
 ) -> Router<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    register_server_fn(f, router, context_providers, None)
+}
+
+fn register_server_fn<S>(
+    f: &'static AxumServerFn,
+    router: Router<S>,
+    context_providers: ContextProviders,
+    csrf: Option<CsrfConfig>,
+) -> Router<S>
 where
     S: Send + Sync + Clone + 'static,
 {
@@ -221,7 +305,7 @@ where
     let method = f.method();
 
     tracing::trace!("Registering server function: {} {}", method, path);
-    let handler = move |req| handle_server_fns_inner(f, context_providers, req);
+    let handler = move |req| handle_server_fns_inner(f, context_providers, csrf, req);
     match method {
         Method::GET => router.route(path, get(handler)),
         Method::POST => router.route(path, post(handler)),
@@ -240,6 +324,7 @@ pub(crate) fn collect_raw_server_fns() -> Vec<&'static AxumServerFn> {
 async fn handle_server_fns_inner(
     f: &AxumServerFn,
     additional_context: ContextProviders,
+    csrf: Option<CsrfConfig>,
     req: Request<Body>,
 ) -> Response<axum::body::Body> {
     let (parts, body) = req.into_parts();
@@ -272,6 +357,19 @@ async fn handle_server_fns_inner(
         service
     };
 
+    // reject requests from other sites before the server fn or its middleware runs
+    if let Some(csrf) = &csrf {
+        if !csrf.allows(f.path(), &server_context.request_parts()) {
+            tracing::trace!("Rejected server function call without a valid CSRF token");
+            let err = (service.ser)(ServerFnErrorErr::ServerError(
+                "Missing or invalid CSRF token".to_string(),
+            ));
+            let mut res = Response::error_response(f.path(), err);
+            *res.status_mut() = StatusCode::FORBIDDEN;
+            return res;
+        }
+    }
+
     // actually run the server fn (which may use the server context)
     let fut = with_server_context(server_context.clone(), || service.run(req));
 
@@ -407,6 +505,7 @@ pub async fn render_handler(
     };
 
     let (parts, _) = request.into_parts();
+    let request_headers = parts.headers.clone();
     let url = parts
         .uri
         .path_and_query()
@@ -420,13 +519,48 @@ pub async fn render_handler(
     add_server_context(&server_context, &state.config.context_providers);
 
     match ssr_state
-        .render(url, cfg, build_virtual_dom, &server_context)
+        .render_page(url, cfg, build_virtual_dom, &server_context)
         .await
     {
-        Ok((freshness, rx)) => {
-            let mut response = axum::response::Html::from(Body::from_stream(rx)).into_response();
+        Ok((freshness, complete, rx)) => {
+            // Pages that are ready before they are sent can be hashed without holding back a stream
+            let (mut response, etag) = if cfg.etags && complete {
+                let html = match rx.try_collect::<String>().await {
+                    Ok(html) => html,
+                    Err(e) => {
+                        tracing::error!("Failed to render page: {}", e);
+                        return Ok(report_err(e).into_response());
+                    }
+                };
+                let etag = crate::etag::etag(html.as_bytes());
+                (axum::response::Html::from(html).into_response(), Some(etag))
+            } else {
+                let response = axum::response::Html::from(Body::from_stream(rx)).into_response();
+                (response, None)
+            };
             freshness.write(response.headers_mut());
             server_context.send_response(&mut response);
+
+            if let Some(etag) = etag {
+                let not_modified = response.status() == StatusCode::OK
+                    && crate::etag::if_none_match(&request_headers, &etag);
+                response.headers_mut().insert(http::header::ETAG, etag);
+                if not_modified {
+                    let (mut parts, _) = response.into_parts();
+                    parts.status = StatusCode::NOT_MODIFIED;
+                    parts.headers.remove(http::header::CONTENT_TYPE);
+                    parts.headers.remove(http::header::CONTENT_LENGTH);
+                    response = Response::from_parts(parts, Body::empty());
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if cfg.compression {
+                if let Some(encoding) = crate::compression::Encoding::negotiate(&request_headers) {
+                    response = crate::compression::compress(response, encoding);
+                }
+            }
+
             Result::<http::Response<axum::body::Body>, StatusCode>::Ok(response)
         }
         Err(SSRError::Incremental(e)) => {
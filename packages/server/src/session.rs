@@ -0,0 +1,552 @@
+//! Server side sessions.
+//!
+//! A [`Session`] stores data for a visitor on the server. The visitor is identified by a random id in the
+//! `dioxus-session` cookie, and the data is kept in the [`SessionStore`] set with
+//! [`ServeConfigBuilder::session_store`](crate::ServeConfigBuilder::session_store). Dioxus includes a
+//! [`MemorySessionStore`] and a [`FileSessionStore`], and you can implement [`SessionStore`] for any other
+//! storage.
+//!
+//! Server functions can extract the session from the server context:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus_server::session::{MemorySessionStore, Session};
+//!
+//! fn main() {
+//!     dioxus::LaunchBuilder::new()
+//!         .with_context(server_only! {
+//!             dioxus_server::ServeConfig::builder().session_store(MemorySessionStore::new())
+//!         })
+//!         .launch(app);
+//! }
+//!
+//! #[server]
+//! async fn login(name: String) -> ServerFnResult {
+//!     let session: Session = extract().await?;
+//!     session.insert("user", name).await?;
+//!     Ok(())
+//! }
+//!
+//! #[server]
+//! async fn current_user() -> ServerFnResult<Option<String>> {
+//!     let session: Session = extract().await?;
+//!     Ok(session.get("user").await?)
+//! }
+//! # fn app() -> Element { todo!() }
+//! ```
+//!
+//! Components that render on the server can read the session with [`use_session`].
+//!
+//! Sessions expire after they have not been saved for the ttl of the store, 30 days by default. Call
+//! [`Session::regenerate`] when the visitor logs in so an id an attacker planted before the login can't be
+//! used to take over the session.
+
+use crate::{cookies::Cookies, server_context, DioxusServerContext, FromServerContext};
+use base64::Engine;
+use cookie::{Cookie, SameSite};
+use parking_lot::{Mutex, RwLock};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// The name of the cookie that stores the session id
+const SESSION_COOKIE_NAME: &str = "dioxus-session";
+
+/// How long the built in session stores keep a session after it was last saved
+const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// The data of a session. Values are stored as json so any store can save them.
+pub type SessionData = HashMap<String, serde_json::Value>;
+
+/// Storage for sessions. Implement this trait to keep sessions in a database or a shared cache.
+#[async_trait::async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Load the data of a session. Returns `None` if the session doesn't exist.
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError>;
+
+    /// Save the data of a session, replacing any existing data.
+    async fn save(&self, id: &str, data: &SessionData) -> Result<(), SessionError>;
+
+    /// Delete a session.
+    async fn delete(&self, id: &str) -> Result<(), SessionError>;
+
+    /// How long a session is kept after it was last saved. This is used as the max age of the session
+    /// cookie. Returns `None` if sessions never expire.
+    fn ttl(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A session store that keeps sessions in memory. Sessions are lost when the server restarts and are not
+/// shared between servers.
+#[derive(Clone)]
+pub struct MemorySessionStore {
+    sessions: Arc<RwLock<HashMap<String, (SystemTime, SessionData)>>>,
+    ttl: Duration,
+}
+
+impl Default for MemorySessionStore {
+    fn default() -> Self {
+        Self {
+            sessions: Default::default(),
+            ttl: DEFAULT_SESSION_TTL,
+        }
+    }
+}
+
+impl MemorySessionStore {
+    /// Create a new empty memory session store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long a session is kept after it was last saved. Defaults to 30 days.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError> {
+        Ok(self
+            .sessions
+            .read()
+            .get(id)
+            .filter(|(saved, _)| !expired(*saved, self.ttl))
+            .map(|(_, data)| data.clone()))
+    }
+
+    async fn save(&self, id: &str, data: &SessionData) -> Result<(), SessionError> {
+        let mut sessions = self.sessions.write();
+        // Drop expired sessions so they don't pile up
+        sessions.retain(|_, (saved, _)| !expired(*saved, self.ttl));
+        sessions.insert(id.to_string(), (SystemTime::now(), data.clone()));
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), SessionError> {
+        self.sessions.write().remove(id);
+        Ok(())
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        Some(self.ttl)
+    }
+}
+
+/// A session store that keeps each session in a json file in a directory. Expired sessions are deleted
+/// when they are loaded.
+#[derive(Clone, Debug)]
+pub struct FileSessionStore {
+    directory: PathBuf,
+    ttl: Duration,
+}
+
+impl FileSessionStore {
+    /// Create a session store that keeps sessions in a directory. The directory is created when the first
+    /// session is saved.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            ttl: DEFAULT_SESSION_TTL,
+        }
+    }
+
+    /// Set how long a session is kept after it was last saved. Defaults to 30 days.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, SessionError> {
+        if !valid_session_id(id) {
+            return Err(SessionError::InvalidId);
+        }
+        Ok(self.directory.join(format!("{id}.json")))
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionStore for FileSessionStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, SessionError> {
+        let path = self.path(id)?;
+        let saved = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata.modified()?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if expired(saved, self.ttl) {
+            self.delete(id).await?;
+            return Ok(None);
+        }
+        match tokio::fs::read(path).await {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn save(&self, id: &str, data: &SessionData) -> Result<(), SessionError> {
+        let path = self.path(id)?;
+        tokio::fs::create_dir_all(&self.directory).await?;
+        tokio::fs::write(path, serde_json::to_vec(data)?).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), SessionError> {
+        match tokio::fs::remove_file(self.path(id)?).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        Some(self.ttl)
+    }
+}
+
+/// Check if a session that was last saved at `saved` has expired
+fn expired(saved: SystemTime, ttl: Duration) -> bool {
+    saved.elapsed().is_ok_and(|elapsed| elapsed > ttl)
+}
+
+/// The session store set in the [`ServeConfig`](crate::ServeConfig)
+#[derive(Clone)]
+pub(crate) struct SharedSessionStore(pub(crate) Arc<dyn SessionStore>);
+
+/// The session state of the current request, shared by every [`Session`] handle for the request
+#[derive(Clone, Default)]
+struct SharedSessionState(Arc<Mutex<SessionState>>);
+
+#[derive(Default)]
+struct SessionState {
+    /// The id of the session, if the visitor has one
+    id: Option<String>,
+    /// The data of the session once it is loaded from the store
+    data: Option<SessionData>,
+}
+
+/// The session of the current visitor.
+///
+/// Changes to the session are saved to the [`SessionStore`] immediately. A new session is only created
+/// and sent to the client when data is first inserted. Session ids from the cookie that are not in the
+/// store are never reused; a new id is created instead.
+#[derive(Clone)]
+pub struct Session {
+    store: Arc<dyn SessionStore>,
+    cookies: Cookies,
+    state: Arc<Mutex<SessionState>>,
+}
+
+impl Session {
+    /// Get the session of the request the server context belongs to.
+    pub fn from_context(context: &DioxusServerContext) -> Result<Self, MissingSessionStore> {
+        let SharedSessionStore(store) = context
+            .get::<SharedSessionStore>()
+            .ok_or(MissingSessionStore)?;
+        let cookies = Cookies::from_context(context);
+        let SharedSessionState(state) = match context.get::<SharedSessionState>() {
+            Some(state) => state,
+            None => {
+                let id = cookies
+                    .get(SESSION_COOKIE_NAME)
+                    .map(|cookie| cookie.value().to_string())
+                    .filter(|id| valid_session_id(id));
+                let state =
+                    SharedSessionState(Arc::new(Mutex::new(SessionState { id, data: None })));
+                context.insert(state.clone());
+                state
+            }
+        };
+        Ok(Self {
+            store,
+            cookies,
+            state,
+        })
+    }
+
+    /// Get the id of the session. Returns `None` if the visitor doesn't have a session yet.
+    pub fn id(&self) -> Option<String> {
+        self.state.lock().id.clone()
+    }
+
+    /// Get a value from the session.
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, SessionError> {
+        let data = self.load().await?;
+        match data.get(key) {
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Insert a value into the session. This creates the session if the visitor doesn't have one yet.
+    pub async fn insert(&self, key: &str, value: impl Serialize) -> Result<(), SessionError> {
+        let value = serde_json::to_value(value)?;
+        let mut data = self.load().await?;
+        data.insert(key.to_string(), value);
+        self.save(data).await
+    }
+
+    /// Remove a value from the session.
+    pub async fn remove(&self, key: &str) -> Result<(), SessionError> {
+        let mut data = self.load().await?;
+        if data.remove(key).is_some() {
+            self.save(data).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete the session from the store and remove the session cookie. Inserting data afterwards creates
+    /// a new session with a new id.
+    pub async fn destroy(&self) -> Result<(), SessionError> {
+        let id = {
+            let mut state = self.state.lock();
+            state.data = Some(SessionData::new());
+            state.id.take()
+        };
+        if let Some(id) = id {
+            self.store.delete(&id).await?;
+            self.cookies
+                .remove(Cookie::build(SESSION_COOKIE_NAME).path("/"));
+        }
+        Ok(())
+    }
+
+    /// Move the session data to a new session id and delete the old session. Call this when the privileges
+    /// of the visitor change, for example after they log in, so an id that was known before can't be used
+    /// to access the session.
+    pub async fn regenerate(&self) -> Result<(), SessionError> {
+        let data = self.load().await?;
+        let Some(old_id) = self.state.lock().id.take() else {
+            return Ok(());
+        };
+        self.store.delete(&old_id).await?;
+        self.save(data).await
+    }
+
+    /// Load the session data from the store if it isn't loaded yet
+    async fn load(&self) -> Result<SessionData, SessionError> {
+        let id = {
+            let state = self.state.lock();
+            if let Some(data) = &state.data {
+                return Ok(data.clone());
+            }
+            state.id.clone()
+        };
+        let data = match id {
+            Some(id) => self.store.load(&id).await?,
+            None => None,
+        };
+        let mut state = self.state.lock();
+        if data.is_none() {
+            // The session expired or the id was never created by this server. Forget the id so saving the
+            // session creates a new one instead of adopting an id the client chose
+            state.id = None;
+        }
+        let data = data.unwrap_or_default();
+        state.data = Some(data.clone());
+        Ok(data)
+    }
+
+    /// Save the session data to the store, creating the session if it doesn't exist yet
+    async fn save(&self, data: SessionData) -> Result<(), SessionError> {
+        let (id, created) = {
+            let mut state = self.state.lock();
+            state.data = Some(data.clone());
+            match &state.id {
+                Some(id) => (id.clone(), false),
+                None => {
+                    let id = new_session_id();
+                    state.id = Some(id.clone());
+                    (id, true)
+                }
+            }
+        };
+        self.store.save(&id, &data).await?;
+        let ttl = self.store.ttl();
+        // Saving a session extends its lifetime, so the cookie is sent again if it expires
+        if created || ttl.is_some() {
+            let mut cookie = Cookie::build((SESSION_COOKIE_NAME, id))
+                .path("/")
+                .http_only(true)
+                .same_site(SameSite::Lax);
+            if let Some(max_age) = ttl.and_then(|ttl| cookie::time::Duration::try_from(ttl).ok()) {
+                cookie = cookie.max_age(max_age);
+            }
+            self.cookies.set(cookie);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl FromServerContext for Session {
+    type Rejection = MissingSessionStore;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        Self::from_context(req)
+    }
+}
+
+/// Get the session of the visitor while rendering a component on the server.
+///
+/// Reading the session is async, so read it in a server future:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_server::session::use_session;
+///
+/// #[component]
+/// fn Greeting() -> Element {
+///     let session = use_session()?;
+///     let user = use_server_future(move || {
+///         let session = session.clone();
+///         async move { session.get::<String>("user").await.ok().flatten() }
+///     })?;
+///     match user() {
+///         Some(Some(user)) => rsx! { "Welcome back {user}" },
+///         _ => rsx! { "Welcome" },
+///     }
+/// }
+/// ```
+///
+/// Sessions only exist on the server. New sessions can only be created while rendering if data is inserted
+/// before the initial chunk of the response is sent.
+pub fn use_session() -> Result<Session, MissingSessionStore> {
+    dioxus_core::use_hook(|| Session::from_context(&server_context()))
+}
+
+/// An error that can occur while reading or writing a session
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SessionError {
+    /// A value in the session could not be serialized or deserialized
+    #[error("Failed to serialize session data: {0}")]
+    Serialization(#[from] serde_json::Error),
+    /// The session store failed to read or write the session
+    #[error("Failed to access the session store: {0}")]
+    Io(#[from] std::io::Error),
+    /// The session id is not a valid id created by dioxus
+    #[error("Invalid session id")]
+    InvalidId,
+    /// A custom session store failed
+    #[error("{0}")]
+    Store(String),
+}
+
+/// A session was used without setting a session store with
+/// [`ServeConfigBuilder::session_store`](crate::ServeConfigBuilder::session_store)
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[error("No session store was set. Set a store with `ServeConfigBuilder::session_store` to use sessions")]
+pub struct MissingSessionStore;
+
+fn new_session_id() -> String {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).expect("Failed to generate a session id");
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Session ids are url safe base64, so they can be used as file names
+fn valid_session_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(store: &MemorySessionStore, cookie: Option<&str>) -> DioxusServerContext {
+        let mut request = http::Request::builder();
+        if let Some(cookie) = cookie {
+            request = request.header(http::header::COOKIE, cookie);
+        }
+        let context = DioxusServerContext::new(request.body(()).unwrap().into_parts().0);
+        context.insert(SharedSessionStore(Arc::new(store.clone())));
+        context
+    }
+
+    #[tokio::test]
+    async fn sessions_persist_between_requests() {
+        let store = MemorySessionStore::new();
+
+        let first = context(&store, None);
+        let session = Session::from_context(&first).unwrap();
+        assert_eq!(session.get::<String>("user").await.unwrap(), None);
+        assert_eq!(session.id(), None);
+        session.insert("user", "ada").await.unwrap();
+        let id = session.id().unwrap();
+        // Other handles in the same request see the change
+        let other = Session::from_context(&first).unwrap();
+        assert_eq!(other.get::<String>("user").await.unwrap().unwrap(), "ada");
+
+        let cookie = format!("{SESSION_COOKIE_NAME}={id}");
+        let second = context(&store, Some(&cookie));
+        let session = Session::from_context(&second).unwrap();
+        assert_eq!(session.get::<String>("user").await.unwrap().unwrap(), "ada");
+        session.destroy().await.unwrap();
+        assert!(store.load(&id).await.unwrap().is_none());
+
+        // Session ids that were not created by dioxus are ignored
+        let third = context(&store, Some("dioxus-session=../../etc/passwd"));
+        assert_eq!(Session::from_context(&third).unwrap().id(), None);
+
+        let without_store = DioxusServerContext::new(http::Request::new(()).into_parts().0);
+        assert!(Session::from_context(&without_store).is_err());
+    }
+
+    #[tokio::test]
+    async fn unknown_session_ids_are_replaced() {
+        let store = MemorySessionStore::new();
+
+        // An attacker plants an id the server never created
+        let cookie = format!("{SESSION_COOKIE_NAME}=planted");
+        let request = context(&store, Some(&cookie));
+        let session = Session::from_context(&request).unwrap();
+        session.insert("user", "ada").await.unwrap();
+        let id = session.id().unwrap();
+        assert_ne!(id, "planted");
+        assert!(store.load("planted").await.unwrap().is_none());
+        assert!(store.load(&id).await.unwrap().is_some());
+
+        // Regenerating moves the data to a new id
+        let cookie = format!("{SESSION_COOKIE_NAME}={id}");
+        let request = context(&store, Some(&cookie));
+        let session = Session::from_context(&request).unwrap();
+        session.regenerate().await.unwrap();
+        let new_id = session.id().unwrap();
+        assert_ne!(new_id, id);
+        assert!(store.load(&id).await.unwrap().is_none());
+        assert_eq!(session.get::<String>("user").await.unwrap().unwrap(), "ada");
+        assert!(store.load(&new_id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn sessions_expire() {
+        let store = MemorySessionStore::new().with_ttl(Duration::ZERO);
+        store.save("abc", &SessionData::new()).await.unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.load("abc").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn file_session_store() {
+        let directory = std::env::temp_dir().join(format!("dioxus-sessions-{}", new_session_id()));
+        let store = FileSessionStore::new(&directory);
+        let data = SessionData::from([("count".to_string(), serde_json::json!(3))]);
+        store.save("abc", &data).await.unwrap();
+        assert_eq!(store.load("abc").await.unwrap(), Some(data));
+        store.delete("abc").await.unwrap();
+        assert_eq!(store.load("abc").await.unwrap(), None);
+        assert!(store.load("../abc").await.is_err());
+        _ = std::fs::remove_dir_all(directory);
+    }
+}
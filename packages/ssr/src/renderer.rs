@@ -97,6 +97,27 @@ impl Renderer {
         self.dynamic_node_id = 0;
     }
 
+    /// Take the next hydration id. Render component callbacks can use this to hydrate nodes they write themselves
+    pub fn next_hydration_id(&mut self) -> usize {
+        let id = self.dynamic_node_id;
+        self.dynamic_node_id += 1;
+        id
+    }
+
+    /// Render a scope with hydration ids that start over from zero. The nodes after the scope continue with the
+    /// hydration ids from before the scope
+    pub fn render_scope_with_new_hydration<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+        scope: ScopeId,
+    ) -> std::fmt::Result {
+        let dynamic_node_id = std::mem::take(&mut self.dynamic_node_id);
+        let result = self.render_scope(buf, dom, scope);
+        self.dynamic_node_id = dynamic_node_id;
+        result
+    }
+
     pub fn render_scope<W: Write + ?Sized>(
         &mut self,
         buf: &mut W,
@@ -12,6 +12,7 @@ use wasm_bindgen::JsCast as _;
 /// ```rust, ignore
 /// dioxus_web::launch(App, Config::new().hydrate(true).root_name("myroot"))
 /// ```
+#[derive(Clone)]
 pub struct Config {
     pub(crate) hydrate: bool,
     pub(crate) root: ConfigRoot,
@@ -21,6 +22,7 @@ pub struct Config {
 
 impl LaunchConfig for Config {}
 
+#[derive(Clone)]
 pub(crate) enum ConfigRoot {
     RootName(String),
     RootNode(web_sys::Node),
@@ -75,11 +77,20 @@ impl Config {
     /// Set the history provider for the application.
     ///
     /// `dioxus-web` provides two history providers:
-    /// - `dioxus_history::WebHistory`: A history provider that uses the browser history API.
-    /// - `dioxus_history::HashHistory`: A history provider that uses the `#` url fragment.
+    /// - [`crate::WebHistory`]: A history provider that uses the browser history API.
+    /// - [`crate::HashHistory`]: A history provider that uses the `#` url fragment.
     ///
-    /// By default, `dioxus-web` uses the `WebHistory` provider, but this method can be used to configure
-    /// a different history provider.
+    /// By default, `dioxus-web` uses the history set with the `history` option in the `[web.app]`
+    /// section of Dioxus.toml, or the `WebHistory` provider if it isn't set. This method can be used
+    /// to configure a different history provider.
+    ///
+    /// ```rust, ignore
+    /// use std::rc::Rc;
+    /// use dioxus_web::{Config, HashHistory};
+    ///
+    /// // Keep the route after `#!` in the url
+    /// let config = Config::new().history(Rc::new(HashHistory::new(Some("!".to_string()), false)));
+    /// ```
     pub fn history(mut self, history: Rc<dyn dioxus_history::History>) -> Self {
         #[cfg(feature = "document")]
         {
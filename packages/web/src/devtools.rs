@@ -3,11 +3,13 @@
 //! This sets up a websocket connection to the devserver and handles messages from it.
 //! We also set up a little recursive timer that will attempt to reconnect if the connection is lost.
 
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::time::Duration;
 
 use dioxus_devtools::{DevserverMsg, HotReloadMsg};
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
 use js_sys::JsString;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::{closure::Closure, JsValue};
@@ -21,15 +23,41 @@ const POLL_INTERVAL_SCALE_FACTOR: i32 = 2;
 const TOAST_TIMEOUT: Duration = Duration::from_secs(5);
 const TOAST_TIMEOUT_LONG: Duration = Duration::from_secs(3600); // Duration::MAX is too long for JS.
 
+thread_local! {
+    /// The virtual doms on the page that receive hot reload messages. Pages with islands run a virtual dom for every
+    /// island, but they all share a single connection to the devserver.
+    static SUBSCRIBERS: RefCell<Option<Vec<UnboundedSender<HotReloadMsg>>>> = const { RefCell::new(None) };
+}
+
 pub(crate) fn init() -> UnboundedReceiver<HotReloadMsg> {
     // Create the tx/rx pair that we'll use for the top-level future in the dioxus loop
     let (tx, rx) = unbounded();
 
-    // Wire up the websocket to the devserver
+    let connected = SUBSCRIBERS.with_borrow_mut(|subscribers| {
+        let connected = subscribers.is_some();
+        subscribers.get_or_insert_with(Vec::new).push(tx);
+        connected
+    });
+    if connected {
+        return rx;
+    }
+
+    // Wire up the websocket to the devserver and forward its messages to every virtual dom
+    let (tx, mut messages) = unbounded::<HotReloadMsg>();
     make_ws(tx.clone(), POLL_INTERVAL_MIN, false);
 
     playground(tx);
 
+    wasm_bindgen_futures::spawn_local(async move {
+        while let Some(msg) = messages.next().await {
+            SUBSCRIBERS.with_borrow_mut(|subscribers| {
+                if let Some(subscribers) = subscribers {
+                    subscribers.retain(|tx| tx.unbounded_send(msg.clone()).is_ok());
+                }
+            });
+        }
+    });
+
     rx
 }
 
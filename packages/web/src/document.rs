@@ -9,6 +9,7 @@ use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
 use js_sys::Function;
 use serde::Serialize;
 use serde_json::Value;
+use std::cell::OnceCell;
 use std::future::Future;
 use std::pin::Pin;
 use std::result;
@@ -16,7 +17,7 @@ use std::{rc::Rc, str::FromStr};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::history::WebHistory;
+use crate::history::{HashHistory, WebHistory};
 
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub struct JSOwner {
@@ -72,12 +73,28 @@ pub fn init_document() {
     if ScopeId::ROOT.has_context::<Rc<dyn Document>>().is_none() {
         ScopeId::ROOT.provide_context(provider);
     }
-    let history_provider: Rc<dyn History> = Rc::new(WebHistory::default());
     if ScopeId::ROOT.has_context::<Rc<dyn History>>().is_none() {
-        ScopeId::ROOT.provide_context(history_provider);
+        ScopeId::ROOT.provide_context(default_history());
     }
 }
 
+thread_local! {
+    static DEFAULT_HISTORY: OnceCell<Rc<dyn History>> = const { OnceCell::new() };
+}
+
+/// The history set in Dioxus.toml. Every virtual dom on the page shares the same history so islands don't each
+/// listen to the browser history on their own.
+fn default_history() -> Rc<dyn History> {
+    DEFAULT_HISTORY.with(|history| {
+        history
+            .get_or_init(|| match dioxus_cli_config::web_history().as_deref() {
+                Some("hash") => Rc::new(HashHistory::default()),
+                _ => Rc::new(WebHistory::default()),
+            })
+            .clone()
+    })
+}
+
 /// The web-target's document provider.
 #[derive(Clone)]
 pub struct WebDocument;
@@ -86,6 +103,11 @@ impl Document for WebDocument {
         Eval::new(WebEvaluator::create(js))
     }
 
+    /// Apply the edits of the next render inside of a view transition
+    fn start_view_transition(&self, types: Vec<String>) {
+        crate::view_transition::queue_view_transition(types);
+    }
+
     /// Set the title of the document
     fn set_title(&self, title: String) {
         let myself = self.clone();
@@ -48,6 +48,9 @@ pub struct WebsysDom {
 
     #[cfg(feature = "hydrate")]
     pub(crate) suspense_hydration_ids: crate::hydration::SuspenseHydrationIds,
+
+    #[cfg(feature = "hydrate")]
+    pub(crate) lazy_hydration: crate::hydration::LazyHydrationBoundaries,
 }
 
 impl WebsysDom {
@@ -129,6 +132,8 @@ impl WebsysDom {
             skip_mutations: false,
             #[cfg(feature = "hydrate")]
             suspense_hydration_ids: Default::default(),
+            #[cfg(feature = "hydrate")]
+            lazy_hydration: Default::default(),
         }
     }
 }
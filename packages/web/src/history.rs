@@ -1,3 +1,8 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 use web_sys::{window, Event, History, ScrollRestoration, Window};
 
@@ -14,7 +19,7 @@ use web_sys::{window, Event, History, ScrollRestoration, Window};
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
 pub struct WebHistory {
-    do_scroll_restoration: bool,
+    do_scroll_restoration: Rc<Cell<bool>>,
     history: History,
     prefix: Option<String>,
     window: Window,
@@ -22,6 +27,7 @@ pub struct WebHistory {
 
 impl Default for WebHistory {
     fn default() -> Self {
+        // dioxus-router disables scroll restoration when it manages scrolling itself
         Self::new(None, true)
     }
 }
@@ -39,7 +45,14 @@ impl WebHistory {
         let prefix_str = myself.prefix.as_deref().unwrap_or("");
         let current_url = format!("{prefix_str}{current_route_str}");
         let state = myself.create_state();
-        let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        // Reloading the page keeps the state of the entry
+        let entry_state = get_current_state(&myself.history);
+        let _ = replace_state_with_url(
+            &myself.history,
+            &state,
+            entry_state.as_deref(),
+            Some(&current_url),
+        );
 
         myself
     }
@@ -66,7 +79,7 @@ impl WebHistory {
             .map(|prefix| format!("/{prefix}"));
 
         Self {
-            do_scroll_restoration,
+            do_scroll_restoration: Rc::new(Cell::new(do_scroll_restoration)),
             history,
             prefix,
             window,
@@ -75,6 +88,7 @@ impl WebHistory {
 
     fn scroll_pos(&self) -> ScrollPosition {
         self.do_scroll_restoration
+            .get()
             .then(|| ScrollPosition::of_window(&self.window))
             .unwrap_or_default()
     }
@@ -85,7 +99,7 @@ impl WebHistory {
     }
 
     fn handle_nav(&self) {
-        if self.do_scroll_restoration {
+        if self.do_scroll_restoration.get() {
             self.window.scroll_to_with_x_and_y(0.0, 0.0)
         }
     }
@@ -112,6 +126,46 @@ impl WebHistory {
             Some(prefix) => format!("{prefix}{state}"),
         }
     }
+
+    fn push_entry(&self, route: String, entry_state: Option<&str>) {
+        if route == dioxus_history::History::current_route(self) {
+            // don't push the same state twice, but keep the state that was attached to it
+            if entry_state.is_some() {
+                self.replace_entry(route, entry_state);
+            }
+            return;
+        }
+
+        let w = window().expect("access to `window`");
+        let h = w.history().expect("`window` has access to `history`");
+
+        // update the scroll position before pushing the new state
+        update_scroll(&w, &h);
+
+        if push_state_and_url(
+            &self.history,
+            &self.create_state(),
+            entry_state,
+            self.full_path(&route),
+        )
+        .is_ok()
+        {
+            self.handle_nav();
+        }
+    }
+
+    fn replace_entry(&self, route: String, entry_state: Option<&str>) {
+        if replace_state_with_url(
+            &self.history,
+            &self.create_state(),
+            entry_state,
+            Some(&self.full_path(&route)),
+        )
+        .is_ok()
+        {
+            self.handle_nav();
+        }
+    }
 }
 
 impl dioxus_history::History for WebHistory {
@@ -132,46 +186,41 @@ impl dioxus_history::History for WebHistory {
     }
 
     fn push(&self, state: String) {
-        if state == self.current_route() {
-            // don't push the same state twice
-            return;
-        }
+        self.push_entry(state, None);
+    }
 
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
+    fn replace(&self, state: String) {
+        self.replace_entry(state, None);
+    }
 
-        // update the scroll position before pushing the new state
-        update_scroll(&w, &h);
+    fn push_with_state(&self, route: String, state: String) {
+        self.push_entry(route, Some(&state));
+    }
 
-        if push_state_and_url(&self.history, &self.create_state(), self.full_path(&state)).is_ok() {
-            self.handle_nav();
-        }
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace_entry(path, Some(&state));
     }
 
-    fn replace(&self, state: String) {
-        if replace_state_with_url(
-            &self.history,
-            &self.create_state(),
-            Some(&self.full_path(&state)),
-        )
-        .is_ok()
-        {
-            self.handle_nav();
-        }
+    fn current_state(&self) -> Option<String> {
+        get_current_state(&self.history)
     }
 
     fn external(&self, url: String) -> bool {
         self.window.location().set_href(&url).is_ok()
     }
 
+    fn disable_scroll_restoration(&self) {
+        self.do_scroll_restoration.set(false);
+    }
+
     fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
         let h = self.history.clone();
-        let d = self.do_scroll_restoration;
+        let d = self.do_scroll_restoration.clone();
 
         let function = Closure::wrap(Box::new(move |_| {
             (*callback)();
-            if d {
+            if d.get() {
                 if let Some([x, y]) = get_current(&h) {
                     ScrollPosition { x, y }.scroll_to(w.clone())
                 }
@@ -187,17 +236,35 @@ impl dioxus_history::History for WebHistory {
 }
 
 /// A [`dioxus_history::History`] provider that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
-/// but uses the url fragment for the route. This allows serving as a single html file or on a single url path.
+/// but keeps the route after the `#` of the url. Every route is served from the same html file, so this
+/// works on static hosts that don't fall back to `index.html` for unknown paths and from `file://` urls.
+///
+/// # Prefix
+/// This [`dioxus_history::History`] supports a prefix, which is written between the `#` and the route.
+/// A prefix of `!` results in urls like `index.html#!/blog`.
+///
+/// # Hash fragments
+/// The hash fragment of a route follows a second `#`, so the route `/blog#comments` is stored as
+/// `index.html#/blog#comments`. The router always percent encodes `#` in the path and query of a
+/// route, so the first `#` after the route starts its fragment.
+///
+/// Anchors that don't start with a route, like `<a href="#comments">`, keep the current route and
+/// only replace its fragment.
 pub struct HashHistory {
-    do_scroll_restoration: bool,
+    do_scroll_restoration: Rc<Cell<bool>>,
     history: History,
+    /// The path and query of the html file the app is served from
     pathname: String,
+    prefix: String,
+    /// The route of the last url that contained a route, used for anchors without one
+    last_route: Rc<RefCell<String>>,
     window: Window,
 }
 
 impl Default for HashHistory {
     fn default() -> Self {
-        Self::new(true)
+        // dioxus-router disables scroll restoration when it manages scrolling itself
+        Self::new(None, true)
     }
 }
 
@@ -206,23 +273,31 @@ impl HashHistory {
     ///
     /// If `do_scroll_restoration` is [`true`], [`HashHistory`] will take control of the history
     /// state. It'll also set the browsers scroll restoration to `manual`.
-    pub fn new(do_scroll_restoration: bool) -> Self {
-        let myself = Self::new_inner(do_scroll_restoration);
+    pub fn new(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
+        let myself = Self::new_inner(prefix, do_scroll_restoration);
 
         let current_route = dioxus_history::History::current_route(&myself);
-        let current_route_str = current_route.to_string();
-        let pathname_str = &myself.pathname;
-        let current_url = format!("{pathname_str}#{current_route_str}");
+        let current_url = myself.full_path(&current_route);
+        *myself.last_route.borrow_mut() = current_route;
         let state = myself.create_state();
-        let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        // Reloading the page keeps the state of the entry
+        let entry_state = get_current_state(&myself.history);
+        let _ = replace_state_with_url(
+            &myself.history,
+            &state,
+            entry_state.as_deref(),
+            Some(&current_url),
+        );
 
         myself
     }
 
-    fn new_inner(do_scroll_restoration: bool) -> Self {
+    fn new_inner(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
         let window = window().expect("access to `window`");
         let history = window.history().expect("`window` has access to `history`");
-        let pathname = window.location().pathname().unwrap();
+        let location = window.location();
+        let pathname =
+            location.pathname().unwrap_or_default() + &location.search().unwrap_or_default();
 
         if do_scroll_restoration {
             history
@@ -230,16 +305,26 @@ impl HashHistory {
                 .expect("`history` can set scroll restoration");
         }
 
+        // Routes start with a slash, so the prefix shouldn't end with one
+        let prefix = prefix
+            .as_deref()
+            .map(|prefix| prefix.trim_end_matches('/'))
+            .unwrap_or_default()
+            .to_string();
+
         Self {
-            do_scroll_restoration,
+            do_scroll_restoration: Rc::new(Cell::new(do_scroll_restoration)),
             history,
             pathname,
+            prefix,
+            last_route: Rc::new(RefCell::new("/".to_string())),
             window,
         }
     }
 
     fn scroll_pos(&self) -> ScrollPosition {
         self.do_scroll_restoration
+            .get()
             .then(|| ScrollPosition::of_window(&self.window))
             .unwrap_or_default()
     }
@@ -249,32 +334,67 @@ impl HashHistory {
         [scroll.x, scroll.y]
     }
 
-    fn full_path(&self, state: &String) -> String {
-        format!("{}#{state}", self.pathname)
+    fn full_path(&self, state: &str) -> String {
+        format!("{}#{}{state}", self.pathname, self.prefix)
     }
 
     fn handle_nav(&self) {
-        if self.do_scroll_restoration {
+        if self.do_scroll_restoration.get() {
             self.window.scroll_to_with_x_and_y(0.0, 0.0)
         }
     }
+
+    /// Change the url. Browsers that don't allow changing the history of `file://` urls fall back
+    /// to setting the hash of the location.
+    fn set_url(&self, state: &str, entry_state: Option<&str>, replace: bool) {
+        *self.last_route.borrow_mut() = state.to_string();
+        let url = self.full_path(state);
+        let result = match replace {
+            true => {
+                replace_state_with_url(&self.history, &self.create_state(), entry_state, Some(&url))
+            }
+            false => push_state_and_url(&self.history, &self.create_state(), entry_state, url),
+        };
+        match result {
+            Ok(()) => self.handle_nav(),
+            Err(_) => {
+                let _ = self
+                    .window
+                    .location()
+                    .set_hash(&format!("{}{state}", self.prefix));
+            }
+        }
+    }
+
+    fn push_entry(&self, route: String, entry_state: Option<&str>) {
+        if route == dioxus_history::History::current_route(self) {
+            // don't push the same state twice, but keep the state that was attached to it
+            if entry_state.is_some() {
+                self.set_url(&route, entry_state, true);
+            }
+            return;
+        }
+
+        let w = window().expect("access to `window`");
+        let h = w.history().expect("`window` has access to `history`");
+
+        // update the scroll position before pushing the new state
+        update_scroll(&w, &h);
+
+        self.set_url(&route, entry_state, false);
+    }
 }
 
 impl dioxus_history::History for HashHistory {
     fn current_route(&self) -> String {
-        let location = self.window.location();
-
-        let hash = location.hash().unwrap();
-        if hash.is_empty() {
-            // If the path is empty, parse the root route instead
-            "/".to_owned()
-        } else {
-            hash.trim_start_matches("#").to_owned()
-        }
+        let hash = self.window.location().hash().unwrap_or_default();
+        let fragment = hash.strip_prefix('#').unwrap_or(&hash);
+        hash_route(fragment, &self.prefix)
+            .unwrap_or_else(|| anchor_route(&self.last_route.borrow(), fragment))
     }
 
     fn current_prefix(&self) -> Option<String> {
-        Some(format!("{}#", self.pathname))
+        Some(format!("{}#{}", self.pathname, self.prefix))
     }
 
     fn go_back(&self) {
@@ -286,46 +406,67 @@ impl dioxus_history::History for HashHistory {
     }
 
     fn push(&self, state: String) {
-        if state == self.current_route() {
-            // don't push the same state twice
-            return;
-        }
+        self.push_entry(state, None);
+    }
 
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
+    fn replace(&self, state: String) {
+        self.set_url(&state, None, true);
+    }
 
-        // update the scroll position before pushing the new state
-        update_scroll(&w, &h);
+    fn push_with_state(&self, route: String, state: String) {
+        self.push_entry(route, Some(&state));
+    }
 
-        if push_state_and_url(&self.history, &self.create_state(), self.full_path(&state)).is_ok() {
-            self.handle_nav();
-        }
+    fn replace_with_state(&self, path: String, state: String) {
+        self.set_url(&path, Some(&state), true);
     }
 
-    fn replace(&self, state: String) {
-        if replace_state_with_url(
-            &self.history,
-            &self.create_state(),
-            Some(&self.full_path(&state)),
-        )
-        .is_ok()
-        {
-            self.handle_nav();
-        }
+    fn current_state(&self) -> Option<String> {
+        get_current_state(&self.history)
     }
 
     fn external(&self, url: String) -> bool {
         self.window.location().set_href(&url).is_ok()
     }
 
+    fn disable_scroll_restoration(&self) {
+        self.do_scroll_restoration.set(false);
+    }
+
     fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
         let h = self.history.clone();
-        let d = self.do_scroll_restoration;
+        let d = self.do_scroll_restoration.clone();
+        let pathname = self.pathname.clone();
+        let prefix = self.prefix.clone();
+        let last_route = self.last_route.clone();
 
         let function = Closure::wrap(Box::new(move |_| {
+            let hash = w.location().hash().unwrap_or_default();
+            let fragment = hash.strip_prefix('#').unwrap_or(&hash);
+            let route = match hash_route(fragment, &prefix) {
+                Some(route) => route,
+                // An anchor without a route only changes the fragment of the current route. Write the route back
+                // into the url so reloading the page keeps it
+                None => {
+                    let route = anchor_route(&last_route.borrow(), fragment);
+                    let scroll = match d.get() {
+                        true => ScrollPosition::of_window(&w),
+                        false => ScrollPosition::default(),
+                    };
+                    let _ = replace_state_with_url(
+                        &h,
+                        &[scroll.x, scroll.y],
+                        get_current_state(&h).as_deref(),
+                        Some(&format!("{pathname}#{prefix}{route}")),
+                    );
+                    route
+                }
+            };
+            *last_route.borrow_mut() = route;
+
             (*callback)();
-            if d {
+            if d.get() {
                 if let Some([x, y]) = get_current(&h) {
                     ScrollPosition { x, y }.scroll_to(w.clone())
                 }
@@ -340,6 +481,23 @@ impl dioxus_history::History for HashHistory {
     }
 }
 
+/// The route in the fragment of a [`HashHistory`] url. Returns [`None`] for anchors that don't start with a route.
+fn hash_route(fragment: &str, prefix: &str) -> Option<String> {
+    match fragment.strip_prefix(prefix) {
+        // If the path is empty, parse the root route instead
+        _ if fragment.is_empty() => Some("/".to_string()),
+        Some("") => Some("/".to_string()),
+        Some(route) if route.starts_with('/') => Some(route.to_string()),
+        _ => None,
+    }
+}
+
+/// The route for an anchor without a route. The anchor replaces the fragment of the last route.
+fn anchor_route(last_route: &str, fragment: &str) -> String {
+    let path = last_route.split('#').next().unwrap_or("/");
+    format!("{path}#{fragment}")
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub(crate) struct ScrollPosition {
     pub x: f64,
@@ -366,26 +524,34 @@ impl ScrollPosition {
     }
 }
 
+/// Create the value of `history.state`: the scroll position followed by the state attached to
+/// the entry, if there is one
+fn history_state(value: &[f64; 2], state: Option<&str>) -> js_sys::Array {
+    let entry = js_sys::Array::new();
+    entry.push(&JsValue::from(value[0]));
+    entry.push(&JsValue::from(value[1]));
+    if let Some(state) = state {
+        entry.push(&JsValue::from_str(state));
+    }
+    entry
+}
+
 pub(crate) fn replace_state_with_url(
     history: &History,
     value: &[f64; 2],
+    state: Option<&str>,
     url: Option<&str>,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-    history.replace_state_with_url(&position, "", url)
+    history.replace_state_with_url(&history_state(value, state), "", url)
 }
 
 pub(crate) fn push_state_and_url(
     history: &History,
     value: &[f64; 2],
+    state: Option<&str>,
     url: String,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-    history.push_state_with_url(&position, "", Some(&url))
+    history.push_state_with_url(&history_state(value, state), "", Some(&url))
 }
 
 pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
@@ -398,7 +564,18 @@ pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
     })
 }
 
+/// Get the state the app attached to the current entry of `history`
+pub(crate) fn get_current_state(history: &History) -> Option<String> {
+    use wasm_bindgen::JsCast;
+    history.state().ok().and_then(|state| {
+        let state = state.dyn_into::<js_sys::Array>().ok()?;
+        state.get(2).as_string()
+    })
+}
+
 fn update_scroll(window: &Window, history: &History) {
     let scroll = ScrollPosition::of_window(window);
-    let _ = replace_state_with_url(history, &[scroll.x, scroll.y], None);
+    // Keep the state of the entry while updating its scroll position
+    let state = get_current_state(history);
+    let _ = replace_state_with_url(history, &[scroll.x, scroll.y], state.as_deref(), None);
 }
@@ -2,18 +2,22 @@
 //! 1. Just hydrate the template on the outside
 //! 2. As we render the virtual dom initially, keep track of the server ids of the suspense boundaries
 //! 3. Register a callback for dx_hydrate(id, data) that takes some new data, reruns the suspense boundary with that new data and then rehydrates the node
+//!
+//! Lazy hydration boundaries are hydrated the same way, except the data is stored on the boundary element and the
+//! hydration starts when the trigger of the boundary fires instead of when the server streams in the data.
 
 use crate::dom::WebsysDom;
 use dioxus_core::{
     AttributeValue, DynamicNode, ElementId, ScopeId, ScopeState, SuspenseBoundaryProps,
     SuspenseContext, TemplateNode, VNode, VirtualDom,
 };
-use dioxus_fullstack_protocol::HydrationContext;
-use futures_channel::mpsc::UnboundedReceiver;
+use dioxus_fullstack_protocol::{HydrationContext, LazyHydration};
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use std::fmt::Write;
+use wasm_bindgen::JsCast;
 use RehydrationError::*;
 
-use super::SuspenseMessage;
+use super::{HydrationMessage, SuspenseMessage};
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -97,7 +101,28 @@ impl SuspenseHydrationIds {
     }
 }
 
+/// The lazy hydration boundaries on the page that are waiting for their trigger to fire
+#[derive(Default)]
+pub(crate) struct LazyHydrationBoundaries {
+    /// The channel the triggers send the boundary to once it should be hydrated
+    sender: Option<UnboundedSender<HydrationMessage>>,
+    /// The suspense boundaries inside of deferred lazy hydration boundaries that were found during the current
+    /// hydration pass. Their triggers are scheduled once the nodes around them are hydrated.
+    discovered: Vec<ScopeId>,
+}
+
 impl WebsysDom {
+    pub(crate) fn rehydrate_message(&mut self, message: HydrationMessage, dom: &mut VirtualDom) {
+        match message {
+            HydrationMessage::Suspense(message) => self.rehydrate_streaming(message, dom),
+            HydrationMessage::Lazy(boundary) => {
+                if let Err(err) = self.rehydrate_lazy(boundary, dom) {
+                    tracing::error!("Lazy rehydration failed. {:?}", err);
+                }
+            }
+        }
+    }
+
     pub fn rehydrate_streaming(&mut self, message: SuspenseMessage, dom: &mut VirtualDom) {
         if let Err(err) = self.rehydrate_streaming_inner(message, dom) {
             tracing::error!("Rehydration failed. {:?}", err);
@@ -186,6 +211,92 @@ impl WebsysDom {
         Ok(())
     }
 
+    fn rehydrate_lazy(
+        &mut self,
+        boundary: ScopeId,
+        dom: &mut VirtualDom,
+    ) -> Result<(), RehydrationError> {
+        // The boundary may have been removed or rerendered on the client since the trigger was scheduled
+        let Some(lazy) = lazy_hydration_context(boundary, dom).filter(LazyHydration::is_deferred)
+        else {
+            return Ok(());
+        };
+        let element = self
+            .lazy_hydration_element(boundary, dom)
+            .ok_or(ElementNotFound)?;
+        let data = take_lazy_hydration_data(&element).ok_or(ElementNotFound)?;
+
+        lazy.hydrate();
+        let server_data = HydrationContext::from_serialized(&data.to_vec(), None, None);
+        server_data.in_context(|| {
+            // Rerun the children now that they are no longer deferred. The placeholder is removed without
+            // replacement because the children are already in the dom
+            SuspenseBoundaryProps::resolve_suspense(
+                boundary,
+                dom,
+                self,
+                |to| {
+                    // Switch to only writing templates
+                    to.skip_mutations = true;
+                },
+                0,
+            );
+            self.skip_mutations = false;
+        });
+
+        // Flush the mutations that remove the placeholder
+        self.flush_edits();
+
+        let Some(scope) = dom.get_scope(boundary) else {
+            return Ok(());
+        };
+        let mut children = Vec::new();
+        let mut current_child = element.first_child();
+        while let Some(node) = current_child {
+            current_child = node.next_sibling();
+            children.push(node);
+        }
+        self.start_hydration_at_scope(scope, dom, children)?;
+
+        // Replay any events the user triggered on the children before they were hydrated
+        finish_lazy_hydration(&element);
+
+        Ok(())
+    }
+
+    /// Get the element a lazy hydration boundary renders its children into from the suspense boundary inside of it
+    fn lazy_hydration_element(
+        &self,
+        boundary: ScopeId,
+        dom: &VirtualDom,
+    ) -> Option<web_sys::Element> {
+        let parent = dom.in_runtime(|| boundary.parent_scope())?;
+        let id = dom
+            .get_scope(parent)?
+            .try_root_node()?
+            .mounted_root(0, dom)?;
+        self.interpreter
+            .base()
+            .get_node(id.0 as u32)
+            .dyn_into()
+            .ok()
+    }
+
+    /// Wait for the trigger of a deferred lazy hydration boundary before hydrating it
+    fn schedule_lazy_hydration(&self, boundary: ScopeId, dom: &VirtualDom) {
+        let (Some(lazy), Some(element), Some(sender)) = (
+            lazy_hydration_context(boundary, dom),
+            self.lazy_hydration_element(boundary, dom),
+            self.lazy_hydration.sender.clone(),
+        ) else {
+            return;
+        };
+        let hydrate = wasm_bindgen::closure::Closure::once_into_js(move || {
+            _ = sender.unbounded_send(HydrationMessage::Lazy(boundary));
+        });
+        schedule_lazy_hydration(&element, lazy.trigger().as_str(), &hydrate);
+    }
+
     fn start_hydration_at_scope(
         &mut self,
         scope: &ScopeState,
@@ -198,8 +309,24 @@ impl WebsysDom {
         // Recursively rehydrate the nodes under the scope
         self.rehydrate_scope(scope, dom, &mut ids, &mut to_mount)?;
 
+        // In development, check that the server rendered the same nodes as the client before hydrating them
+        #[cfg(debug_assertions)]
+        {
+            let first = match under.as_slice() {
+                [root] if *root == self.root => root.first_child(),
+                nodes => nodes.first().cloned(),
+            };
+            super::mismatch::verify_hydration(scope, dom, first);
+        }
+
+        // The nodes inside of deferred lazy hydration boundaries are hydrated later with their own ids
+        hide_lazy_hydration_markers(under.clone());
         self.interpreter.base().hydrate(ids, under);
 
+        for boundary in std::mem::take(&mut self.lazy_hydration.discovered) {
+            self.schedule_lazy_hydration(boundary, dom);
+        }
+
         #[cfg(feature = "mounted")]
         for id in to_mount {
             self.send_mount_event(id);
@@ -211,22 +338,23 @@ impl WebsysDom {
     pub fn rehydrate(
         &mut self,
         vdom: &VirtualDom,
-    ) -> Result<UnboundedReceiver<SuspenseMessage>, RehydrationError> {
+    ) -> Result<UnboundedReceiver<HydrationMessage>, RehydrationError> {
         let (mut tx, rx) = futures_channel::mpsc::unbounded();
+        self.lazy_hydration.sender = Some(tx.clone());
         let closure =
             move |path: Vec<u32>,
                   data: js_sys::Uint8Array,
                   #[allow(unused)] debug_types: Option<Vec<String>>,
                   #[allow(unused)] debug_locations: Option<Vec<String>>| {
                 let data = data.to_vec();
-                _ = tx.start_send(SuspenseMessage {
+                _ = tx.start_send(HydrationMessage::Suspense(SuspenseMessage {
                     suspense_path: path,
                     data,
                     #[cfg(debug_assertions)]
                     debug_types,
                     #[cfg(debug_assertions)]
                     debug_locations,
-                });
+                }));
             };
         let closure = wasm_bindgen::closure::Closure::new(closure);
         dioxus_interpreter_js::minimal_bindings::register_rehydrate_chunk_for_streaming_debug(
@@ -253,8 +381,17 @@ impl WebsysDom {
             SuspenseContext::downcast_suspense_boundary_from_scope(&dom.runtime(), scope.id())
         {
             if suspense.has_suspended_tasks() {
-                self.suspense_hydration_ids
-                    .add_suspense_boundary(scope.id());
+                // Deferred lazy hydration boundaries wait for their trigger instead of the server
+                match lazy_hydration_context(scope.id(), dom) {
+                    Some(lazy) => {
+                        if lazy.is_deferred() {
+                            self.lazy_hydration.discovered.push(scope.id());
+                        }
+                    }
+                    None => self
+                        .suspense_hydration_ids
+                        .add_suspense_boundary(scope.id()),
+                }
             }
         }
 
@@ -372,6 +509,143 @@ impl WebsysDom {
     }
 }
 
+/// Get the lazy hydration boundary that wraps a suspense boundary. The lazy hydration context is provided directly
+/// on the parent of the suspense boundary.
+fn lazy_hydration_context(boundary: ScopeId, dom: &VirtualDom) -> Option<LazyHydration> {
+    dom.in_runtime(|| boundary.parent_scope()?.has_context::<LazyHydration>())
+}
+
+#[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
+    const LAZY = "dx-lazy[data-hydration]";
+    const SHOWN = { attribute: "data-node-hydration", comments: ["node-id", "placeholder"] };
+    const HIDDEN = { attribute: "data-lazy-hydration", comments: ["lazy-text", "lazy-empty"] };
+    const INTERACTIONS = ["pointerdown", "click", "focusin", "keydown", "submit"];
+
+    // Get the deferred lazy hydration boundary that hydrates a node. The placeholder in front of the children of a
+    // boundary is hydrated with the nodes around the boundary.
+    function lazyOwner(node) {
+        let parent = node.parentElement;
+        if (parent && parent.matches(LAZY) && parent.firstChild === node) {
+            parent = parent.parentElement;
+        }
+        return parent && parent.closest(LAZY);
+    }
+
+    function renameMarkers(lazy, owned, from, to) {
+        for (const element of lazy.querySelectorAll(`[${from.attribute}]`)) {
+            if (owned(lazyOwner(element))) {
+                element.setAttribute(to.attribute, element.getAttribute(from.attribute));
+                element.removeAttribute(from.attribute);
+            }
+        }
+        const walker = document.createTreeWalker(lazy, NodeFilter.SHOW_COMMENT);
+        while (walker.nextNode()) {
+            const comment = walker.currentNode;
+            const index = from.comments.findIndex((prefix) => comment.textContent.startsWith(prefix));
+            if (index !== -1 && owned(lazyOwner(comment))) {
+                comment.textContent = to.comments[index] + comment.textContent.slice(from.comments[index].length);
+            }
+        }
+    }
+
+    export function hide_lazy_hydration_markers(under) {
+        for (const root of under) {
+            if (!(root instanceof Element)) continue;
+            const owned = (owner) => owner !== null && root.contains(owner);
+            const boundaries = [...root.querySelectorAll(LAZY)];
+            if (root.matches(LAZY)) boundaries.unshift(root);
+            for (const lazy of boundaries) renameMarkers(lazy, owned, SHOWN, HIDDEN);
+        }
+    }
+
+    export function take_lazy_hydration_data(lazy) {
+        renameMarkers(lazy, (owner) => owner === lazy, HIDDEN, SHOWN);
+        const data = lazy.getAttribute("data-hydration");
+        lazy.removeAttribute("data-hydration");
+        return data === null ? undefined : Uint8Array.from(atob(data), (c) => c.charCodeAt(0));
+    }
+
+    export function schedule_lazy_hydration(lazy, trigger, hydrate) {
+        const state = { events: [], cleanup: [] };
+        lazy.dxLazyHydration = state;
+        let started = false;
+        const start = () => {
+            if (!started) {
+                started = true;
+                hydrate();
+            }
+        };
+        if (trigger === "visible" && lazy.children.length > 0) {
+            // The boundary is rendered with display: contents, so it has no box of its own to observe
+            const observer = new IntersectionObserver((entries) => {
+                if (entries.some((entry) => entry.isIntersecting)) start();
+            });
+            for (const child of lazy.children) observer.observe(child);
+            state.cleanup.push(() => observer.disconnect());
+        } else if (trigger === "interaction") {
+            // Hold on to the event until the children are hydrated and can handle it. Clicks and submits are
+            // cancelled so the hydrated handlers can decide if the default action should run when they are replayed
+            const capture = (event) => {
+                event.stopPropagation();
+                if (event.type === "click" || event.type === "submit") event.preventDefault();
+                state.events.push(event);
+                start();
+            };
+            for (const name of INTERACTIONS) {
+                lazy.addEventListener(name, capture, true);
+                state.cleanup.push(() => lazy.removeEventListener(name, capture, true));
+            }
+        } else if ("requestIdleCallback" in window) {
+            const id = requestIdleCallback(start);
+            state.cleanup.push(() => cancelIdleCallback(id));
+        } else {
+            const id = setTimeout(start, 1);
+            state.cleanup.push(() => clearTimeout(id));
+        }
+    }
+
+    export function finish_lazy_hydration(lazy) {
+        const state = lazy.dxLazyHydration;
+        if (!state) return;
+        delete lazy.dxLazyHydration;
+        for (const cleanup of state.cleanup) cleanup();
+        for (const event of state.events) replay(event);
+    }
+
+    // Events created in javascript are not trusted, so the browser doesn't run the default action of a dispatched
+    // click or submit. Those are replayed through the element instead which runs the hydrated handlers first and
+    // then the default action if the handlers didn't prevent it.
+    function replay(event) {
+        const target = event.target;
+        if (!target.isConnected) return;
+        if (event.type === "click" && target instanceof HTMLElement) {
+            target.click();
+        } else if (event.type === "submit" && target instanceof HTMLFormElement) {
+            const submitter = event.submitter && event.submitter.form === target ? event.submitter : null;
+            target.requestSubmit(submitter);
+        } else {
+            // The default action of the other events already ran, only the hydrated handlers still need to see them
+            target.dispatchEvent(new event.constructor(event.type, event));
+        }
+    }
+"#)]
+extern "C" {
+    /// Rename the hydration markers of the nodes inside of deferred lazy hydration boundaries so the hydration of
+    /// the nodes around them skips them
+    fn hide_lazy_hydration_markers(under: Vec<web_sys::Node>);
+    /// Restore the hydration markers of the nodes inside of a lazy hydration boundary and take the hydration data
+    /// the server serialized for them
+    fn take_lazy_hydration_data(lazy: &web_sys::Element) -> Option<js_sys::Uint8Array>;
+    /// Call `hydrate` once the trigger of a lazy hydration boundary fires
+    fn schedule_lazy_hydration(
+        lazy: &web_sys::Element,
+        trigger: &str,
+        hydrate: &wasm_bindgen::JsValue,
+    );
+    /// Stop waiting for the trigger of a lazy hydration boundary and replay the events it captured
+    fn finish_lazy_hydration(lazy: &web_sys::Element);
+}
+
 fn write_comma_separated(id: &[u32], into: &mut String) {
     let mut iter = id.iter();
     if let Some(first) = iter.next() {
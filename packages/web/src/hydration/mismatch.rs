@@ -0,0 +1,338 @@
+//! Development only diagnostics for differences between the html the server rendered and the first render on the
+//! client.
+//!
+//! Before the client hydrates a part of the page, it walks the templates it rendered next to the nodes the server
+//! rendered and reports every difference it finds. Once a list of siblings stops lining up, the rest of that list is
+//! skipped since every node after it would be reported as well. Without this, a mismatch usually only shows up later
+//! as a panic in the interpreter or as events that are attached to the wrong node.
+
+use dioxus_core::{
+    internal::template_location, AttributeValue, DynamicNode, ScopeState, TemplateAttribute,
+    TemplateNode, VNode, VirtualDom,
+};
+use std::fmt::{self, Display};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Node};
+
+/// The longest text that is included in a report before it is cut off
+const MAX_TEXT_LEN: usize = 80;
+
+/// Compare the nodes a scope rendered on the client with the nodes the server rendered starting at `first`. Every
+/// mismatch is logged to the console. In development, the console is forwarded to `dx serve` through the devtools
+/// websocket as a `ClientMsg::Log`.
+pub(crate) fn verify_hydration(scope: &ScopeState, dom: &VirtualDom, first: Option<Node>) {
+    let mut verifier = HydrationVerifier {
+        dom,
+        component: scope.name(),
+        mismatches: Vec::new(),
+    };
+    let mut cursor = first;
+    _ = verifier.verify_vnode(scope.root_node(), &mut cursor);
+
+    for mismatch in verifier.mismatches {
+        web_sys::console::warn_1(&mismatch.to_string().into());
+    }
+}
+
+/// A difference between the html the server rendered and the first render on the client
+#[derive(Debug)]
+struct HydrationMismatch {
+    /// The name of the component that rendered the template
+    component: &'static str,
+    /// The location of the rsx! call that created the template if it is known
+    location: Option<&'static str>,
+    /// What the client rendered
+    expected: String,
+    /// What the server rendered
+    found: String,
+}
+
+impl Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hydration mismatch in the component {}", self.component)?;
+        if let Some(location) = self.location {
+            write!(f, " at {location}")?;
+        }
+        writeln!(
+            f,
+            ". The html the server rendered is different from the first render on the client:"
+        )?;
+        writeln!(f, "- client: {}", self.expected)?;
+        write!(f, "+ server: {}", self.found)
+    }
+}
+
+/// Returned when a list of siblings no longer lines up with the template
+struct Diverged;
+
+struct HydrationVerifier<'a> {
+    dom: &'a VirtualDom,
+    /// The name of the component that rendered the nodes that are currently being verified
+    component: &'static str,
+    mismatches: Vec<HydrationMismatch>,
+}
+
+impl HydrationVerifier<'_> {
+    fn report(&mut self, vnode: &VNode, expected: String, found: String) {
+        self.mismatches.push(HydrationMismatch {
+            component: self.component,
+            location: template_location(&vnode.template),
+            expected,
+            found,
+        });
+    }
+
+    fn diverge(&mut self, vnode: &VNode, expected: String, found: Option<&Node>) -> Diverged {
+        self.report(vnode, expected, describe_node(found));
+        Diverged
+    }
+
+    fn verify_scope(
+        &mut self,
+        scope: &ScopeState,
+        cursor: &mut Option<Node>,
+    ) -> Result<(), Diverged> {
+        let parent = std::mem::replace(&mut self.component, scope.name());
+        let result = self.verify_vnode(scope.root_node(), cursor);
+        self.component = parent;
+        result
+    }
+
+    fn verify_vnode(&mut self, vnode: &VNode, cursor: &mut Option<Node>) -> Result<(), Diverged> {
+        for root in vnode.template.roots {
+            self.verify_template_node(vnode, root, true, cursor)?;
+        }
+        Ok(())
+    }
+
+    fn verify_template_node(
+        &mut self,
+        vnode: &VNode,
+        node: &TemplateNode,
+        is_root: bool,
+        cursor: &mut Option<Node>,
+    ) -> Result<(), Diverged> {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let Some(element) = cursor
+                    .as_ref()
+                    .and_then(|node| node.dyn_ref::<Element>())
+                    .filter(|element| element.local_name().eq_ignore_ascii_case(tag))
+                    .cloned()
+                else {
+                    return Err(self.diverge(vnode, format!("<{tag}>"), cursor.as_ref()));
+                };
+                *cursor = element.next_sibling();
+
+                let has_inner_html = self.verify_attributes(vnode, &element, attrs);
+                // The children of elements with inner html are not part of the template
+                if has_inner_html {
+                    return Ok(());
+                }
+
+                let mut child = element.first_child();
+                let children = children
+                    .iter()
+                    .try_for_each(|node| self.verify_template_node(vnode, node, false, &mut child));
+                // The children of deferred lazy hydration boundaries are hydrated later
+                if let (Ok(()), Some(extra)) = (children, child) {
+                    if !element.has_attribute("data-hydration") {
+                        self.report(
+                            vnode,
+                            format!("the end of <{tag}>"),
+                            describe_node(Some(&extra)),
+                        );
+                    }
+                }
+                Ok(())
+            }
+            TemplateNode::Text { text } => {
+                // Text at the root of a template is wrapped in comments so the client can find it
+                if is_root {
+                    self.expect_comment(vnode, "node-id", text, cursor)?;
+                }
+                self.expect_text(vnode, text, cursor)?;
+                if is_root {
+                    self.expect_comment(vnode, "#", text, cursor)?;
+                }
+                Ok(())
+            }
+            TemplateNode::Dynamic { id } => self.verify_dynamic_node(vnode, *id, cursor),
+        }
+    }
+
+    fn verify_dynamic_node(
+        &mut self,
+        vnode: &VNode,
+        index: usize,
+        cursor: &mut Option<Node>,
+    ) -> Result<(), Diverged> {
+        match &vnode.dynamic_nodes[index] {
+            DynamicNode::Text(text) => {
+                self.expect_comment(vnode, "node-id", &text.value, cursor)?;
+                if !text.value.is_empty() {
+                    self.expect_text(vnode, &text.value, cursor)?;
+                }
+                self.expect_comment(vnode, "#", &text.value, cursor)
+            }
+            DynamicNode::Placeholder(_) => self.expect_comment(vnode, "placeholder", "", cursor),
+            DynamicNode::Component(component) => {
+                let Some(scope) = component.mounted_scope(index, vnode, self.dom) else {
+                    return Ok(());
+                };
+                self.verify_scope(scope, cursor)
+            }
+            DynamicNode::Fragment(fragment) => {
+                for vnode in fragment {
+                    self.verify_vnode(vnode, cursor)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Verify the attributes of an element. Returns true if the element has inner html
+    fn verify_attributes(
+        &mut self,
+        vnode: &VNode,
+        element: &Element,
+        attrs: &[TemplateAttribute],
+    ) -> bool {
+        let mut expected = Vec::new();
+        for attr in attrs {
+            match attr {
+                TemplateAttribute::Static {
+                    name,
+                    value,
+                    namespace,
+                } => expected.push((*name, *namespace, Some(value.to_string()))),
+                TemplateAttribute::Dynamic { id } => {
+                    for attribute in &*vnode.dynamic_attrs[*id] {
+                        let value = match &attribute.value {
+                            AttributeValue::Text(value) => Some(value.clone()),
+                            AttributeValue::Float(value) => Some(value.to_string()),
+                            AttributeValue::Int(value) => Some(value.to_string()),
+                            AttributeValue::Bool(value) => Some(value.to_string()),
+                            _ => None,
+                        };
+                        expected.push((attribute.name, attribute.namespace, value));
+                    }
+                }
+            }
+        }
+
+        let has_inner_html = expected
+            .iter()
+            .any(|(name, _, _)| *name == "dangerous_inner_html");
+        for (name, namespace, value) in &expected {
+            // Styles are merged into a single attribute and attributes that are set more than once are merged
+            // by the renderer, so only attributes that map directly to the html are compared
+            let Some(value) = value else { continue };
+            if namespace.is_some()
+                || *name == "dangerous_inner_html"
+                || expected
+                    .iter()
+                    .filter(|(other, _, _)| other == name)
+                    .count()
+                    > 1
+            {
+                continue;
+            }
+            let tag = element.local_name();
+            match element.get_attribute(name) {
+                Some(found) if found == *value => {}
+                // Boolean attributes are left out of the html when they are falsy
+                None if matches!(value.as_str(), "false" | "" | "0") => {}
+                Some(found) => self.report(
+                    vnode,
+                    format!("<{tag} {name}={value:?}>"),
+                    format!("<{tag} {name}={found:?}>"),
+                ),
+                None => self.report(
+                    vnode,
+                    format!("<{tag} {name}={value:?}>"),
+                    format!("<{tag}> without {name}"),
+                ),
+            }
+        }
+
+        has_inner_html
+    }
+
+    fn expect_text(
+        &mut self,
+        vnode: &VNode,
+        text: &str,
+        cursor: &mut Option<Node>,
+    ) -> Result<(), Diverged> {
+        match cursor.take() {
+            Some(node) if node.node_type() == Node::TEXT_NODE => {
+                *cursor = node.next_sibling();
+                let found = node.text_content().unwrap_or_default();
+                if found != text {
+                    self.report(vnode, describe_text(text), describe_text(&found));
+                }
+                Ok(())
+            }
+            node => Err(self.diverge(vnode, describe_text(text), node.as_ref())),
+        }
+    }
+
+    /// Expect a hydration marker comment that starts with `prefix` in front of or after some text
+    fn expect_comment(
+        &mut self,
+        vnode: &VNode,
+        prefix: &str,
+        text: &str,
+        cursor: &mut Option<Node>,
+    ) -> Result<(), Diverged> {
+        match cursor.take() {
+            Some(node)
+                if node.node_type() == Node::COMMENT_NODE
+                    && node.text_content().unwrap_or_default().starts_with(prefix) =>
+            {
+                *cursor = node.next_sibling();
+                Ok(())
+            }
+            node => {
+                let expected = match prefix {
+                    "placeholder" => "<!--placeholder-->".to_string(),
+                    _ => describe_text(text),
+                };
+                Err(self.diverge(vnode, expected, node.as_ref()))
+            }
+        }
+    }
+}
+
+fn describe_node(node: Option<&Node>) -> String {
+    let Some(node) = node else {
+        return "nothing".to_string();
+    };
+    if let Some(element) = node.dyn_ref::<Element>() {
+        // Only show the opening tag of the element
+        let html = element.outer_html();
+        return match html.find('>') {
+            Some(end) => html[..=end].to_string(),
+            None => html,
+        };
+    }
+    let content = node.text_content().unwrap_or_default();
+    match node.node_type() {
+        Node::TEXT_NODE => describe_text(&content),
+        Node::COMMENT_NODE => format!("<!--{content}-->"),
+        _ => node.node_name(),
+    }
+}
+
+fn describe_text(text: &str) -> String {
+    match text.char_indices().nth(MAX_TEXT_LEN) {
+        Some((end, _)) => format!("{:?}...", &text[..end]),
+        None => format!("{text:?}"),
+    }
+}
@@ -5,6 +5,9 @@ mod hydrate;
 #[allow(unused)]
 pub use hydrate::*;
 
+#[cfg(all(feature = "hydrate", debug_assertions))]
+mod mismatch;
+
 /// The message sent from the server to the client to hydrate a suspense boundary
 #[derive(Debug)]
 pub(crate) struct SuspenseMessage {
@@ -23,3 +26,14 @@ pub(crate) struct SuspenseMessage {
     /// The location of the data in the source code
     debug_locations: Option<Vec<String>>,
 }
+
+/// A message that hydrates part of the page after the initial hydration
+#[derive(Debug)]
+#[cfg_attr(not(feature = "hydrate"), allow(dead_code))]
+pub(crate) enum HydrationMessage {
+    /// The server streamed in the resolved contents of a suspense boundary
+    Suspense(SuspenseMessage),
+    #[cfg(feature = "hydrate")]
+    /// The trigger of the lazy hydration boundary with this suspense boundary fired
+    Lazy(dioxus_core::ScopeId),
+}
@@ -7,7 +7,7 @@
 use std::time::Duration;
 
 pub use crate::cfg::Config;
-use crate::hydration::SuspenseMessage;
+use crate::hydration::HydrationMessage;
 use dioxus_core::VirtualDom;
 use dom::WebsysDom;
 use futures_util::{pin_mut, select, FutureExt, StreamExt};
@@ -32,6 +32,8 @@ pub use document::WebDocument;
 pub use file_engine::*;
 #[cfg(feature = "document")]
 pub use history::{HashHistory, WebHistory};
+#[cfg(feature = "document")]
+mod view_transition;
 
 #[cfg(all(feature = "devtools", debug_assertions))]
 mod devtools;
@@ -68,7 +70,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
 
     let mut websys_dom = WebsysDom::new(web_config, runtime);
 
-    let mut hydration_receiver: Option<futures_channel::mpsc::UnboundedReceiver<SuspenseMessage>> =
+    let mut hydration_receiver: Option<futures_channel::mpsc::UnboundedReceiver<HydrationMessage>> =
         None;
 
     if should_hydrate {
@@ -99,10 +101,13 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
             websys_dom.skip_mutations = true;
             // Get the initial hydration data from the client
             #[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
-                export function get_initial_hydration_data() {
-                    const decoded = atob(window.initial_dioxus_hydration_data);
+                export function decode_hydration_data(data) {
+                    const decoded = atob(data);
                     return Uint8Array.from(decoded, (c) => c.charCodeAt(0))
                 }
+                export function get_initial_hydration_data() {
+                    return decode_hydration_data(window.initial_dioxus_hydration_data);
+                }
                 export function get_initial_hydration_debug_types() {
                     return window.initial_dioxus_hydration_debug_types;
                 }
@@ -111,19 +116,35 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
                 }
             "#)]
             extern "C" {
+                fn decode_hydration_data(data: &str) -> js_sys::Uint8Array;
                 fn get_initial_hydration_data() -> js_sys::Uint8Array;
                 fn get_initial_hydration_debug_types() -> Option<Vec<String>>;
                 fn get_initial_hydration_debug_locations() -> Option<Vec<String>>;
             }
-            let hydration_data = get_initial_hydration_data().to_vec();
+            // Islands are rendered with their own hydration data on the root element
+            use wasm_bindgen::JsCast;
+            let island_data = websys_dom
+                .root
+                .dyn_ref::<web_sys::Element>()
+                .and_then(|root| root.get_attribute("data-hydration"));
+            let hydration_data = match &island_data {
+                Some(data) => decode_hydration_data(data).to_vec(),
+                None => get_initial_hydration_data().to_vec(),
+            };
 
             // If we are running in debug mode, also get the debug types and locations
             #[cfg(debug_assertions)]
-            let debug_types = get_initial_hydration_debug_types();
+            let debug_types = island_data
+                .is_none()
+                .then(get_initial_hydration_debug_types)
+                .flatten();
             #[cfg(not(debug_assertions))]
             let debug_types = None;
             #[cfg(debug_assertions)]
-            let debug_locations = get_initial_hydration_debug_locations();
+            let debug_locations = island_data
+                .is_none()
+                .then(get_initial_hydration_debug_locations)
+                .flatten();
             #[cfg(not(debug_assertions))]
             let debug_locations = None;
 
@@ -163,7 +184,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         #[cfg(all(feature = "devtools", debug_assertions))]
         let template;
         #[allow(unused)]
-        let mut hydration_work: Option<SuspenseMessage> = None;
+        let mut hydration_work: Option<HydrationMessage> = None;
 
         {
             let work = virtual_dom.wait_for_work().fuse();
@@ -232,7 +253,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
 
         #[cfg(feature = "hydrate")]
         if let Some(hydration_data) = hydration_work {
-            websys_dom.rehydrate_streaming(hydration_data, &mut virtual_dom);
+            websys_dom.rehydrate_message(hydration_data, &mut virtual_dom);
         }
 
         // Todo: This is currently disabled because it has a negative impact on response times for events but it could be re-enabled for tasks
@@ -252,6 +273,9 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         // wait for the animation frame to fire so we can apply our changes
         // work_loop.wait_for_raf().await;
 
+        #[cfg(feature = "document")]
+        view_transition::flush_edits(&mut websys_dom).await;
+        #[cfg(not(feature = "document"))]
         websys_dom.flush_edits();
     }
 }
@@ -0,0 +1,67 @@
+//! Apply the edits of a render inside of a [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API).
+
+use std::cell::RefCell;
+
+use js_sys::{Array, Function, Promise};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::dom::WebsysDom;
+
+thread_local! {
+    /// The types of the view transition the next flush should run in
+    static PENDING: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+#[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
+    export function start_view_transition(types) {
+        if (!document.startViewTransition) return null;
+        let ready;
+        let finish;
+        const readyPromise = new Promise((resolve) => (ready = resolve));
+        const finished = new Promise((resolve) => (finish = resolve));
+        const update = () => {
+            ready();
+            return finished;
+        };
+        try {
+            document.startViewTransition({ update, types });
+        } catch (_) {
+            // Browsers without transition types only accept the update callback
+            document.startViewTransition(update);
+        }
+        return [readyPromise, finish];
+    }
+"#)]
+extern "C" {
+    fn start_view_transition(types: Vec<String>) -> Option<Array>;
+}
+
+/// Run the next flush of edits inside of a view transition
+pub(crate) fn queue_view_transition(types: Vec<String>) {
+    PENDING.with(|pending| match &mut *pending.borrow_mut() {
+        Some(pending) => pending.extend(types),
+        pending => *pending = Some(types),
+    });
+}
+
+/// Flush the edits of the dom. If a view transition was queued, the edits are applied once the
+/// browser captured the old state of the page.
+pub(crate) async fn flush_edits(websys_dom: &mut WebsysDom) {
+    let Some(types) = PENDING.with(|pending| pending.borrow_mut().take()) else {
+        websys_dom.flush_edits();
+        return;
+    };
+
+    // Browsers without view transitions update the page right away
+    let Some(transition) = start_view_transition(types) else {
+        websys_dom.flush_edits();
+        return;
+    };
+
+    let ready: Promise = transition.get(0).unchecked_into();
+    let finish: Function = transition.get(1).unchecked_into();
+    _ = JsFuture::from(ready).await;
+    websys_dom.flush_edits();
+    _ = finish.call0(&wasm_bindgen::JsValue::NULL);
+}